@@ -35,6 +35,14 @@ pub async fn start_server(
     let db = Arc::new(Database::new()?);
     info!("Database initialized");
     
+    // Rips that were running when the server stopped go back in the queue; they resume from
+    // their checkpoints when the disc is (re)detected
+    match db.reconcile_stale_queue_entries() {
+        Ok(0) => {}
+        Ok(count) => info!("Requeued {} rip(s) interrupted by a restart", count),
+        Err(e) => tracing::warn!("Failed to reconcile rip queue: {}", e),
+    }
+    
     // Create shared state
     let state = ApiState {
         config: Arc::new(RwLock::new(config)),
@@ -42,6 +50,7 @@ pub async fn start_server(
         event_tx: event_tx.clone(),
         db: Arc::clone(&db),
         operations: Arc::new(RwLock::new(std::collections::HashMap::new())),
+        metadata_choices: Arc::default(),
     };
     
     // Spawn background task to log events to database
@@ -188,6 +197,7 @@ pub async fn start_server(
                         if has_media && (!was_known || !had_media) {
                             let state_for_rip = state_for_poller.clone();
                             let device_for_rip = device.clone();
+                            let media_type_for_rip = drive_info.media_type.clone();
                             
                             // Check if already ripping this drive
                             let is_ripping = {
@@ -202,6 +212,30 @@ pub async fn start_server(
                                     // Small delay to ensure drive is ready
                                     tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                                     
+                                    // A queued (or interrupted) rip for this drive keeps its own settings
+                                    if let Ok(Some(entry)) = state_clone.db.get_next_queue_entry(Some(&device_for_rip)) {
+                                        info!("Starting queued rip {:?} for {}", entry.id, device_for_rip);
+                                        process_queue(state_clone, device_for_rip).await;
+                                        return;
+                                    }
+                                    
+                                    // A disc ripped before waits for the user to skip it, re-rip it or reuse its assignment
+                                    if let Some((fingerprint, previous)) = find_known_disc(&state_clone, &device_for_rip, &media_type_for_rip).await {
+                                        info!("Disc in {} was ripped before ({})", device_for_rip, fingerprint);
+                                        send_log_to_web_ui(&state_clone, &device_for_rip, "warning", format!(
+                                            "💿 This disc was already ripped{} on {} - skip it, re-rip it or reuse the assignment",
+                                            previous.title.as_deref().map(|t| format!(" as '{}'", t)).unwrap_or_default(),
+                                            previous.timestamp.format("%Y-%m-%d"),
+                                        ), None).await;
+                                        let _ = state_clone.event_tx.send(ApiEvent::KnownDiscInserted {
+                                            drive: device_for_rip.clone(),
+                                            fingerprint,
+                                            previous,
+                                            actions: vec![KnownDiscAction::Skip, KnownDiscAction::Rerip, KnownDiscAction::Reuse],
+                                        });
+                                        return;
+                                    }
+                                    
                                     let rip_request = StartRipRequest {
                                         drive: Some(device_for_rip.clone()),
                                         output_path: None,
@@ -210,6 +244,9 @@ pub async fn start_server(
                                         skip_filebot: false,
                                         profile: None,
                                         priority: None,
+                                        titles: None,
+                                        session_id: None,
+                                        reuse_fingerprint: None,
                                     };
                                     
                                     // Call the internal start_rip logic
@@ -292,6 +329,14 @@ pub struct ApiState {
     pub event_tx: broadcast::Sender<ApiEvent>,
     pub db: Arc<Database>,
     pub operations: Arc<RwLock<std::collections::HashMap<String, Operation>>>,
+    pub metadata_choices: Arc<RwLock<std::collections::HashMap<String, PendingMetadataChoice>>>, // By drive
+}
+
+/// Metadata candidates offered for the disc in a drive, waiting for the user to pick one
+pub struct PendingMetadataChoice {
+    pub query: String,
+    pub candidates: Vec<crate::dvd_metadata::MetadataCandidate>,
+    reply: tokio::sync::oneshot::Sender<Option<usize>>, // Index into candidates; None for "none of these"
 }
 
 /// Per-drive ripping status
@@ -302,6 +347,8 @@ pub struct DriveRipStatus {
     pub progress: f32,
     pub paused: bool,
     pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip)]
+    pub control: crate::rip_backend::RipControl, // Pauses, resumes or stops the backend's ripping tool
 }
 
 /// Operation type enum
@@ -340,6 +387,36 @@ pub struct Operation {
     pub started_at: chrono::DateTime<chrono::Utc>,
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<SessionProgress>, // Set when the rip belongs to a box-set session
+}
+
+/// Box-set session progress of a rip operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionProgress {
+    pub session_id: i64,
+    pub show_name: String,
+    pub season: u32,
+    pub disc: u32,
+    pub expected_discs: u32,
+    pub first_episode: u32,
+    pub episodes_ripped: u32,
+    pub summary: String, // e.g. "disc 3 of 5, episodes 9–12"
+}
+
+impl SessionProgress {
+    pub fn new(session: &crate::database::RipSession, episodes_ripped: u32) -> Self {
+        Self {
+            session_id: session.id.unwrap_or_default(),
+            show_name: session.show_name.clone(),
+            season: session.season,
+            disc: session.current_disc(),
+            expected_discs: session.expected_discs,
+            first_episode: session.next_episode,
+            episodes_ripped,
+            summary: session.summary(episodes_ripped),
+        }
+    }
 }
 
 /// Generate a unique operation ID
@@ -379,6 +456,7 @@ async fn create_operation(
         started_at: chrono::Utc::now(),
         completed_at: None,
         error: None,
+        session: None,
     };
     
     // Add to operations map
@@ -417,6 +495,14 @@ async fn update_operation(
     }
 }
 
+/// Attach box-set session progress to an operation
+async fn update_operation_session(state: &ApiState, operation_id: &str, session: SessionProgress) {
+    let mut operations = state.operations.write().await;
+    if let Some(op) = operations.get_mut(operation_id) {
+        op.session = Some(session);
+    }
+}
+
 /// Complete an operation (success)
 async fn complete_operation(
     state: &ApiState,
@@ -557,6 +643,17 @@ pub enum ApiEvent {
     OperationFailed { operation_id: String, error: String },
     AgentStatusChanged { agent_id: String, status: String, last_seen: String, operation_id: Option<String> },
     UpscalingJobStatusChanged { job_id: String, status: String, progress: f32, error_message: Option<String>, operation_id: Option<String> },
+    KnownDiscInserted { drive: String, fingerprint: String, previous: crate::database::RipHistory, actions: Vec<KnownDiscAction> },
+    MetadataChoiceRequired { drive: String, query: String, candidates: Vec<crate::dvd_metadata::MetadataCandidate>, operation_id: Option<String> },
+}
+
+/// What to do with an inserted disc that has been ripped before
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KnownDiscAction {
+    Skip,   // Leave the disc alone
+    Rerip,  // Rip it again from scratch
+    Reuse,  // Rip it again with the previous rip's show
 }
 
 /// Request body for starting a rip operation
@@ -569,6 +666,12 @@ pub struct StartRipRequest {
     pub skip_filebot: bool,
     pub profile: Option<String>, // Optional profile name
     pub priority: Option<i32>, // Optional priority for queue (higher = higher priority, default 0)
+    #[serde(default)]
+    pub titles: Option<Vec<u32>>, // Explicit title indices to rip (e.g. picked from /drives/:device/scan)
+    #[serde(default)]
+    pub session_id: Option<i64>, // Box-set session to rip into; rips without one stay out of any session
+    #[serde(default)]
+    pub reuse_fingerprint: Option<String>, // Known disc whose stored show, season and first episode to reuse
 }
 
 /// Response for API errors
@@ -596,6 +699,9 @@ pub fn create_router(state: ApiState) -> Router {
         .route("/rip/stop", post(stop_rip))
         .route("/drives", get(list_drives))
         .route("/drives/:device/eject", post(eject_drive))
+        .route("/drives/:device/scan", get(get_disc_scan))
+        .route("/drives/:device/known-disc", post(resolve_known_disc))
+        .route("/drives/:device/metadata", get(get_metadata_choice).post(choose_metadata))
         .route("/rename", post(rename_files))
         .route("/logs", get(get_logs))
         .route("/logs/search", get(search_logs_handler))
@@ -617,6 +723,8 @@ pub fn create_router(state: ApiState) -> Router {
         .route("/shows/:id", put(update_show))
         .route("/shows/:id", delete(delete_show))
         .route("/shows/:id/select", post(select_show))
+        .route("/shows/:id/title-selection", put(update_show_title_selection))
+        .route("/shows/:id/episode-order", put(update_show_episode_order))
         .route("/statistics", get(get_statistics))
         .route("/statistics/drives", get(get_drive_stats))
         .route("/statistics/errors", get(get_error_frequency))
@@ -626,9 +734,25 @@ pub fn create_router(state: ApiState) -> Router {
         .route("/rip-profiles", get(get_rip_profiles))
         .route("/queue", get(get_queue_handler))
         .route("/queue/:id/cancel", delete(cancel_queue_handler))
+        .route("/rip-checkpoints", get(get_rip_checkpoints_handler))
+        .route("/rip-checkpoints/:fingerprint", delete(clear_rip_checkpoints_handler))
+        .route("/disc-assignments", get(get_disc_assignments_handler))
+        .route("/disc-assignments", post(save_disc_assignment_handler))
+        .route("/disc-assignments/:id", put(update_disc_assignment_handler))
+        .route("/disc-assignments/:id", delete(delete_disc_assignment_handler))
+        .route("/sessions", get(get_rip_sessions_handler))
+        .route("/sessions", post(create_rip_session_handler))
+        .route("/sessions/:id", get(get_rip_session_handler))
+        .route("/sessions/:id", put(update_rip_session_handler))
+        .route("/sessions/:id", delete(delete_rip_session_handler))
         .route("/rip/:drive/pause", put(pause_rip_handler))
         .route("/rip/:drive/resume", put(resume_rip_handler))
         .route("/episode-match-statistics", get(get_episode_match_statistics_handler))
+        .route("/episode-matches/pending", get(get_pending_episode_matches_handler))
+        .route("/episode-matches/:id/accept", post(accept_episode_match_handler))
+        .route("/episode-matches/:id/override", post(override_episode_match_handler))
+        .route("/renames/:batch_id", get(get_rename_batch_handler))
+        .route("/renames/:batch_id/undo", post(undo_rename_batch_handler))
         .route("/database/backup", post(backup_database_handler))
         .route("/database/restore", post(restore_database_handler))
         .route("/monitor/operations", get(get_monitor_operations))
@@ -774,6 +898,7 @@ async fn start_rip_internal(
         progress: 0.0,
         paused: false,
         paused_at: None,
+        control: crate::rip_backend::RipControl::default(),
     });
     drop(status);
     
@@ -790,8 +915,7 @@ async fn start_rip_internal(
         let result = run_rip_operation(state_clone.clone(), request_clone, drive_clone.clone(), operation_id_clone.clone()).await;
         
         // Remove from active rips when done
-        let mut status = state_clone.rip_status.write().await;
-        status.active_rips.remove(&drive_clone);
+        state_clone.rip_status.write().await.active_rips.remove(&drive_clone);
         
         // Complete or fail the operation
         if let Err(ref e) = result {
@@ -808,7 +932,8 @@ async fn start_rip_internal(
             complete_operation(&state_clone, &operation_id_clone, Some("Rip completed successfully".to_string())).await;
         }
         
-        // Process queue after rip completes
+        // The drive is free again, so start whatever was queued behind this rip
+        process_queue(state_clone, drive_clone).await;
     });
     
     Ok(())
@@ -819,16 +944,18 @@ async fn start_rip(
     State(state): State<ApiState>,
     Json(request): Json<StartRipRequest>,
 ) -> Result<Json<serde_json::Value>, ErrorResponse> {
-    // Determine the drive identifier (use "default" if not specified)
-    let drive = request.drive.clone();
+    // Without a drive the rip goes to a free drive with a disc in it
+    let drive = match request.drive.clone() {
+        Some(drive) => Some(drive),
+        None => find_free_drive(&state).await,
+    };
     
     let status = state.rip_status.write().await;
     
-    // Check if this specific drive (or any drive if not specified) is already ripping
-    let drive_busy = if let Some(ref d) = drive {
-        status.active_rips.contains_key(d)
-    } else {
-        !status.active_rips.is_empty()
+    // Check if this drive is already ripping (or no drive is free)
+    let drive_busy = match drive {
+        Some(ref d) => status.active_rips.contains_key(d),
+        None => true,
     };
     
     if drive_busy {
@@ -836,7 +963,7 @@ async fn start_rip(
         let queue_entry = RipQueueEntry {
             id: None,
             created_at: chrono::Utc::now(),
-            drive: drive.clone(),
+            drive: request.drive.clone(),
             output_path: request.output_path.clone(),
             title: request.title.clone(),
             skip_metadata: request.skip_metadata,
@@ -845,6 +972,9 @@ async fn start_rip(
             priority: request.priority.unwrap_or(0), // Use provided priority or default to 0
             status: QueueStatus::Pending,
             started_at: None,
+            titles: request.titles.clone(),
+            session_id: request.session_id,
+            reuse_fingerprint: request.reuse_fingerprint.clone(),
         };
         
         drop(status);
@@ -869,7 +999,7 @@ async fn start_rip(
     }
     
     // Drive is available, start immediately
-    let drive_id = drive.clone().unwrap_or_else(|| "default".to_string());
+    let drive_id = drive.unwrap_or_default();
     
     match start_rip_internal(&state, &request, &drive_id).await {
         Ok(_) => {
@@ -890,6 +1020,10 @@ async fn start_rip(
 async fn stop_rip(State(state): State<ApiState>) -> Json<serde_json::Value> {
     let mut status = state.rip_status.write().await;
     let drive_count = status.active_rips.len();
+    // Kill each ripping tool; the rip tasks remove their partial output and record the cancellation
+    for rip_status in status.active_rips.values() {
+        rip_status.control.stop();
+    }
     status.active_rips.clear();
     
     let _ = state.event_tx.send(ApiEvent::Log {
@@ -919,6 +1053,7 @@ async fn pause_rip_handler(
             });
         }
         
+        rip_status.control.pause();
         rip_status.paused = true;
         rip_status.paused_at = Some(chrono::Utc::now());
         
@@ -961,6 +1096,7 @@ async fn resume_rip_handler(
             });
         }
         
+        rip_status.control.resume();
         rip_status.paused = false;
         rip_status.paused_at = None;
         
@@ -1037,6 +1173,320 @@ async fn eject_drive(
     }
 }
 
+/// Query parameters for the disc scan endpoint
+#[derive(Debug, Deserialize)]
+struct DiscScanQuery {
+    refresh: Option<bool>,
+    profile: Option<String>,
+}
+
+/// Get the scan of the disc in a drive (the stored scan of the inserted disc unless refresh=true)
+async fn get_disc_scan(
+    State(state): State<ApiState>,
+    axum::extract::Path(device): axum::extract::Path<String>,
+    axum::extract::Query(params): axum::extract::Query<DiscScanQuery>,
+) -> Result<Json<crate::disc_scan::DiscScan>, (http::StatusCode, ErrorResponse)> {
+    // URL decode the device parameter (e.g., %2Fdev%2Fdisk2 -> /dev/disk2)
+    let device = urlencoding::decode(&device)
+        .map_err(|e| (http::StatusCode::BAD_REQUEST, ErrorResponse {
+            error: format!("Invalid device path: {}", e),
+        }))?
+        .into_owned();
+    
+    let profile = {
+        let config = state.config.read().await;
+        config.resolve_profile(params.profile.as_deref()).cloned()
+    };
+    
+    // Backends that don't need a drive supply the media type; otherwise the drive must hold a disc
+    let detected = crate::drive::detect_drives().await.ok()
+        .and_then(|drives| drives.into_iter().find(|d| d.device == device))
+        .map(|d| d.media_type)
+        .unwrap_or(crate::drive::MediaType::None);
+    let backend = crate::rip_backend::backend_for_profile(profile.as_ref(), &detected, 5, Default::default())
+        .map_err(|e| (http::StatusCode::INTERNAL_SERVER_ERROR, ErrorResponse {
+            error: format!("Failed to scan disc: {}", e),
+        }))?;
+    let media_type = match (detected, backend.media_hint()) {
+        (crate::drive::MediaType::None, Some(hint)) => hint,
+        (crate::drive::MediaType::None, None) => {
+            return Err((http::StatusCode::NOT_FOUND, ErrorResponse {
+                error: format!("No disc detected in {}", device),
+            }));
+        }
+        (media_type, _) => media_type,
+    };
+    
+    // A stored scan is only returned for the disc that's in the drive now
+    let fingerprint = inserted_disc_fingerprint(&device, &media_type).await;
+    if let (false, Some(fp)) = (params.refresh.unwrap_or(false), fingerprint.as_deref()) {
+        match state.db.get_latest_disc_scan(fp) {
+            Ok(Some(scan)) => return Ok(Json(scan)),
+            Ok(None) => {}
+            Err(e) => {
+                return Err((http::StatusCode::INTERNAL_SERVER_ERROR, ErrorResponse {
+                    error: format!("Failed to load disc scan: {}", e),
+                }));
+            }
+        }
+    }
+    
+    // Scanning a drive that's ripping would disturb the rip (abcde's scan even kills it)
+    if state.rip_status.read().await.active_rips.contains_key(&device) {
+        return Err((http::StatusCode::CONFLICT, ErrorResponse {
+            error: format!("Drive {} is ripping; scan it once the rip has finished", device),
+        }));
+    }
+    
+    match backend.scan(&device).await {
+        Ok(scan) => {
+            if let Err(e) = state.db.save_disc_scan(&scan, fingerprint.as_deref()) {
+                tracing::warn!("Failed to save disc scan: {}", e);
+            }
+            Ok(Json(scan))
+        }
+        Err(e) => Err((http::StatusCode::INTERNAL_SERVER_ERROR, ErrorResponse {
+            error: format!("Failed to scan disc: {}", e),
+        })),
+    }
+}
+
+/// Identify the disc in a drive without scanning it: an audio CD's disc ID or a mounted video disc's file structure
+async fn inserted_disc_fingerprint(device: &str, media_type: &crate::drive::MediaType) -> Option<String> {
+    match media_type {
+        crate::drive::MediaType::AudioCD => crate::metadata::get_disc_id(device).await.ok()
+            .map(|id| crate::fingerprint::cd_fingerprint(&id)),
+        crate::drive::MediaType::DVD | crate::drive::MediaType::BluRay => {
+            let volume_name = get_dvd_volume_name(device).await.ok();
+            crate::fingerprint::mounted_fingerprint(device, volume_name.as_deref())
+        }
+        crate::drive::MediaType::None => None,
+    }
+}
+
+/// Fingerprint the disc just inserted in `device` and look for an earlier successful rip of it
+///
+/// A mounted video disc is looked up by its file structure alone; only a disc whose structure can't be read is
+/// scanned with the default profile's backend, and looked up by the scan fingerprint rips record as an alias.
+async fn find_known_disc(
+    state: &ApiState,
+    device: &str,
+    media_type: &crate::drive::MediaType,
+) -> Option<(String, crate::database::RipHistory)> {
+    use crate::drive::MediaType;
+    
+    let lookup = |fingerprint: String| match state.db.find_rip_by_fingerprint(&fingerprint) {
+        Ok(previous) => previous.map(|previous| (fingerprint, previous)),
+        Err(e) => {
+            tracing::warn!("Failed to look up disc fingerprint: {}", e);
+            None
+        }
+    };
+    
+    match media_type {
+        MediaType::AudioCD => crate::metadata::get_disc_id(device).await.ok()
+            .map(|id| crate::fingerprint::cd_fingerprint(&id))
+            .and_then(lookup),
+        MediaType::DVD | MediaType::BluRay => {
+            let volume_name = get_dvd_volume_name(device).await.ok();
+            if let Some(mounted) = crate::fingerprint::mounted_fingerprint(device, volume_name.as_deref()) {
+                return state.db.canonical_fingerprint(&mounted).ok().and_then(lookup);
+            }
+            
+            let profile = state.config.read().await.resolve_profile(None).cloned();
+            let backend = crate::rip_backend::backend_for_profile(profile.as_ref(), media_type, 5, Default::default()).ok()?;
+            let scan = backend.scan(device).await
+                .map_err(|e| tracing::warn!("Could not scan {} to fingerprint it: {}", device, e))
+                .ok()?;
+            if let Err(e) = state.db.save_disc_scan(&scan, None) {
+                tracing::warn!("Failed to save disc scan: {}", e);
+            }
+            crate::fingerprint::video_fingerprint(&scan)
+                .and_then(|fp| state.db.canonical_fingerprint(&fp).ok())
+                .and_then(lookup)
+        }
+        MediaType::None => None,
+    }
+}
+
+/// Request body for deciding what to do with a known disc
+#[derive(Debug, Deserialize)]
+pub struct KnownDiscRequest {
+    pub action: KnownDiscAction,
+    pub fingerprint: Option<String>, // Required for reuse
+}
+
+/// Skip, re-rip or reuse the previous assignment for a disc that has been ripped before
+async fn resolve_known_disc(
+    State(state): State<ApiState>,
+    axum::extract::Path(device): axum::extract::Path<String>,
+    Json(request): Json<KnownDiscRequest>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    let device = urlencoding::decode(&device)
+        .map_err(|e| ErrorResponse {
+            error: format!("Invalid device path: {}", e),
+        })?
+        .into_owned();
+    
+    let (title, reuse_fingerprint) = match request.action {
+        KnownDiscAction::Skip => {
+            send_log_to_web_ui(&state, &device, "info", "⏭️  Skipping already ripped disc".to_string(), None).await;
+            return Ok(Json(serde_json::json!({
+                "status": "skipped",
+                "drive": device
+            })));
+        }
+        KnownDiscAction::Rerip => (None, None),
+        KnownDiscAction::Reuse => {
+            let fingerprint = request.fingerprint.as_deref().ok_or_else(|| ErrorResponse {
+                error: "A fingerprint is required to reuse a previous assignment".to_string(),
+            })?;
+            let previous = state.db.find_rip_by_fingerprint(fingerprint)
+                .map_err(|e| ErrorResponse {
+                    error: format!("Failed to look up disc: {}", e),
+                })?
+                .ok_or_else(|| ErrorResponse {
+                    error: format!("No previous rip found for disc {}", fingerprint),
+                })?;
+            (previous.title, Some(fingerprint.to_string()))
+        }
+    };
+    
+    let rip_request = StartRipRequest {
+        drive: Some(device.clone()),
+        output_path: None,
+        title: title.clone(),
+        skip_metadata: false,
+        skip_filebot: false,
+        profile: None,
+        priority: None,
+        titles: None,
+        session_id: None,
+        reuse_fingerprint,
+    };
+    
+    start_rip_internal(&state, &rip_request, &device).await
+        .map_err(|e| ErrorResponse {
+            error: format!("Failed to start rip: {}", e),
+        })?;
+    
+    Ok(Json(serde_json::json!({
+        "status": "started",
+        "drive": device,
+        "title": title
+    })))
+}
+
+/// Let the user pick between metadata candidates that are too close to call before the disc is named
+///
+/// Returns the candidates to try, in order: the chosen one, none for "none of these", or all of them when the
+/// search wasn't ambiguous or nobody answered within `METADATA_CHOICE_TIMEOUT`.
+async fn confirm_metadata_candidates(
+    state: &ApiState,
+    device: &str,
+    query: &str,
+    candidates: Vec<crate::dvd_metadata::MetadataCandidate>,
+    operation_id: &str,
+) -> Vec<crate::dvd_metadata::MetadataCandidate> {
+    if !crate::dvd_metadata::is_ambiguous(&candidates) {
+        return candidates;
+    }
+    
+    let (reply, choice) = tokio::sync::oneshot::channel();
+    state.metadata_choices.write().await.insert(device.to_string(), PendingMetadataChoice {
+        query: query.to_string(),
+        candidates: candidates.clone(),
+        reply,
+    });
+    send_log_to_web_ui(state, device, "warning", format!(
+        "❓ '{}' matches {} titles - choose one (the best guess is used in {} minutes)",
+        query, candidates.len(), crate::dvd_metadata::METADATA_CHOICE_TIMEOUT.as_secs() / 60,
+    ), Some(operation_id)).await;
+    let _ = state.event_tx.send(ApiEvent::MetadataChoiceRequired {
+        drive: device.to_string(),
+        query: query.to_string(),
+        candidates: candidates.clone(),
+        operation_id: Some(operation_id.to_string()),
+    });
+    
+    let choice = tokio::time::timeout(crate::dvd_metadata::METADATA_CHOICE_TIMEOUT, choice).await;
+    state.metadata_choices.write().await.remove(device);
+    match choice {
+        Ok(Ok(Some(index))) => candidates.into_iter().skip(index).take(1).collect(),
+        Ok(Ok(None)) => {
+            send_log_to_web_ui(state, device, "info", "⏭️  None of the candidates chosen, naming from the title".to_string(), Some(operation_id)).await;
+            Vec::new()
+        }
+        _ => {
+            send_log_to_web_ui(state, device, "info", format!("⏱️  No choice made, using '{}'", candidates[0].title), Some(operation_id)).await;
+            candidates
+        }
+    }
+}
+
+/// Metadata candidates waiting for a choice on a drive
+async fn get_metadata_choice(
+    State(state): State<ApiState>,
+    axum::extract::Path(device): axum::extract::Path<String>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    let device = urlencoding::decode(&device)
+        .map_err(|e| ErrorResponse {
+            error: format!("Invalid device path: {}", e),
+        })?
+        .into_owned();
+    
+    let choices = state.metadata_choices.read().await;
+    let pending = choices.get(&device).ok_or_else(|| ErrorResponse {
+        error: format!("No metadata choice pending for {}", device),
+    })?;
+    Ok(Json(serde_json::json!({
+        "drive": device,
+        "query": pending.query,
+        "candidates": pending.candidates,
+    })))
+}
+
+/// Request body for choosing between metadata candidates
+#[derive(Debug, Deserialize)]
+pub struct ChooseMetadataRequest {
+    pub index: Option<usize>, // Position in the offered candidates; null for "none of these"
+}
+
+/// Pick the show or movie for the disc in a drive
+async fn choose_metadata(
+    State(state): State<ApiState>,
+    axum::extract::Path(device): axum::extract::Path<String>,
+    Json(request): Json<ChooseMetadataRequest>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    let device = urlencoding::decode(&device)
+        .map_err(|e| ErrorResponse {
+            error: format!("Invalid device path: {}", e),
+        })?
+        .into_owned();
+    
+    let mut choices = state.metadata_choices.write().await;
+    let pending = choices.get(&device).ok_or_else(|| ErrorResponse {
+        error: format!("No metadata choice pending for {}", device),
+    })?;
+    let chosen = match request.index {
+        Some(index) => Some(pending.candidates.get(index).cloned().ok_or_else(|| ErrorResponse {
+            error: format!("No candidate {} (there are {})", index, pending.candidates.len()),
+        })?),
+        None => None,
+    };
+    
+    let pending = choices.remove(&device).expect("checked above");
+    pending.reply.send(request.index).map_err(|_| ErrorResponse {
+        error: format!("The rip on {} is no longer waiting", device),
+    })?;
+    
+    Ok(Json(serde_json::json!({
+        "status": "chosen",
+        "drive": device,
+        "candidate": chosen,
+    })))
+}
+
 /// Rename files request
 #[derive(Debug, Deserialize)]
 pub struct RenameRequest {
@@ -1044,6 +1494,10 @@ pub struct RenameRequest {
     pub title: Option<String>,
     pub skip_speech: bool,
     pub skip_filebot: bool,
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub order: Option<crate::dvd_metadata::EpisodeOrder>, // The show's saved order when unset
 }
 
 /// Rename existing files
@@ -1113,16 +1567,25 @@ async fn run_rip_operation(
     operation_id: String,
 ) -> anyhow::Result<()> {
     
-    let retry_config = {
+    let (retry_config, profile) = {
         let config = state.config.read().await;
-        config.retry.clone()
+        (config.retry.clone(), config.resolve_profile(request.profile.as_deref()).cloned())
     };
     
+    if let Some(ref p) = profile {
+        info!("Using rip profile '{}' (backend: {})", p.name, p.backend.as_deref().unwrap_or("auto"));
+    }
+    
     let start_time = chrono::Utc::now();
     let drive = drive_id.clone(); // Use the provided drive identifier
+    let control = state.rip_status.read().await.active_rips.get(&drive)
+        .map(|rip_status| rip_status.control.clone())
+        .unwrap_or_default();
     
     // Attempt rip with retries
     let mut last_error = None;
+    let mut media_type = None;
+    let mut fingerprint = None;
     let mut attempt = 1u32;
     let max_attempts = if retry_config.enabled { retry_config.max_attempts } else { 1 };
     
@@ -1144,10 +1607,14 @@ async fn run_rip_operation(
             tokio::time::sleep(tokio::time::Duration::from_secs(delay)).await;
         }
         
+        if control.is_stopped() {
+            break;
+        }
+        
         let is_final_attempt = attempt >= max_attempts;
         update_operation(&state, &operation_id, 10.0 * attempt as f32, format!("Rip attempt {}/{}", attempt, max_attempts)).await;
         
-        match run_single_rip_attempt(&state, &request, &drive, start_time, is_final_attempt, &operation_id).await {
+        match run_single_rip_attempt(&state, &request, profile.as_ref(), &drive, start_time, is_final_attempt, &operation_id, &control, &mut media_type, &mut fingerprint).await {
             Ok(_) => {
                 // Success - return immediately (history already logged)
                 update_operation(&state, &operation_id, 100.0, "Rip completed successfully".to_string()).await;
@@ -1157,8 +1624,8 @@ async fn run_rip_operation(
                 last_error = Some(e);
                 let error_msg = last_error.as_ref().unwrap().to_string();
                 
-                // Check if error is retryable
-                if !is_retryable_error(&error_msg) || is_final_attempt {
+                // Check if error is retryable (a stopped rip never is)
+                if control.is_stopped() || !is_retryable_error(&error_msg) || is_final_attempt {
                     // Not retryable or out of retries - break and log failure
                     break;
                 }
@@ -1178,6 +1645,29 @@ async fn run_rip_operation(
         state.db.get_last_title().ok().flatten()
     };
     
+    if control.is_stopped() {
+        let history = RipHistory {
+            id: None,
+            timestamp: start_time,
+            drive: drive.clone(),
+            disc: None,
+            title,
+            disc_type: media_type.and_then(|m| m.history_label()).map(String::from),
+            status: RipStatus::Cancelled,
+            duration_seconds: Some(duration_seconds),
+            file_size_bytes: None,
+            output_path: request.output_path.clone(),
+            error_message: Some("Stopped by user".to_string()),
+            avg_speed_mbps: None,
+            checksum: None,
+            fingerprint,
+        };
+        if let Err(e) = state.db.add_rip_history(&history) {
+            tracing::error!("Failed to save rip history: {}", e);
+        }
+        return Err(anyhow::anyhow!("Rip stopped by user"));
+    }
+    
     let error_msg = last_error.as_ref().map(|e| e.to_string()).unwrap_or_else(|| "Unknown error".to_string());
     
     let _ = state.event_tx.send(ApiEvent::RipError {
@@ -1192,7 +1682,7 @@ async fn run_rip_operation(
         drive: drive.clone(),
         disc: None,
         title: title.clone(),
-        disc_type: None,
+        disc_type: media_type.and_then(|m| m.history_label()).map(String::from),
         status: RipStatus::Failed,
         duration_seconds: Some(duration_seconds),
         file_size_bytes: None,
@@ -1200,6 +1690,7 @@ async fn run_rip_operation(
         error_message: Some(format!("Failed after {} attempts: {}", max_attempts, error_msg)),
         avg_speed_mbps: None,
         checksum: None, // No checksum for failed rips
+        fingerprint,
     };
     
     if let Err(e) = state.db.add_rip_history(&history) {
@@ -1210,24 +1701,37 @@ async fn run_rip_operation(
 }
 
 /// Run a single rip attempt (without retry logic)
+///
+/// The detected media type and disc fingerprint are stored in `detected_media` and `fingerprint` so failed
+/// rips can still be recorded by disc type and disc.
+#[allow(clippy::too_many_arguments)]
 async fn run_single_rip_attempt(
     state: &ApiState,
     request: &StartRipRequest,
+    profile: Option<&crate::config::RipProfile>,
     drive: &str,
     start_time: chrono::DateTime<chrono::Utc>,
     is_final_attempt: bool,
     operation_id: &str,
+    control: &crate::rip_backend::RipControl,
+    detected_media: &mut Option<crate::drive::MediaType>,
+    fingerprint: &mut Option<String>,
 ) -> anyhow::Result<()> {
     use crate::database::{RipHistory, RipStatus};
     
     // Get title: use provided, or selected show name, or last saved title
+    let mut show_selection = None;
     let title = if request.title.is_some() {
+        show_selection = state.db.get_shows().ok()
+            .and_then(|shows| shows.into_iter().find(|s| Some(&s.name) == request.title.as_ref()))
+            .and_then(|show| show.title_selection);
         request.title.clone()
     } else {
         // Try to get selected show name from database
         if let Ok(Some(show_id)) = state.db.get_last_show_id() {
             if let Ok(Some(show)) = state.db.get_show(show_id) {
                 info!("Using selected show: {}", show.name);
+                show_selection = show.title_selection;
                 // Send log after we have the title
                 Some(show.name)
             } else {
@@ -1238,6 +1742,13 @@ async fn run_single_rip_attempt(
         }
     };
     
+    // Title selection: explicit request titles, then show rules, then profile rules
+    let selection = crate::title_selection::TitleSelection::resolve(
+        profile.and_then(|p| p.title_selection.as_ref()),
+        show_selection.as_ref(),
+        request.titles.as_deref(),
+    );
+    
     // Detect media type - get from current drives
     let media_type = {
         match crate::drive::detect_drives().await {
@@ -1251,15 +1762,26 @@ async fn run_single_rip_attempt(
         }
     };
     
+    // Select the rip backend for this profile; backends that don't need a drive supply the media type
+    let backend = crate::rip_backend::backend_for_profile(profile, &media_type, 5, control.clone())?;
+    let media_type = match (media_type, backend.media_hint()) {
+        (crate::drive::MediaType::None, Some(hint)) => hint,
+        (media_type, _) => media_type,
+    };
+    *detected_media = Some(media_type.clone());
+    
     // Run web-UI-only rip operation (no TUI)
     let result = rip_disc_web_ui(
         state,
+        backend.as_ref(),
         drive,
-        media_type,
+        media_type.clone(),
         title.clone(),
         request,
+        &selection,
         operation_id,
         is_final_attempt,
+        fingerprint,
     ).await;
     
     // On success, log to history
@@ -1303,7 +1825,7 @@ async fn run_single_rip_attempt(
             drive: drive.to_string(),
             disc: None,
             title: title.clone(),
-            disc_type: None,
+            disc_type: media_type.history_label().map(String::from),
             status: RipStatus::Success,
             duration_seconds: Some(duration_seconds),
             file_size_bytes,
@@ -1317,6 +1839,7 @@ async fn run_single_rip_attempt(
                 }
             }),
             checksum,
+            fingerprint: fingerprint.clone(),
         };
         
         if let Err(e) = state.db.add_rip_history(&history) {
@@ -1380,14 +1903,18 @@ async fn update_operation_progress(
 }
 
 /// Web-UI-only rip function (no TUI, all logs go to web UI)
+#[allow(clippy::too_many_arguments)]
 async fn rip_disc_web_ui(
     state: &ApiState,
+    backend: &dyn crate::rip_backend::RipBackend,
     device: &str,
     media_type: crate::drive::MediaType,
     title: Option<String>,
     request: &StartRipRequest,
+    selection: &crate::title_selection::TitleSelection,
     operation_id: &str,
     eject_when_done: bool,
+    fingerprint: &mut Option<String>,
 ) -> anyhow::Result<()> {
     // Log that we're using the selected show if we have a title
     if let Some(ref show_name) = title {
@@ -1396,7 +1923,7 @@ async fn rip_disc_web_ui(
     
     // Handle DVD/Blu-ray ripping
     if matches!(media_type, crate::drive::MediaType::DVD | crate::drive::MediaType::BluRay) {
-        return rip_dvd_disc_web_ui(state, device, media_type, title, request, operation_id, eject_when_done).await;
+        return rip_dvd_disc_web_ui(state, backend, device, media_type, title, request, selection, operation_id, eject_when_done, fingerprint).await;
     }
     
     // Handle audio CD ripping
@@ -1427,6 +1954,7 @@ async fn rip_disc_web_ui(
     let disc_id = match crate::metadata::get_disc_id(device).await {
         Ok(id) => {
             send_log_to_web_ui(state, device, "info", format!("📀 Disc ID: {}", id), Some(operation_id)).await;
+            *fingerprint = Some(crate::fingerprint::cd_fingerprint(&id));
             id
         }
         Err(e) => {
@@ -1442,7 +1970,13 @@ async fn rip_disc_web_ui(
     let metadata = if request.skip_metadata {
         create_dummy_metadata()
     } else {
-        match crate::metadata::fetch_metadata(&disc_id, 3).await {
+        let config = state.config.read().await.clone();
+        let lookup = async {
+            let provider = crate::metadata_provider::music_provider_from_config(&config)?;
+            let provider = crate::metadata_provider::with_cache(provider, Some(&state.db), &config);
+            crate::metadata::fetch_metadata(provider.as_ref(), &disc_id, 3).await
+        };
+        match lookup.await {
             Ok(meta) => {
                 send_log_to_web_ui(state, device, "success", format!("✅ Found: {} - {} ({} tracks)", 
                     meta.artist, meta.album, meta.tracks.len()), Some(operation_id)).await;
@@ -1478,11 +2012,15 @@ async fn rip_disc_web_ui(
     let state_log = state.clone();
     let operation_id_log = operation_id.to_string();
     
+    let template = state.config.read().await.naming_template(crate::naming::MediaKind::Music, None);
+    let journal = crate::rename_journal::RenameJournal::new(Some(&state.db), false);
     let result = crate::ripper::rip_cd(
+        backend,
         device,
         &metadata,
         &output_folder,
-        5, // Default quality
+        &template,
+        &journal,
         move |progress| {
             let state = state_progress.clone();
             let operation_id = operation_id_progress.clone();
@@ -1509,36 +2047,35 @@ async fn rip_disc_web_ui(
     ).await;
     
     match result {
-        Ok(_) => {
+        Ok(track_dirs) => {
             send_log_to_web_ui(state, device, "success", format!("✅ Completed: {}", album_info), Some(operation_id)).await;
             update_operation_progress(state, operation_id, 100.0, "Rip completed successfully".to_string()).await;
             
-            // Filebot music processing if enabled
+            // Filebot music processing if enabled, in the folders the music template put the tracks in
             let config = state.config.read().await;
             if config.filebot.use_for_music {
                 send_log_to_web_ui(state, device, "info", "🎵 Running Filebot to standardize filenames...".to_string(), Some(operation_id)).await;
                 
-                let album_dir = output_folder
-                    .join(crate::ripper::sanitize_filename(&metadata.artist))
-                    .join(crate::ripper::sanitize_filename(&metadata.album));
-                
-                let state_clone = state.clone();
-                let device_clone = device.to_string();
-                let operation_id_clone = operation_id.to_string();
-                
-                if let Err(e) = crate::filebot::rename_music_with_filebot(
-                    &album_dir,
-                    move |log_msg| {
-                        let state = state_clone.clone();
-                        let device = device_clone.clone();
-                        let operation_id = operation_id_clone.clone();
-                        tokio::spawn(async move {
-                            send_log_to_web_ui(&state, &device, "info", log_msg, Some(&operation_id)).await;
-                        });
+                for album_dir in &track_dirs {
+                    let state_clone = state.clone();
+                    let device_clone = device.to_string();
+                    let operation_id_clone = operation_id.to_string();
+                    
+                    if let Err(e) = crate::filebot::rename_music_with_filebot(
+                        album_dir,
+                        &config,
+                        move |log_msg| {
+                            let state = state_clone.clone();
+                            let device = device_clone.clone();
+                            let operation_id = operation_id_clone.clone();
+                            tokio::spawn(async move {
+                                send_log_to_web_ui(&state, &device, "info", log_msg, Some(&operation_id)).await;
+                            });
+                        }
+                    ).await {
+                        tracing::warn!("Filebot music processing failed: {}", e);
+                        send_log_to_web_ui(state, device, "warning", format!("⚠️  Filebot failed: {}", e), Some(operation_id)).await;
                     }
-                ).await {
-                    tracing::warn!("Filebot music processing failed: {}", e);
-                    send_log_to_web_ui(state, device, "warning", format!("⚠️  Filebot failed: {}", e), Some(operation_id)).await;
                 }
             }
             
@@ -1583,14 +2120,18 @@ async fn rip_disc_web_ui(
 }
 
 /// Web-UI-only DVD/Blu-ray rip function (no TUI, all logs go to web UI)
+#[allow(clippy::too_many_arguments)]
 async fn rip_dvd_disc_web_ui(
     state: &ApiState,
+    backend: &dyn crate::rip_backend::RipBackend,
     device: &str,
     media_type: crate::drive::MediaType,
     title: Option<String>,
     request: &StartRipRequest,
+    selection: &crate::title_selection::TitleSelection,
     operation_id: &str,
     _eject_when_done: bool,
+    fingerprint: &mut Option<String>,
 ) -> anyhow::Result<()> {
     let media_name = match media_type {
         crate::drive::MediaType::BluRay => "Blu-ray",
@@ -1601,6 +2142,10 @@ async fn rip_dvd_disc_web_ui(
     send_log_to_web_ui(state, device, "info", format!("📀 Detected {} in {}", media_name, device), Some(operation_id)).await;
     update_operation_progress(state, operation_id, 1.0, format!("Detected {} in {}", media_name, device)).await;
     
+    // Scan up front so the web UI can see what's on the disc (and so it can be fingerprinted)
+    send_log_to_web_ui(state, device, "info", format!("🔍 Scanning {}...", media_name), Some(operation_id)).await;
+    let scan = backend.scan(device).await?;
+    send_log_to_web_ui(state, device, "info", format!("📀 Found {} titles", scan.titles.len()), Some(operation_id)).await;
     // Try to get disc volume name
     send_log_to_web_ui(state, device, "info", format!("🔍 Fetching {} metadata...", media_name), Some(operation_id)).await;
     update_operation_progress(state, operation_id, 2.0, "Fetching disc metadata...".to_string()).await;
@@ -1616,9 +2161,51 @@ async fn rip_dvd_disc_web_ui(
         }
     };
     
-    // Use provided title, or selected show name, or volume name
+    let mounted = crate::fingerprint::mounted_fingerprint(device, volume_name.as_deref());
+    *fingerprint = crate::fingerprint::record_video_fingerprint(&state.db, mounted.as_deref(), &scan);
+    if let Some(ref fp) = fingerprint {
+        info!("Disc fingerprint for {}: {}", device, fp);
+    }
+    // Stored under the fingerprint /drives/:device/scan can compute without scanning
+    if let Err(e) = state.db.save_disc_scan(&scan, mounted.as_deref()) {
+        tracing::warn!("Failed to save disc scan: {}", e);
+    }
+    
+    // A remembered disc (or the next disc of a box set) knows its show, season and first episode
+    let disc_key = volume_name.clone().or_else(|| scan.volume_label.clone());
+    // Only a rip that names its session joins it; an unrelated disc must not advance a box set's numbering
+    let session = request.session_id
+        .and_then(|id| state.db.get_rip_session(id).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load rip session {}: {}", id, e);
+            None
+        }))
+        .filter(|s| s.status == crate::database::RipSessionStatus::Active);
+    if let (Some(id), None) = (request.session_id, session.as_ref()) {
+        send_log_to_web_ui(state, device, "warning", format!("⚠️  Box set session {} is not active, ripping without it", id), Some(operation_id)).await;
+    }
+    let assignment = match session {
+        // A box-set session continues its running episode counter
+        Some(ref session) => {
+            send_log_to_web_ui(state, device, "info", format!("📦 Box set session: {} season {}, {}", session.show_name, session.season, session.summary(0)), Some(operation_id)).await;
+            update_operation_session(state, operation_id, SessionProgress::new(session, 0)).await;
+            Some(crate::database::DiscAssignment::for_session(session, fingerprint.clone(), disc_key.clone()))
+        }
+        // Reusing a known disc's assignment brings back its season and first episode, not just the show
+        None => request.reuse_fingerprint.as_deref()
+            .and_then(|fp| state.db.get_disc_assignment(Some(fp), None).unwrap_or_else(|e| {
+                tracing::warn!("Failed to load disc assignment for {}: {}", fp, e);
+                None
+            }))
+            .or_else(|| state.db.resolve_disc_assignment(fingerprint.as_deref(), disc_key.as_deref(), title.as_deref())),
+    };
+    if let Some(ref a) = assignment {
+        send_log_to_web_ui(state, device, "info", format!("📚 {} season {}, starting at episode {}", a.show_name, a.season, a.first_episode), Some(operation_id)).await;
+    }
+    
+    // Use provided title, or assigned show name, or volume name
     let title_to_search = if !request.skip_metadata {
-        let final_title = title
+        let final_title = title.clone()
+            .or_else(|| assignment.as_ref().map(|a| a.show_name.clone()))
             .or_else(|| volume_name.clone())
             .unwrap_or_else(|| "Unknown".to_string());
         
@@ -1638,7 +2225,20 @@ async fn rip_dvd_disc_web_ui(
         send_log_to_web_ui(state, device, "info", format!("🔍 Searching TMDB for '{}'...", title_str), Some(operation_id)).await;
         update_operation_progress(state, operation_id, 3.0, format!("Searching TMDB for '{}'...", title_str)).await;
         
-        match crate::dvd_metadata::fetch_dvd_metadata("", Some(title_str)).await {
+        // Without a season from the assignment or label, every season is fetched and matching picks one
+        let season = assignment.as_ref().map(|a| a.season)
+            .or_else(|| disc_key.as_deref().and_then(crate::dvd_metadata::extract_season_from_volume));
+        let start_episode = assignment.as_ref().map(|a| a.first_episode);
+        let order = state.db.episode_order_for_show(title_str);
+        let config = state.config.read().await.clone();
+        let lookup = async {
+            let provider = crate::metadata_provider::video_provider_from_config(&config)?;
+            let provider = crate::metadata_provider::with_cache(provider, Some(&state.db), &config);
+            let candidates = crate::dvd_metadata::search_candidates(provider.as_ref(), title_str).await;
+            let candidates = confirm_metadata_candidates(state, device, title_str, candidates, operation_id).await;
+            crate::dvd_metadata::fetch_metadata_from_candidates(provider.as_ref(), &candidates, title_str, season, start_episode, order).await
+        };
+        match lookup.await {
             Ok(meta) => {
                 send_log_to_web_ui(state, device, "success", format!("📺 Found: {}", meta.title), Some(operation_id)).await;
                 if meta.media_type == crate::dvd_metadata::MediaType::TVShow && !meta.episodes.is_empty() {
@@ -1655,6 +2255,21 @@ async fn rip_dvd_disc_web_ui(
         None
     };
     
+    // Checkpoints from an interrupted rip of this disc let us pick up where it stopped; they're keyed by fingerprint
+    // since generic labels ("DVD_VIDEO") are shared by many discs
+    let checkpoint_key = fingerprint.clone().or_else(|| disc_key.clone());
+    let checkpoints = match checkpoint_key {
+        Some(ref key) => state.db.get_rip_checkpoints(Some(key)).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load rip checkpoints: {}", e);
+            Vec::new()
+        }),
+        None => Vec::new(),
+    };
+    let completed_titles: Vec<u32> = checkpoints.iter().map(|c| c.title_index).collect();
+    if !checkpoints.is_empty() {
+        send_log_to_web_ui(state, device, "info", format!("⏯️  Resuming interrupted rip: {} titles already ripped", checkpoints.len()), Some(operation_id)).await;
+    }
+    
     let default_label = match media_type {
         crate::drive::MediaType::BluRay => "Blu-ray Video",
         crate::drive::MediaType::DVD => "DVD Video",
@@ -1693,7 +2308,12 @@ async fn rip_dvd_disc_web_ui(
         };
         format!("{}_{}", prefix, timestamp)
     };
-    let dvd_dir = media_output.join(folder_name.clone());
+    // Every disc of a box-set session lands in the same season folder
+    let dvd_dir = match (checkpoints.first(), session.as_ref()) {
+        (Some(checkpoint), _) => std::path::PathBuf::from(&checkpoint.output_dir),
+        (None, Some(session)) => session.season_folder(&media_output),
+        (None, None) => media_output.join(folder_name.clone()),
+    };
     
     // Create completed subfolder
     let completed_dir = dvd_dir.join("completed");
@@ -1706,6 +2326,11 @@ async fn rip_dvd_disc_web_ui(
     send_log_to_web_ui(state, device, "info", format!("Output: {}", dvd_dir.display()), Some(operation_id)).await;
     update_operation_progress(state, operation_id, 5.0, format!("Starting rip to: {}", dvd_dir.display())).await;
     
+    // Movie rips name each title as the movie, one of its cuts or an extra
+    let movie_plan = dvd_metadata.as_ref()
+        .filter(|m| m.media_type == crate::dvd_metadata::MediaType::Movie)
+        .and_then(|_| crate::movie::plan_movie(&scan.titles, selection));
+    
     let device_clone = device.to_string();
     let state_clone = state.clone();
     let operation_id_clone = operation_id.to_string();
@@ -1717,60 +2342,153 @@ async fn rip_dvd_disc_web_ui(
     let state_for_episode = state.clone();
     let device_for_episode = device.to_string();
     let operation_id_for_episode = operation_id.to_string();
-    
-    let result = crate::dvd_ripper::rip_dvd(
-        device,
-        &dvd_dir,
-        dvd_metadata.as_ref(),
-        move |progress| {
-            let state = state_progress.clone();
-            let operation_id = operation_id_progress.clone();
-            
-            tokio::spawn(async move {
-                let progress_pct = progress.percentage;
-                let message = format!("Track {}/{}: {} ({:.1}%)", 
-                    progress.current_track, 
-                    progress.total_tracks,
-                    progress.track_name,
-                    progress_pct);
-                update_operation_progress(&state, &operation_id, progress_pct, message).await;
-            });
-        },
-        move |log_line| {
-            let state = state_clone.clone();
-            let operation_id = operation_id_clone.clone();
-            let device = device_clone.clone();
-            
-            tokio::spawn(async move {
-                send_log_to_web_ui(&state, &device, "info", log_line, Some(&operation_id)).await;
-            });
-        },
-        move |file_path: &std::path::Path, _title_num: u32| {
-            let completed_dir = completed_dir_clone.clone();
-            let metadata = metadata_for_episode.clone();
-            let state = state_for_episode.clone();
-            let device = device_for_episode.clone();
-            let operation_id = operation_id_for_episode.clone();
-            let file_path_clone = file_path.to_path_buf();
-            
-            Box::pin(async move {
-                process_episode_immediately(
+    let checkpoint_key_for_episode = checkpoint_key.clone();
+    let dvd_dir_for_episode = dvd_dir.clone();
+    let episodes_matched = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0));
+    let session_for_episode = session.clone();
+    // Every rename of this rip goes in one journal batch so it can be undone as a whole
+    let journal = crate::rename_journal::RenameJournal::new(Some(&state.db), false);
+    let batch_for_episode = journal.batch_id().to_string();
+    
+    let on_progress = move |progress: crate::ripper::RipProgress| {
+        let state = state_progress.clone();
+        let operation_id = operation_id_progress.clone();
+        
+        tokio::spawn(async move {
+            let progress_pct = progress.percentage;
+            let message = format!("{}Track {}/{}: {} ({:.1}%)", 
+                if progress.status == crate::ripper::RipStatus::Paused { "Paused - " } else { "" },
+                progress.current_track, 
+                progress.total_tracks,
+                progress.track_name,
+                progress_pct);
+            update_operation_progress(&state, &operation_id, progress_pct, message).await;
+        });
+    };
+    let on_log = move |log_line| {
+        let state = state_clone.clone();
+        let operation_id = operation_id_clone.clone();
+        let device = device_clone.clone();
+        
+        tokio::spawn(async move {
+            send_log_to_web_ui(&state, &device, "info", log_line, Some(&operation_id)).await;
+        });
+    };
+    let on_episode = crate::dvd_ripper::episode_callback(move |file_path: &std::path::Path, title_num: u32, matched: Option<&crate::episode_assignment::Assignment>| {
+        let completed_dir = completed_dir_clone.clone();
+        let metadata = metadata_for_episode.clone();
+        let state = state_for_episode.clone();
+        let device = device_for_episode.clone();
+        let operation_id = operation_id_for_episode.clone();
+        let file_path_clone = file_path.to_path_buf();
+        // Only titles confidently matched to an episode count towards the session's numbering
+        let disc_episodes = match matched {
+            Some(a) if !a.needs_review => episodes_matched.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1,
+            _ => episodes_matched.load(std::sync::atomic::Ordering::SeqCst),
+        };
+        let session_progress = session_for_episode.as_ref().map(|s| SessionProgress::new(s, disc_episodes));
+        let movie_title = movie_plan.as_ref().and_then(|plan| plan.get(title_num)).cloned();
+        let batch_id = batch_for_episode.clone();
+        let matched = matched.cloned();
+        
+        let checkpoint_key = checkpoint_key_for_episode.clone();
+        let output_dir = dvd_dir_for_episode.to_string_lossy().to_string();
+        
+        Box::pin(async move {
+            if let Some(progress) = session_progress {
+                update_operation_session(&state, &operation_id, progress).await;
+            }
+            let final_path = match (movie_title, metadata.as_ref()) {
+                (Some(movie_title), Some(metadata)) => {
+                    process_movie_title(&state, &device, &operation_id, &file_path_clone, &completed_dir, metadata, &movie_title, &batch_id).await?
+                }
+                _ => process_episode_immediately(
                     &state,
                     &device,
                     &operation_id,
                     &file_path_clone,
+                    title_num,
+                    matched.as_ref(),
                     &completed_dir,
                     metadata.as_ref(),
-                ).await
-            })
-        },
-    ).await;
+                    &batch_id,
+                ).await?,
+            };
+            
+            // Checkpoint the title once it's safely in the completed folder, so a failed move is ripped again on resume
+            if let Some(key) = checkpoint_key {
+                let checkpoint = crate::database::RipCheckpoint {
+                    id: None,
+                    fingerprint: key,
+                    device: device.clone(),
+                    output_dir,
+                    title_index: title_num,
+                    output_file: final_path.to_string_lossy().to_string(),
+                    file_size_bytes: std::fs::metadata(&final_path).ok().map(|m| m.len() as i64),
+                    completed_at: chrono::Utc::now(),
+                };
+                if let Err(e) = state.db.add_rip_checkpoint(&checkpoint) {
+                    tracing::warn!("Failed to save rip checkpoint for title {}: {}", title_num, e);
+                }
+            }
+            Ok(())
+        })
+    });
+    
+    // Blu-rays get their own main feature / playlist heuristics
+    let result = if media_type == crate::drive::MediaType::BluRay {
+        crate::bluray::rip_bluray(backend, device, &dvd_dir, Some(scan), selection, &completed_titles, dvd_metadata.as_ref(), on_progress, on_log, on_episode).await
+    } else {
+        crate::dvd_ripper::rip_dvd(backend, device, &dvd_dir, Some(scan), selection, &completed_titles, dvd_metadata.as_ref(), on_progress, on_log, on_episode).await
+    };
     
     tracing::info!("Rip result received, checking: result.is_ok()={}", result.is_ok());
     match result {
-        Ok(_) => {
+        Ok(matched_episodes) => {
             tracing::info!("Rip completed successfully - all episodes processed immediately");
+            if let Some(ref key) = checkpoint_key {
+                if let Err(e) = state.db.clear_rip_checkpoints(key) {
+                    tracing::warn!("Failed to clear rip checkpoints: {}", e);
+                }
+            }
+            
+            // The disc's episodes are the ones its titles confidently matched, including titles ripped before a
+            // resume; titles left for review don't advance the box set's numbering
+            let disc_episodes = matched_episodes.iter().filter(|a| !a.needs_review).count() as u32;
+            if let Some(id) = session.as_ref().and_then(|s| s.id) {
+                match state.db.record_rip_session_disc(id, disc_episodes) {
+                    Ok(updated) if updated.status == crate::database::RipSessionStatus::Completed => {
+                        send_log_to_web_ui(state, device, "success", format!("📦 Box set session complete: {} discs, {} episodes", updated.discs_ripped, updated.next_episode - 1), Some(operation_id)).await;
+                    }
+                    Ok(updated) => {
+                        send_log_to_web_ui(state, device, "info", format!("📦 Insert the next disc: {}", updated.summary(0)), Some(operation_id)).await;
+                    }
+                    Err(e) => tracing::warn!("Failed to update rip session: {}", e),
+                }
+            }
+            
+            // Remember where this disc sits in its box set so the next disc continues the numbering
+            let show_name = session.as_ref().map(|s| s.show_name.clone()).or_else(|| dvd_metadata.as_ref()
+                .filter(|m| m.media_type == crate::dvd_metadata::MediaType::TVShow)
+                .map(|m| m.title.clone()));
+            if let Some(show_name) = show_name {
+                // The first episode the titles confidently matched tells a new disc's season, even when the label doesn't
+                let first_match = matched_episodes.iter()
+                    .filter(|a| !a.needs_review)
+                    .map(|a| (a.episode_match.season, a.episode_match.episode))
+                    .min();
+                if let (None, Some((season, first_episode))) = (assignment.as_ref(), first_match) {
+                    send_log_to_web_ui(state, device, "info", format!("📚 Titles matched season {}, starting at episode {}", season, first_episode), Some(operation_id)).await;
+                }
+                let disc_assignment = crate::database::DiscAssignment::after_rip(
+                    assignment.as_ref(), show_name, first_match, fingerprint.clone(), disc_key.clone(), disc_episodes,
+                );
+                if let Err(e) = state.db.save_disc_assignment(&disc_assignment) {
+                    tracing::warn!("Failed to save disc assignment: {}", e);
+                }
+            }
             send_log_to_web_ui(state, device, "success", format!("✅ {} rip complete - all episodes processed", media_name), Some(operation_id)).await;
+            send_log_to_web_ui(state, device, "info", format!("↩️  Undo renames with: ripley rename --undo {}", journal.batch_id()), Some(operation_id)).await;
             update_operation_progress(state, operation_id, 100.0, format!("{} rip complete", media_name)).await;
             
             // Send notification
@@ -1825,28 +2543,59 @@ async fn rip_dvd_disc_web_ui(
     Ok(())
 }
 
-/// Process a single episode immediately after ripping: OpenAI -> Validate -> Filebot -> Move to completed
+/// Process a single episode immediately after ripping: name it after its matched episode -> Move to completed
+///
+/// A title confidently matched to an episode gets the show's naming template (see `rename::episode_path`);
+/// anything else falls back to `DISC_LABEL-TIMESTAMP.mkv`, and a low-confidence match is queued for review.
+/// Every rename is recorded in the rip's journal batch so `/renames/:batch_id/undo` can put it back.
+/// Returns where the file ended up.
+#[allow(clippy::too_many_arguments)]
 async fn process_episode_immediately(
     state: &ApiState,
     device: &str,
     operation_id: &str,
     file_path: &std::path::Path,
+    title_num: u32,
+    matched: Option<&crate::episode_assignment::Assignment>,
     completed_dir: &std::path::Path,
     metadata: Option<&crate::dvd_metadata::DvdMetadata>,
-) -> anyhow::Result<()> {
+    batch_id: &str,
+) -> anyhow::Result<std::path::PathBuf> {
     use anyhow::Context;
     use std::time::{SystemTime, UNIX_EPOCH};
     
+    let journal = crate::rename_journal::RenameJournal::for_batch(Some(&state.db), batch_id);
     let file_name = file_path.file_name()
         .and_then(|n| n.to_str())
         .context("Invalid file path")?;
     
-    send_log_to_web_ui(state, device, "info", format!("🔄 Processing episode: {}", file_name), Some(operation_id)).await;
-    
-    // Simple renaming: DISC_LABEL-TIMESTAMP.mkv
-    let disc_label = metadata
-        .map(|m| m.title.replace(' ', "_").replace("/", "_"))
-        .unwrap_or_else(|| "Unknown".to_string());
+    send_log_to_web_ui(state, device, "info", format!("🔄 Processing title {}: {}", title_num, file_name), Some(operation_id)).await;
+    
+    let show = metadata.filter(|m| m.media_type == crate::dvd_metadata::MediaType::TVShow);
+    if let (Some(assignment), Some(show)) = (matched.filter(|a| !a.needs_review), show) {
+        let ep_match = &assignment.episode_match;
+        let config = state.config.read().await.clone();
+        let named = crate::rename::episode_path(&config, &completed_dir.join(file_name), &show.title, show.year.as_deref(), ep_match)
+            .and_then(|dest| {
+                journal.rename(file_path, &dest, &ep_match.match_method)?;
+                Ok(dest)
+            });
+        match named {
+            Ok(dest) => {
+                let shown = dest.strip_prefix(completed_dir).unwrap_or(&dest);
+                send_log_to_web_ui(state, device, "success", format!("✅ S{:02}E{:02} moved to completed: {}", ep_match.season, ep_match.episode, shown.display()), Some(operation_id)).await;
+                return Ok(dest);
+            }
+            Err(e) => {
+                send_log_to_web_ui(state, device, "warning", format!("  ⚠️  Could not name title {} as S{:02}E{:02}: {}", title_num, ep_match.season, ep_match.episode, e), Some(operation_id)).await;
+            }
+        }
+    }
+    
+    // Simple renaming: DISC_LABEL-TIMESTAMP.mkv
+    let disc_label = metadata
+        .map(|m| m.title.replace(' ', "_").replace("/", "_"))
+        .unwrap_or_else(|| "Unknown".to_string());
     
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1857,7 +2606,7 @@ async fn process_episode_immediately(
     let final_file_path = file_path.parent().unwrap().join(&new_name);
     
     // Rename the file
-    if let Err(e) = tokio::fs::rename(&file_path, &final_file_path).await {
+    if let Err(e) = journal.rename(file_path, &final_file_path, "disc_label") {
         send_log_to_web_ui(state, device, "warning", format!("  ⚠️  Failed to rename: {}", e), Some(operation_id)).await;
         return Err(anyhow::anyhow!("Failed to rename file: {}", e));
     }
@@ -1897,10 +2646,17 @@ async fn process_episode_immediately(
         }
     }
     
-    match tokio::fs::rename(&final_file_path, &dest_path).await {
+    match journal.rename(&final_file_path, &dest_path, "completed") {
         Ok(_) => {
             send_log_to_web_ui(state, device, "success", format!("✅ Moved to completed: {}", dest_path.file_name().unwrap().to_string_lossy()), Some(operation_id)).await;
-            Ok(())
+            if let (Some(assignment), Some(show)) = (matched.filter(|a| a.needs_review), show) {
+                let ep_match = &assignment.episode_match;
+                send_log_to_web_ui(state, device, "warning", format!("  ⚠️  Low confidence ({:.0}%) for S{:02}E{:02}, queued for review", ep_match.confidence, ep_match.season, ep_match.episode), Some(operation_id)).await;
+                if let Err(e) = crate::rename::queue_for_review(&state.db, &show.title, &dest_path, assignment) {
+                    tracing::warn!("Failed to queue episode match review: {}", e);
+                }
+            }
+            Ok(dest_path)
         }
         Err(e) => {
             send_log_to_web_ui(state, device, "error", format!("❌ Failed to move to completed: {} (file: {})", e, final_file_path.display()), Some(operation_id)).await;
@@ -1909,6 +2665,37 @@ async fn process_episode_immediately(
     }
 }
 
+/// Name a title of a movie rip after the movie (an extra goes in its extras folder) inside the completed folder
+#[allow(clippy::too_many_arguments)]
+async fn process_movie_title(
+    state: &ApiState,
+    device: &str,
+    operation_id: &str,
+    file_path: &std::path::Path,
+    completed_dir: &std::path::Path,
+    metadata: &crate::dvd_metadata::DvdMetadata,
+    movie_title: &crate::movie::MovieTitle,
+    batch_id: &str,
+) -> anyhow::Result<std::path::PathBuf> {
+    let config = state.config.read().await.clone();
+    let renamed = crate::movie::movie_path(&config, completed_dir, file_path, metadata, movie_title)
+        .and_then(|dest| {
+            crate::rename_journal::RenameJournal::for_batch(Some(&state.db), batch_id).rename(file_path, &dest, "movie")?;
+            Ok(dest)
+        });
+    match renamed {
+        Ok(dest) => {
+            let shown = dest.strip_prefix(completed_dir).unwrap_or(&dest);
+            send_log_to_web_ui(state, device, "success", format!("✅ Moved to completed: {}", shown.display()), Some(operation_id)).await;
+            Ok(dest)
+        }
+        Err(e) => {
+            send_log_to_web_ui(state, device, "error", format!("❌ Failed to move {} to completed: {}", file_path.display(), e), Some(operation_id)).await;
+            Err(e)
+        }
+    }
+}
+
 /// Helper to get DVD volume name (extracted from app.rs)
 async fn get_dvd_volume_name(device: &str) -> anyhow::Result<String> {
     tracing::debug!("Getting volume name for device: {}", device);
@@ -2242,6 +3029,8 @@ async fn run_rename_operation(
         request.title.clone(),
         request.skip_speech,
         request.skip_filebot,
+        request.dry_run,
+        request.order,
     ).await {
         Ok(_) => {
             let _ = state.event_tx.send(ApiEvent::Log {
@@ -2533,6 +3322,40 @@ async fn update_show(
     }
 }
 
+/// Set or clear a show's title selection rules (send `null` to fall back to the rip profile)
+async fn update_show_title_selection(
+    State(state): State<ApiState>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+    Json(selection): Json<Option<crate::title_selection::TitleSelection>>,
+) -> Result<Json<serde_json::Value>, (http::StatusCode, ErrorResponse)> {
+    match state.db.set_show_title_selection(id, selection.as_ref()) {
+        Ok(true) => Ok(Json(serde_json::json!({ "success": true }))),
+        Ok(false) => Err((http::StatusCode::NOT_FOUND, ErrorResponse {
+            error: "Show not found".to_string(),
+        })),
+        Err(e) => Err((http::StatusCode::INTERNAL_SERVER_ERROR, ErrorResponse {
+            error: format!("Failed to update title selection: {}", e),
+        })),
+    }
+}
+
+/// Set or clear a show's episode order (aired, dvd, absolute, story_arc, production, digital)
+async fn update_show_episode_order(
+    State(state): State<ApiState>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+    Json(order): Json<Option<crate::dvd_metadata::EpisodeOrder>>,
+) -> Result<Json<serde_json::Value>, (http::StatusCode, ErrorResponse)> {
+    match state.db.set_show_episode_order(id, order) {
+        Ok(true) => Ok(Json(serde_json::json!({ "success": true }))),
+        Ok(false) => Err((http::StatusCode::NOT_FOUND, ErrorResponse {
+            error: "Show not found".to_string(),
+        })),
+        Err(e) => Err((http::StatusCode::INTERNAL_SERVER_ERROR, ErrorResponse {
+            error: format!("Failed to update episode order: {}", e),
+        })),
+    }
+}
+
 /// Delete a show
 async fn delete_show(
     State(state): State<ApiState>,
@@ -2680,121 +3503,121 @@ async fn restore_database_handler(
     }
 }
 
-/// Process rip queue - checks for available drives and starts queued rips
-#[allow(dead_code)]
-async fn process_queue(state: ApiState) {
+/// A detected drive with a disc in it that isn't already ripping
+async fn find_free_drive(state: &ApiState) -> Option<String> {
+    let drives = crate::drive::detect_drives().await.ok()?;
     let status = state.rip_status.read().await;
-    let active_drives: std::collections::HashSet<String> = status.active_rips.keys().cloned().collect();
-    drop(status);
+    drives.into_iter()
+        .filter(|d| !matches!(d.media_type, crate::drive::MediaType::None))
+        .map(|d| d.device)
+        .find(|device| !status.active_rips.contains_key(device))
+}
+
+/// Process rip queue - runs queued rips on a free drive (queued for it or for any drive) until none are left
+async fn process_queue(state: ApiState, drive_id: String) {
+    tokio::spawn(async move {
+        while let Some(entry) = claim_next_queue_entry(&state, &drive_id).await {
+            run_queued_rip(&state, &drive_id, entry).await;
+        }
+    });
+}
+
+/// Take the next queued rip for a drive and mark the drive busy with it, unless the drive is already ripping
+async fn claim_next_queue_entry(state: &ApiState, drive_id: &str) -> Option<RipQueueEntry> {
+    let mut status = state.rip_status.write().await;
+    if status.active_rips.contains_key(drive_id) {
+        return None; // Drive is busy
+    }
     
-    // Check for queue entries that can be processed
-    let next_entry = match state.db.get_next_queue_entry(None) {
+    // Check for queue entries that can run on this drive
+    let next_entry = match state.db.get_next_queue_entry(Some(drive_id)) {
         Ok(Some(entry)) => entry,
-        Ok(None) => return, // No queued items
+        Ok(None) => return None, // No queued items
         Err(e) => {
             tracing::error!("Failed to get next queue entry: {}", e);
-            return;
+            return None;
         }
     };
     
-    // Check if the requested drive is available
-    let drive_available = if let Some(ref requested_drive) = next_entry.drive {
-        !active_drives.contains(requested_drive)
-    } else {
-        // Any drive can be used - check if we have at least one free
-        active_drives.is_empty()
-    };
-    
-    if !drive_available {
-        return; // No available drives
-    }
-    
-    let queue_id = next_entry.id.unwrap();
-    let drive_id = next_entry.drive.clone().unwrap_or_else(|| "default".to_string());
-    
     // Mark as processing
-    if let Err(e) = state.db.update_queue_status(queue_id, QueueStatus::Processing, Some(chrono::Utc::now())) {
+    if let Err(e) = state.db.update_queue_status(next_entry.id?, QueueStatus::Processing, Some(chrono::Utc::now())) {
         tracing::error!("Failed to update queue status: {}", e);
-        return;
+        return None;
     }
     
-    // Create operation for this queued rip
-    let operation_id = create_operation(
-        &state,
-        OperationType::Rip,
-        Some(drive_id.clone()),
-        next_entry.title.clone(),
-        format!("Starting queued rip on drive {}", drive_id),
-    ).await;
-    
     // Mark drive as active
-    let mut status = state.rip_status.write().await;
-    status.active_rips.insert(drive_id.clone(), DriveRipStatus {
+    status.active_rips.insert(drive_id.to_string(), DriveRipStatus {
         current_disc: None,
         current_title: next_entry.title.clone(),
         progress: 0.0,
         paused: false,
         paused_at: None,
+        control: crate::rip_backend::RipControl::default(),
     });
-    drop(status);
+    
+    Some(next_entry)
+}
+
+/// Run a claimed queue entry to completion, then free the drive and record how the rip went
+async fn run_queued_rip(state: &ApiState, drive_id: &str, entry: RipQueueEntry) {
+    // Create operation for this queued rip
+    let operation_id = create_operation(
+        state,
+        OperationType::Rip,
+        Some(drive_id.to_string()),
+        entry.title.clone(),
+        format!("Starting queued rip on drive {}", drive_id),
+    ).await;
     
     // Convert queue entry to StartRipRequest
     let request = StartRipRequest {
-        drive: next_entry.drive.clone(),
-        output_path: next_entry.output_path.clone(),
-        title: next_entry.title.clone(),
-        skip_metadata: next_entry.skip_metadata,
-        skip_filebot: next_entry.skip_filebot,
-        profile: next_entry.profile.clone(),
-        priority: Some(next_entry.priority), // Preserve priority
+        drive: Some(drive_id.to_string()),
+        output_path: entry.output_path.clone(),
+        title: entry.title.clone(),
+        skip_metadata: entry.skip_metadata,
+        skip_filebot: entry.skip_filebot,
+        profile: entry.profile.clone(),
+        priority: Some(entry.priority), // Preserve priority
+        titles: entry.titles.clone(),
+        session_id: entry.session_id,
+        reuse_fingerprint: entry.reuse_fingerprint.clone(),
     };
     
-    let state_clone = state.clone();
-    let drive_clone = drive_id.clone();
-    let queue_id_clone = queue_id;
-    let operation_id_clone = operation_id.clone();
-    let output_path_clone = request.output_path.clone();
-    let title_clone = request.title.clone();
+    let result = run_rip_operation(state.clone(), request, drive_id.to_string(), operation_id.clone()).await;
     
-    // Spawn rip operation
-    tokio::spawn(async move {
-        let result = run_rip_operation(state_clone.clone(), request, drive_clone.clone(), operation_id_clone.clone()).await;
-        
-        // Complete or fail the operation
-        if let Err(ref e) = result {
-            fail_operation(&state_clone, &operation_id_clone, format!("{}", e)).await;
-        } else {
-            // Try to create upscaling job if this was a video rip
-            if let Some(ref output_path) = output_path_clone {
-                if let Err(e) = create_upscaling_job_for_rip(&state_clone, output_path, title_clone.as_deref()).await {
-                    tracing::warn!("Failed to create upscaling job after queued rip: {}", e);
-                }
+    // Complete or fail the operation
+    if let Err(ref e) = result {
+        fail_operation(state, &operation_id, format!("{}", e)).await;
+    } else {
+        // Try to create upscaling job if this was a video rip
+        if let Some(ref output_path) = entry.output_path {
+            if let Err(e) = create_upscaling_job_for_rip(state, output_path, entry.title.as_deref()).await {
+                tracing::warn!("Failed to create upscaling job after queued rip: {}", e);
             }
-            
-            complete_operation(&state_clone, &operation_id_clone, Some("Queued rip completed successfully".to_string())).await;
         }
         
-        // Remove from active rips when done
-        let mut status = state_clone.rip_status.write().await;
-        status.active_rips.remove(&drive_clone);
-        
-        // Update queue status
-        let queue_status = if result.is_ok() {
-            QueueStatus::Completed
-        } else {
-            QueueStatus::Failed
-        };
-        
-        if let Err(e) = state_clone.db.update_queue_status(queue_id_clone, queue_status, None) {
+        complete_operation(state, &operation_id, Some("Queued rip completed successfully".to_string())).await;
+    }
+    
+    // Remove from active rips when done
+    state.rip_status.write().await.active_rips.remove(drive_id);
+    
+    // Update queue status
+    let queue_status = if result.is_ok() {
+        QueueStatus::Completed
+    } else {
+        QueueStatus::Failed
+    };
+    
+    if let Some(queue_id) = entry.id {
+        if let Err(e) = state.db.update_queue_status(queue_id, queue_status, None) {
             tracing::error!("Failed to update queue status: {}", e);
         }
-        
-        if let Err(e) = result {
-            tracing::error!("Rip operation failed: {:?}", e);
-        }
-        
-        // Queue will be processed on next start_rip call or when checking for available drives
-    });
+    }
+    
+    if let Err(e) = result {
+        tracing::error!("Rip operation failed: {:?}", e);
+    }
 }
 
 /// Get rip queue
@@ -2807,6 +3630,190 @@ async fn get_queue_handler(State(state): State<ApiState>) -> Result<Json<Vec<Rip
     }
 }
 
+/// List checkpoints of interrupted rips
+async fn get_rip_checkpoints_handler(
+    State(state): State<ApiState>,
+) -> Result<Json<Vec<crate::database::RipCheckpoint>>, ErrorResponse> {
+    match state.db.get_rip_checkpoints(None) {
+        Ok(checkpoints) => Ok(Json(checkpoints)),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to get rip checkpoints: {}", e),
+        }),
+    }
+}
+
+/// Discard a disc's checkpoints so the next rip starts from the first title
+async fn clear_rip_checkpoints_handler(
+    State(state): State<ApiState>,
+    axum::extract::Path(fingerprint): axum::extract::Path<String>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    let fingerprint = urlencoding::decode(&fingerprint)
+        .map_err(|e| ErrorResponse {
+            error: format!("Invalid fingerprint: {}", e),
+        })?
+        .into_owned();
+    
+    match state.db.clear_rip_checkpoints(&fingerprint) {
+        Ok(count) => Ok(Json(serde_json::json!({
+            "success": true,
+            "cleared": count
+        }))),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to clear rip checkpoints: {}", e),
+        }),
+    }
+}
+
+/// Get remembered disc assignments (optionally `?show=` for one show)
+async fn get_disc_assignments_handler(
+    State(state): State<ApiState>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Result<Json<Vec<crate::database::DiscAssignment>>, ErrorResponse> {
+    match state.db.get_disc_assignments(params.get("show").map(String::as_str)) {
+        Ok(assignments) => Ok(Json(assignments)),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to get disc assignments: {}", e),
+        }),
+    }
+}
+
+/// Assign a disc to a show, season and first episode (replaces any assignment for the same disc)
+async fn save_disc_assignment_handler(
+    State(state): State<ApiState>,
+    Json(assignment): Json<crate::database::DiscAssignment>,
+) -> Result<Json<serde_json::Value>, (http::StatusCode, ErrorResponse)> {
+    validate_disc_assignment(&assignment)?;
+    
+    match state.db.save_disc_assignment(&assignment) {
+        Ok(id) => Ok(Json(serde_json::json!({ "id": id, "success": true }))),
+        Err(e) => Err((http::StatusCode::INTERNAL_SERVER_ERROR, ErrorResponse {
+            error: format!("Failed to save disc assignment: {}", e),
+        })),
+    }
+}
+
+/// Edit a disc assignment
+async fn update_disc_assignment_handler(
+    State(state): State<ApiState>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+    Json(assignment): Json<crate::database::DiscAssignment>,
+) -> Result<Json<serde_json::Value>, (http::StatusCode, ErrorResponse)> {
+    validate_disc_assignment(&assignment)?;
+    
+    match state.db.update_disc_assignment(id, &assignment) {
+        Ok(true) => Ok(Json(serde_json::json!({ "success": true }))),
+        Ok(false) => Err((http::StatusCode::NOT_FOUND, ErrorResponse {
+            error: "Disc assignment not found".to_string(),
+        })),
+        Err(e) => Err((http::StatusCode::INTERNAL_SERVER_ERROR, ErrorResponse {
+            error: format!("Failed to update disc assignment: {}", e),
+        })),
+    }
+}
+
+/// Reject disc assignments that can't identify a disc or number its episodes
+fn validate_disc_assignment(assignment: &crate::database::DiscAssignment) -> Result<(), (http::StatusCode, ErrorResponse)> {
+    if assignment.fingerprint.is_none() && assignment.volume_label.is_none() {
+        return Err((http::StatusCode::BAD_REQUEST, ErrorResponse {
+            error: "A disc assignment needs a fingerprint or volume label".to_string(),
+        }));
+    }
+    if assignment.first_episode == 0 {
+        return Err((http::StatusCode::BAD_REQUEST, ErrorResponse {
+            error: "A disc assignment's first episode starts at 1".to_string(),
+        }));
+    }
+    Ok(())
+}
+
+/// Delete a disc assignment
+async fn delete_disc_assignment_handler(
+    State(state): State<ApiState>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    match state.db.delete_disc_assignment(id) {
+        Ok(_) => Ok(Json(serde_json::json!({ "success": true }))),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to delete disc assignment: {}", e),
+        }),
+    }
+}
+
+/// Get box-set sessions, newest first
+async fn get_rip_sessions_handler(
+    State(state): State<ApiState>,
+) -> Result<Json<Vec<crate::database::RipSession>>, ErrorResponse> {
+    match state.db.get_rip_sessions() {
+        Ok(sessions) => Ok(Json(sessions)),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to get sessions: {}", e),
+        }),
+    }
+}
+
+/// Start a box-set session; video rips that name it with `session_id` join it until it has seen all its discs
+async fn create_rip_session_handler(
+    State(state): State<ApiState>,
+    Json(session): Json<crate::database::RipSession>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    if session.expected_discs == 0 {
+        return Err(ErrorResponse {
+            error: "A session needs at least one disc".to_string(),
+        });
+    }
+    
+    match state.db.create_rip_session(&session) {
+        Ok(id) => Ok(Json(serde_json::json!({ "id": id, "success": true }))),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to create session: {}", e),
+        }),
+    }
+}
+
+/// Get a single box-set session
+async fn get_rip_session_handler(
+    State(state): State<ApiState>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+) -> Result<Json<crate::database::RipSession>, ErrorResponse> {
+    match state.db.get_rip_session(id) {
+        Ok(Some(session)) => Ok(Json(session)),
+        Ok(None) => Err(ErrorResponse {
+            error: "Session not found".to_string(),
+        }),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to get session: {}", e),
+        }),
+    }
+}
+
+/// Edit a box-set session (correct the episode counter, add discs, or end it)
+async fn update_rip_session_handler(
+    State(state): State<ApiState>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+    Json(session): Json<crate::database::RipSession>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    let session = crate::database::RipSession { id: Some(id), ..session };
+    match state.db.update_rip_session(&session) {
+        Ok(_) => Ok(Json(serde_json::json!({ "success": true }))),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to update session: {}", e),
+        }),
+    }
+}
+
+/// Delete a box-set session
+async fn delete_rip_session_handler(
+    State(state): State<ApiState>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    match state.db.delete_rip_session(id) {
+        Ok(_) => Ok(Json(serde_json::json!({ "success": true }))),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to delete session: {}", e),
+        }),
+    }
+}
+
 /// Cancel queue entry
 async fn cancel_queue_handler(
     State(state): State<ApiState>,
@@ -2835,6 +3842,80 @@ async fn get_episode_match_statistics_handler(
     }
 }
 
+/// Get low-confidence episode matches waiting for review
+async fn get_pending_episode_matches_handler(
+    State(state): State<ApiState>,
+) -> Result<Json<Vec<crate::database::EpisodeMatchReview>>, ErrorResponse> {
+    match state.db.get_pending_episode_match_reviews() {
+        Ok(reviews) => Ok(Json(reviews)),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to get pending episode matches: {}", e),
+        }),
+    }
+}
+
+/// Accept the suggested episode for a review and rename the file
+async fn accept_episode_match_handler(
+    State(state): State<ApiState>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    let config = state.config.read().await.clone();
+    match crate::rename::apply_review(&state.db, &config, id, None, None) {
+        Ok(path) => Ok(Json(serde_json::json!({ "success": true, "file_path": path }))),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to accept episode match: {}", e),
+        }),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OverrideEpisodeMatchRequest {
+    season: u32,
+    episode: u32,
+    title: Option<String>,
+}
+
+/// Resolve a review with a different episode and rename the file
+async fn override_episode_match_handler(
+    State(state): State<ApiState>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+    Json(request): Json<OverrideEpisodeMatchRequest>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    let config = state.config.read().await.clone();
+    match crate::rename::apply_review(&state.db, &config, id, Some((request.season, request.episode)), request.title.as_deref()) {
+        Ok(path) => Ok(Json(serde_json::json!({ "success": true, "file_path": path }))),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to override episode match: {}", e),
+        }),
+    }
+}
+
+/// Get the renames made in one batch
+async fn get_rename_batch_handler(
+    State(state): State<ApiState>,
+    axum::extract::Path(batch_id): axum::extract::Path<String>,
+) -> Result<Json<Vec<crate::database::RenameJournalEntry>>, ErrorResponse> {
+    match state.db.get_rename_batch(&batch_id) {
+        Ok(entries) => Ok(Json(entries)),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to get rename batch: {}", e),
+        }),
+    }
+}
+
+/// Revert every rename in a batch
+async fn undo_rename_batch_handler(
+    State(state): State<ApiState>,
+    axum::extract::Path(batch_id): axum::extract::Path<String>,
+) -> Result<Json<serde_json::Value>, ErrorResponse> {
+    match crate::rename_journal::undo_batch(&state.db, &batch_id) {
+        Ok(restored) => Ok(Json(serde_json::json!({ "success": true, "restored": restored }))),
+        Err(e) => Err(ErrorResponse {
+            error: format!("Failed to undo rename batch: {}", e),
+        }),
+    }
+}
+
 /// Get rip history
 #[derive(Debug, Deserialize)]
 struct RipHistoryQuery {
@@ -2913,6 +3994,7 @@ async fn get_monitor_operations(State(state): State<ApiState>) -> Result<Json<Ve
                         started_at,
                         completed_at: job.completed_at,
                         error: job.error_message,
+                        session: None,
                     };
                     
                     all_operations.push(operation);
@@ -3995,6 +5077,66 @@ mod tests {
         assert_eq!(response.0["status"], "ok");
     }
 
+    #[tokio::test]
+    async fn test_disc_assignment_handlers_validate() {
+        std::env::set_var("RIPLEY_TEST_DB", ":memory:");
+        let state = ApiState {
+            config: Arc::new(RwLock::new(Config::default())),
+            rip_status: Arc::default(),
+            event_tx: broadcast::channel(100).0,
+            db: Arc::new(Database::new().unwrap()),
+            operations: Arc::default(),
+            metadata_choices: Arc::default(),
+        };
+        let assignment = crate::database::DiscAssignment {
+            id: None,
+            fingerprint: Some("dvd:disc1".to_string()),
+            volume_label: None,
+            show_name: "Show".to_string(),
+            season: 1,
+            first_episode: 1,
+            episode_count: 4,
+            updated_at: chrono::Utc::now(),
+        };
+        let saved = save_disc_assignment_handler(State(state.clone()), Json(assignment.clone())).await.unwrap();
+        let id = saved.0["id"].as_i64().unwrap();
+
+        let status = |result: Result<Json<serde_json::Value>, (http::StatusCode, ErrorResponse)>| result.err().map(|(status, _)| status);
+        let unidentified = crate::database::DiscAssignment { fingerprint: None, ..assignment.clone() };
+        let episode_zero = crate::database::DiscAssignment { first_episode: 0, ..assignment.clone() };
+        for invalid in [unidentified, episode_zero] {
+            assert_eq!(status(save_disc_assignment_handler(State(state.clone()), Json(invalid.clone())).await), Some(http::StatusCode::BAD_REQUEST));
+            assert_eq!(status(update_disc_assignment_handler(State(state.clone()), axum::extract::Path(id), Json(invalid)).await), Some(http::StatusCode::BAD_REQUEST));
+        }
+        assert_eq!(
+            status(update_disc_assignment_handler(State(state.clone()), axum::extract::Path(id + 1), Json(assignment.clone())).await),
+            Some(http::StatusCode::NOT_FOUND)
+        );
+
+        let edited = crate::database::DiscAssignment { first_episode: 5, ..assignment };
+        assert!(update_disc_assignment_handler(State(state.clone()), axum::extract::Path(id), Json(edited)).await.is_ok());
+        assert_eq!(state.db.get_disc_assignment(Some("dvd:disc1"), None).unwrap().unwrap().first_episode, 5);
+    }
+
+    #[tokio::test]
+    async fn test_update_show_episode_order_unknown_show() {
+        std::env::set_var("RIPLEY_TEST_DB", ":memory:");
+        let state = ApiState {
+            config: Arc::new(RwLock::new(Config::default())),
+            rip_status: Arc::default(),
+            event_tx: broadcast::channel(100).0,
+            db: Arc::new(Database::new().unwrap()),
+            operations: Arc::default(),
+            metadata_choices: Arc::default(),
+        };
+        let id = state.db.add_show("Firefly").unwrap();
+        let order = || Json(Some(crate::dvd_metadata::EpisodeOrder::Dvd));
+
+        assert!(update_show_episode_order(State(state.clone()), axum::extract::Path(id), order()).await.is_ok());
+        let missing = update_show_episode_order(State(state.clone()), axum::extract::Path(id + 1), order()).await;
+        assert_eq!(missing.err().map(|(status, _)| status), Some(http::StatusCode::NOT_FOUND));
+    }
+
     #[test]
     fn test_api_event_serialization() {
         let event = ApiEvent::Log {
@@ -4007,4 +5149,158 @@ mod tests {
         assert!(json.contains("Log"));
         assert!(json.contains("test"));
     }
+
+    #[test]
+    fn test_known_disc_event_serialization() {
+        let event = ApiEvent::KnownDiscInserted {
+            drive: "/dev/sr0".to_string(),
+            fingerprint: "dvd:0123abcd".to_string(),
+            previous: crate::database::RipHistory {
+                id: Some(7),
+                timestamp: chrono::Utc::now(),
+                drive: "/dev/sr0".to_string(),
+                disc: None,
+                title: Some("Futurama".to_string()),
+                disc_type: Some("dvd".to_string()),
+                status: crate::database::RipStatus::Success,
+                duration_seconds: None,
+                file_size_bytes: None,
+                output_path: None,
+                error_message: None,
+                avg_speed_mbps: None,
+                checksum: None,
+                fingerprint: Some("dvd:0123abcd".to_string()),
+            },
+            actions: vec![KnownDiscAction::Skip, KnownDiscAction::Rerip, KnownDiscAction::Reuse],
+        };
+        let json: serde_json::Value = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "KnownDiscInserted");
+        assert_eq!(json["data"]["previous"]["title"], "Futurama");
+        assert_eq!(json["data"]["actions"], serde_json::json!(["skip", "rerip", "reuse"]));
+
+        let request: KnownDiscRequest = serde_json::from_str(r#"{"action": "reuse", "fingerprint": "dvd:0123abcd"}"#).unwrap();
+        assert_eq!(request.action, KnownDiscAction::Reuse);
+    }
+
+    #[tokio::test]
+    async fn test_choose_metadata_candidate() {
+        std::env::set_var("RIPLEY_TEST_DB", ":memory:");
+        let (event_tx, mut events) = broadcast::channel(100);
+        let state = ApiState {
+            config: Arc::new(RwLock::new(Config::default())),
+            rip_status: Arc::default(),
+            event_tx,
+            db: Arc::new(Database::new().unwrap()),
+            operations: Arc::default(),
+            metadata_choices: Arc::default(),
+        };
+        let candidate = |title: &str, year: &str| crate::dvd_metadata::MetadataCandidate {
+            media_type: crate::dvd_metadata::MediaType::TVShow,
+            id: year.to_string(),
+            title: title.to_string(),
+            year: Some(year.to_string()),
+            overview: None,
+            poster_url: None,
+            score: 1000,
+        };
+        let candidates = vec![candidate("Battlestar Galactica", "2004"), candidate("Battlestar Galactica", "1978")];
+
+        let rip = tokio::spawn({
+            let state = state.clone();
+            async move { confirm_metadata_candidates(&state, "/dev/sr0", "BSG", candidates, "op-1").await }
+        });
+        loop {
+            if let ApiEvent::MetadataChoiceRequired { drive, candidates, .. } = events.recv().await.unwrap() {
+                assert_eq!(drive, "/dev/sr0");
+                assert_eq!(candidates.len(), 2);
+                break;
+            }
+        }
+
+        let device = || axum::extract::Path("%2Fdev%2Fsr0".to_string());
+        let pending = get_metadata_choice(State(state.clone()), device()).await.unwrap();
+        assert_eq!(pending.0["query"], "BSG");
+        let out_of_range = ChooseMetadataRequest { index: Some(5) };
+        assert!(choose_metadata(State(state.clone()), device(), Json(out_of_range)).await.is_err());
+
+        let response = choose_metadata(State(state.clone()), device(), Json(ChooseMetadataRequest { index: Some(1) })).await.unwrap();
+        assert_eq!(response.0["candidate"]["year"], "1978");
+        let chosen = rip.await.unwrap();
+        assert_eq!(chosen.len(), 1);
+        assert_eq!(chosen[0].year.as_deref(), Some("1978"));
+        assert!(state.metadata_choices.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_queued_rip_starts_after_running_rip() {
+        std::env::set_var("RIPLEY_TEST_DB", ":memory:");
+        let mut config = Config::default();
+        config.retry.enabled = false;
+        config.rip_profiles.push(crate::config::RipProfile {
+            name: "fake".to_string(),
+            description: None,
+            audio_quality: None,
+            makemkv_profile: None,
+            is_default: false,
+            backend: Some("fake".to_string()),
+            fake_script: None,
+            title_selection: None,
+        });
+        let state = ApiState {
+            config: Arc::new(RwLock::new(config)),
+            rip_status: Arc::default(),
+            event_tx: broadcast::channel(100).0,
+            db: Arc::new(Database::new().unwrap()),
+            operations: Arc::default(),
+            metadata_choices: Arc::default(),
+        };
+        let output = tempfile::tempdir().unwrap();
+        let output_path = Some(output.path().to_string_lossy().into_owned());
+
+        let queue_id = state.db.add_to_queue(&RipQueueEntry {
+            id: None,
+            created_at: chrono::Utc::now(),
+            drive: Some("/dev/fake0".to_string()),
+            output_path: output_path.clone(),
+            title: Some("Queued".to_string()),
+            skip_metadata: true,
+            skip_filebot: true,
+            profile: Some("fake".to_string()),
+            priority: 0,
+            status: QueueStatus::Pending,
+            started_at: None,
+            titles: None,
+            session_id: None,
+            reuse_fingerprint: None,
+        }).unwrap();
+
+        let request = StartRipRequest {
+            drive: Some("/dev/fake0".to_string()),
+            output_path,
+            title: Some("Running".to_string()),
+            skip_metadata: true,
+            skip_filebot: true,
+            profile: Some("fake".to_string()),
+            priority: None,
+            titles: None,
+            session_id: None,
+            reuse_fingerprint: None,
+        };
+        start_rip_internal(&state, &request, "/dev/fake0").await.unwrap();
+
+        // Once the running rip is done the queued one takes the drive and runs to the end
+        let queued = tokio::time::timeout(std::time::Duration::from_secs(30), async {
+            loop {
+                let entries = state.db.get_queue_entries(true).unwrap();
+                let entry = entries.into_iter().find(|e| e.id == Some(queue_id)).unwrap();
+                if matches!(entry.status, QueueStatus::Completed | QueueStatus::Failed) {
+                    return entry;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            }
+        }).await.expect("queued rip never ran");
+        assert!(queued.started_at.is_some());
+        assert!(matches!(queued.status, QueueStatus::Completed));
+        assert!(!state.rip_status.read().await.active_rips.contains_key("/dev/fake0"));
+    }
 }
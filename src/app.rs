@@ -2,7 +2,7 @@ use anyhow::Result;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
 
 use crate::cli::RipArgs;
 use crate::drive::{self, DriveInfo};
@@ -116,9 +116,15 @@ async fn rip_disc(
         s.add_drive_log(device, msg);
     }
 
+    // Pick the rip backend from the selected profile
+    let profile = crate::config::Config::load().ok()
+        .and_then(|config| config.resolve_profile(args.profile.as_deref()).cloned());
+    let backend = crate::rip_backend::backend_for_profile(profile.as_ref(), &media_type, args.quality, Default::default())?;
+
     // Handle DVD/Blu-ray ripping (MakeMKV handles both)
     if matches!(media_type, drive::MediaType::DVD | drive::MediaType::BluRay) {
-        return rip_dvd_disc(device, media_type.clone(), args, tui_state).await;
+        let profile_selection = profile.and_then(|p| p.title_selection);
+        return rip_dvd_disc(backend.as_ref(), device, media_type.clone(), profile_selection.as_ref(), args, tui_state).await;
     }
 
     add_log(&tui_state, device, format!("📀 Detected audio CD in {}", device)).await;
@@ -163,7 +169,14 @@ async fn rip_disc(
         // Create dummy metadata
         create_dummy_metadata()
     } else {
-        match metadata::fetch_metadata(&disc_id, 3).await {
+        let config = crate::config::Config::load().unwrap_or_default();
+        let db = crate::database::Database::new().ok();
+        let lookup = async {
+            let provider = crate::metadata_provider::music_provider_from_config(&config)?;
+            let provider = crate::metadata_provider::with_cache(provider, db.as_ref(), &config);
+            metadata::fetch_metadata(provider.as_ref(), &disc_id, 3).await
+        };
+        match lookup.await {
             Ok(meta) => {
                 add_log(&tui_state, device, format!("✅ Found: {} - {} ({} tracks)", 
                     meta.artist, meta.album, meta.tracks.len())).await;
@@ -199,11 +212,16 @@ async fn rip_disc(
     let device_log_clone = device.to_string();
 
     let output_folder = args.get_output_folder();
+    let template = crate::config::Config::load().unwrap_or_default().naming_template(crate::naming::MediaKind::Music, None);
+    let db = crate::database::Database::new().ok();
+    let journal = crate::rename_journal::RenameJournal::new(db.as_ref(), false);
     let result = ripper::rip_cd(
+        backend.as_ref(),
         device,
         &metadata,
         &output_folder,
-        args.quality,
+        &template,
+        &journal,
         move |progress| {
             let device = device_clone.clone();
             let album_info = album_info_clone.clone();
@@ -229,7 +247,7 @@ async fn rip_disc(
     ).await;
 
     match result {
-        Ok(_) => {
+        Ok(track_dirs) => {
             add_log(&tui_state, device, format!("✅ Completed: {}", album_info)).await;
             
             // Load config to check if Filebot music mode is enabled
@@ -237,25 +255,24 @@ async fn rip_disc(
                 if config.filebot.use_for_music {
                     add_log(&tui_state, device, "🎵 Running Filebot to standardize filenames...".to_string()).await;
                     
-                    let output_folder = args.get_output_folder();
-                    let album_dir = output_folder
-                        .join(crate::ripper::sanitize_filename(&metadata.artist))
-                        .join(crate::ripper::sanitize_filename(&metadata.album));
-                    
-                    let tui_state_clone = Arc::clone(&tui_state);
-                    let device_clone = device.to_string();
-                    
-                    if let Err(e) = crate::filebot::rename_music_with_filebot(
-                        &album_dir,
-                        move |log_msg| {
-                            let tui_state = Arc::clone(&tui_state_clone);
-                            let device = device_clone.clone();
-                            tokio::spawn(async move {
-                                add_log(&tui_state, &device, log_msg).await;
-                            });
+                    // The music template already moved the tracks; Filebot works on the folders they landed in
+                    for album_dir in &track_dirs {
+                        let tui_state_clone = Arc::clone(&tui_state);
+                        let device_clone = device.to_string();
+                        
+                        if let Err(e) = crate::filebot::rename_music_with_filebot(
+                            album_dir,
+                            &config,
+                            move |log_msg| {
+                                let tui_state = Arc::clone(&tui_state_clone);
+                                let device = device_clone.clone();
+                                tokio::spawn(async move {
+                                    add_log(&tui_state, &device, log_msg).await;
+                                });
+                            }
+                        ).await {
+                            tracing::warn!("Filebot music processing failed: {}", e);
                         }
-                    ).await {
-                        tracing::warn!("Filebot music processing failed: {}", e);
                     }
                 }
             }
@@ -301,8 +318,10 @@ async fn rip_disc(
 }
 
 async fn rip_dvd_disc(
+    backend: &dyn crate::rip_backend::RipBackend,
     device: &str,
     media_type: drive::MediaType,
+    profile_selection: Option<&crate::title_selection::TitleSelection>,
     args: RipArgs,
     tui_state: Arc<Mutex<crate::tui::AppState>>,
 ) -> Result<()> {
@@ -340,10 +359,43 @@ async fn rip_dvd_disc(
         }
     };
     
+    // A box-set session named with --session supplies the show, season and next episode
+    let db = crate::database::Database::new().ok().map(Arc::new);
+    let session = args.session_id
+        .and_then(|id| db.as_ref()?.get_rip_session(id).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load rip session {}: {}", id, e);
+            None
+        }))
+        .filter(|s| s.status == crate::database::RipSessionStatus::Active);
+    if let (Some(id), None) = (args.session_id, session.as_ref()) {
+        add_log(&tui_state, device, format!("⚠️  Box set session {} is not active, ripping without it", id)).await;
+    }
+    if let Some(ref session) = session {
+        add_log(&tui_state, device, format!("📦 Box set session: {} season {}, {}", session.show_name, session.season, session.summary(0))).await;
+        tui_state.lock().await.set_drive_session(device, Some(session.summary(0)));
+    }
+    
+    // Scan up front so the disc can be fingerprinted (and movie titles planned)
+    let scan = backend.scan(device).await?;
+    let mounted = crate::fingerprint::mounted_fingerprint(device, volume_name.as_deref());
+    let fingerprint = match db.as_deref() {
+        Some(db) => crate::fingerprint::record_video_fingerprint(db, mounted.as_deref(), &scan),
+        None => mounted.or_else(|| crate::fingerprint::video_fingerprint(&scan)),
+    };
+    
+    // A remembered disc (or the next disc of a box set) knows its show, season and first episode
+    let disc_key = volume_name.clone().or_else(|| scan.volume_label.clone());
+    let known_show = db.as_ref()
+        .and_then(|db| db.resolve_disc_assignment(fingerprint.as_deref(), disc_key.as_deref(), args.title.as_deref()))
+        .map(|a| a.show_name);
+    
     // For DVDs/Blu-rays, prompt for title only (Filebot will handle episode matching)
-    let title_to_search = if !args.skip_metadata {
-        // Prompt for title (with default from --title flag or volume name)
-        let default_title = args.title.clone().or(volume_name.clone());
+    let title_to_search = if let (false, Some(session)) = (args.skip_metadata, session.as_ref()) {
+        add_log(&tui_state, device, format!("📺 Using title: '{}'", session.show_name)).await;
+        Some(session.show_name.clone())
+    } else if !args.skip_metadata {
+        // Prompt for title (with default from --title flag, the disc's assigned show or volume name)
+        let default_title = args.title.clone().or(known_show).or(volume_name.clone());
         
         add_log(&tui_state, device, "📝 Please enter TV show title...".to_string()).await;
         {
@@ -378,10 +430,45 @@ async fn rip_dvd_disc(
         None
     };
     
+    let assignment = match session {
+        // A box-set session continues its running episode counter
+        Some(ref session) => Some(crate::database::DiscAssignment::for_session(session, fingerprint.clone(), disc_key.clone())),
+        None => db.as_ref().and_then(|db| db.resolve_disc_assignment(fingerprint.as_deref(), disc_key.as_deref(), title_to_search.as_deref())),
+    };
+    if let (Some(a), None) = (assignment.as_ref(), session.as_ref()) {
+        add_log(&tui_state, device, format!("📚 {} season {}, starting at episode {}", a.show_name, a.season, a.first_episode)).await;
+    }
+    
+    // Title selection: explicit --titles, then the show's rules, then the profile's
+    let show_selection = title_to_search.as_ref()
+        .and_then(|title| db.as_ref()?.get_shows().ok()?.into_iter().find(|s| &s.name == title))
+        .and_then(|show| show.title_selection);
+    let selection = crate::title_selection::TitleSelection::resolve(
+        profile_selection,
+        show_selection.as_ref(),
+        args.titles.as_deref(),
+    );
+    
     let dvd_metadata = if let Some(ref title) = title_to_search {
         add_log(&tui_state, device, format!("🔍 Searching TMDB for '{}'...", title)).await;
         
-        match crate::dvd_metadata::fetch_dvd_metadata("", Some(title.as_str())).await {
+        // Without a season from the assignment or label, every season is fetched and matching picks one
+        let season = assignment.as_ref().map(|a| a.season)
+            .or_else(|| disc_key.as_deref().and_then(crate::dvd_metadata::extract_season_from_volume));
+        let start_episode = assignment.as_ref().map(|a| a.first_episode);
+        let order = db.as_ref().map(|db| db.episode_order_for_show(title)).unwrap_or_default();
+        if order != crate::dvd_metadata::EpisodeOrder::Aired {
+            add_log(&tui_state, device, format!("🔢 Using {:?} episode order", order)).await;
+        }
+        let config = crate::config::Config::load().unwrap_or_default();
+        let lookup = async {
+            let provider = crate::metadata_provider::video_provider_from_config(&config)?;
+            let provider = crate::metadata_provider::with_cache(provider, db.as_deref(), &config);
+            let candidates = crate::dvd_metadata::search_candidates(provider.as_ref(), title).await;
+            let candidates = choose_metadata_candidate(&tui_state, device, candidates).await;
+            crate::dvd_metadata::fetch_metadata_from_candidates(provider.as_ref(), &candidates, title, season, start_episode, order).await
+        };
+        match lookup.await {
             Ok(meta) => {
                 add_log(&tui_state, device, format!("📺 Found: {}", meta.title)).await;
                 if meta.media_type == crate::dvd_metadata::MediaType::TVShow && !meta.episodes.is_empty() {
@@ -411,7 +498,7 @@ async fn rip_dvd_disc(
             meta.title.clone()
         }
     } else {
-        volume_name.unwrap_or_else(|| default_label.to_string())
+        volume_name.clone().unwrap_or_else(|| default_label.to_string())
     };
 
     // Update album info
@@ -444,7 +531,26 @@ async fn rip_dvd_disc(
         };
         format!("{}_{}", prefix, timestamp)
     };
-    let dvd_dir = media_output.join(folder_name);
+    // Checkpoints from an interrupted rip of this disc (in the TUI or web UI) let us pick up where it stopped
+    let checkpoint_key = fingerprint.clone().or_else(|| disc_key.clone());
+    let checkpoints = match (db.as_ref(), checkpoint_key.as_ref()) {
+        (Some(db), Some(key)) => db.get_rip_checkpoints(Some(key)).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load rip checkpoints: {}", e);
+            Vec::new()
+        }),
+        _ => Vec::new(),
+    };
+    let completed_titles: Vec<u32> = checkpoints.iter().map(|c| c.title_index).collect();
+    if !checkpoints.is_empty() {
+        add_log(&tui_state, device, format!("⏯️  Resuming interrupted rip: {} titles already ripped", checkpoints.len())).await;
+    }
+    
+    // Every disc of a box-set session lands in the same season folder
+    let dvd_dir = match (checkpoints.first(), session.as_ref()) {
+        (Some(checkpoint), _) => std::path::PathBuf::from(&checkpoint.output_dir),
+        (None, Some(session)) => session.season_folder(&media_output),
+        (None, None) => media_output.join(folder_name),
+    };
     
     add_log(&tui_state, device, format!("Output: {}", dvd_dir.display())).await;
 
@@ -453,42 +559,194 @@ async fn rip_dvd_disc(
     let tui_state_log_clone = Arc::clone(&tui_state);
     let device_log_clone = device.to_string();
 
-    let result = crate::dvd_ripper::rip_dvd(
-        device,
-        &dvd_dir,
-        dvd_metadata.as_ref(),
-        move |progress| {
-            let device = device_clone.clone();
-            let tui_state = Arc::clone(&tui_state_clone);
+    let album_label = match media_type {
+        drive::MediaType::BluRay => "Blu-ray Video",
+        _ => "DVD Video",
+    };
+    let on_progress = move |progress| {
+        let device = device_clone.clone();
+        let tui_state = Arc::clone(&tui_state_clone);
 
-            tokio::spawn(async move {
-                let mut s = tui_state.lock().await;
-                if let Some(drive) = s.drives.iter_mut().find(|d| d.device == device) {
-                    drive.progress = Some(progress);
-                    if drive.album_info.is_none() {
-                        drive.album_info = Some("DVD Video".to_string());
-                    }
+        tokio::spawn(async move {
+            let mut s = tui_state.lock().await;
+            if let Some(drive) = s.drives.iter_mut().find(|d| d.device == device) {
+                drive.progress = Some(progress);
+                if drive.album_info.is_none() {
+                    drive.album_info = Some(album_label.to_string());
                 }
-            });
-        },
-        move |log_line| {
-            let device = device_log_clone.clone();
-            let tui_state = Arc::clone(&tui_state_log_clone);
-            tokio::spawn(async move {
-                add_log(&tui_state, &device, log_line).await;
-            });
-        },
-        move |_file_path: &std::path::Path, _title_num: u32| {
-            // TUI mode: no per-episode processing (legacy support)
-            Box::pin(async move {
-                Ok(())
-            })
-        },
-    ).await;
+            }
+        });
+    };
+    let on_log = move |log_line| {
+        let device = device_log_clone.clone();
+        let tui_state = Arc::clone(&tui_state_log_clone);
+        tokio::spawn(async move {
+            add_log(&tui_state, &device, log_line).await;
+        });
+    };
+    // Movie rips are planned from the scan so each title can be named as the movie, a cut or an extra
+    let movie_plan = dvd_metadata.as_ref()
+        .filter(|m| m.media_type == crate::dvd_metadata::MediaType::Movie)
+        .and_then(|_| crate::movie::plan_movie(&scan.titles, &selection));
+    
+    // Each ripped file with its title number and the episode its runtime (or playlist) was matched to;
+    // titles ripped before an interruption are renamed along with the rest
+    type RippedFile = (std::path::PathBuf, u32, Option<crate::episode_assignment::Assignment>);
+    let ripped_files: Arc<Mutex<Vec<RippedFile>>> = Arc::new(Mutex::new(checkpoints.iter()
+        .map(|c| (std::path::PathBuf::from(&c.output_file), c.title_index, None))
+        .collect()));
+    let ripped_files_clone = Arc::clone(&ripped_files);
+    let session_for_episode = session.clone();
+    let tui_state_episode_clone = Arc::clone(&tui_state);
+    let device_episode_clone = device.to_string();
+    let db_for_episode = db.clone();
+    let checkpoint_key_for_episode = checkpoint_key.clone();
+    let dvd_dir_for_episode = dvd_dir.to_string_lossy().to_string();
+    let on_episode = crate::dvd_ripper::episode_callback(move |file_path: &std::path::Path, title_num: u32, matched: Option<&crate::episode_assignment::Assignment>| {
+        // TUI mode: no per-episode processing (legacy support), just track session progress and checkpoint the title
+        let ripped_files = Arc::clone(&ripped_files_clone);
+        let session = session_for_episode.clone();
+        let tui_state = Arc::clone(&tui_state_episode_clone);
+        let device = device_episode_clone.clone();
+        let file_path = file_path.to_path_buf();
+        let matched = matched.cloned();
+        let db = db_for_episode.clone();
+        let checkpoint_key = checkpoint_key_for_episode.clone();
+        let output_dir = dvd_dir_for_episode.clone();
+        Box::pin(async move {
+            if let (Some(db), Some(key)) = (db, checkpoint_key) {
+                let checkpoint = crate::database::RipCheckpoint {
+                    id: None,
+                    fingerprint: key,
+                    device: device.clone(),
+                    output_dir,
+                    title_index: title_num,
+                    output_file: file_path.to_string_lossy().to_string(),
+                    file_size_bytes: std::fs::metadata(&file_path).ok().map(|m| m.len() as i64),
+                    completed_at: chrono::Utc::now(),
+                };
+                if let Err(e) = db.add_rip_checkpoint(&checkpoint) {
+                    tracing::warn!("Failed to save rip checkpoint for title {}: {}", title_num, e);
+                }
+            }
+            let mut files = ripped_files.lock().await;
+            files.push((file_path, title_num, matched));
+            if let Some(session) = session {
+                let matched_count = files.iter().filter(|(_, _, matched)| matched.as_ref().is_some_and(|a| !a.needs_review)).count() as u32;
+                tui_state.lock().await.set_drive_session(&device, Some(session.summary(matched_count)));
+            }
+            Ok(())
+        })
+    });
+
+    // Blu-rays get their own main feature / playlist heuristics
+    let result = if media_type == drive::MediaType::BluRay {
+        crate::bluray::rip_bluray(backend, device, &dvd_dir, Some(scan), &selection, &completed_titles, dvd_metadata.as_ref(), on_progress, on_log, on_episode).await
+    } else {
+        crate::dvd_ripper::rip_dvd(backend, device, &dvd_dir, Some(scan), &selection, &completed_titles, dvd_metadata.as_ref(), on_progress, on_log, on_episode).await
+    };
 
     match result {
-        Ok(_) => {
+        Ok(matched_episodes) => {
             add_log(&tui_state, device, format!("✅ {} rip complete", media_name)).await;
+            if let (Some(db), Some(key)) = (db.as_ref(), checkpoint_key.as_ref()) {
+                if let Err(e) = db.clear_rip_checkpoints(key) {
+                    tracing::warn!("Failed to clear rip checkpoints: {}", e);
+                }
+            }
+            
+            // Titles ripped before a resume take their episode from this run's matching
+            for (_, title_num, matched) in ripped_files.lock().await.iter_mut().filter(|(_, _, m)| m.is_none()) {
+                *matched = matched_episodes.iter().find(|a| a.title_index == *title_num).cloned();
+            }
+            
+            // Count the disc's confidently matched episodes against its box-set session so the next disc continues
+            // the numbering; titles left for review don't advance it
+            let disc_episodes = matched_episodes.iter().filter(|a| !a.needs_review).count() as u32;
+            if let (Some(db), Some(id)) = (db.as_ref(), session.as_ref().and_then(|s| s.id)) {
+                match db.record_rip_session_disc(id, disc_episodes) {
+                    Ok(updated) if updated.status == crate::database::RipSessionStatus::Completed => {
+                        add_log(&tui_state, device, format!("📦 Box set session complete: {} discs, {} episodes", updated.discs_ripped, updated.next_episode - 1)).await;
+                    }
+                    Ok(updated) => {
+                        add_log(&tui_state, device, format!("📦 Insert the next disc: {}", updated.summary(0))).await;
+                    }
+                    Err(e) => tracing::warn!("Failed to update rip session: {}", e),
+                }
+            }
+            
+            // Remember where this disc sits in its box set so the next disc continues the numbering
+            let show_name = session.as_ref().map(|s| s.show_name.clone()).or_else(|| dvd_metadata.as_ref()
+                .filter(|m| m.media_type == crate::dvd_metadata::MediaType::TVShow)
+                .map(|m| m.title.clone()));
+            if let (Some(db), Some(show_name)) = (db.as_ref(), show_name) {
+                // The first episode the titles confidently matched tells a new disc's season, even when the label doesn't
+                let first_match = matched_episodes.iter()
+                    .filter(|a| !a.needs_review)
+                    .map(|a| (a.episode_match.season, a.episode_match.episode))
+                    .min();
+                if let (None, Some((season, first_episode))) = (assignment.as_ref(), first_match) {
+                    add_log(&tui_state, device, format!("📚 Titles matched season {}, starting at episode {}", season, first_episode)).await;
+                }
+                let disc_assignment = crate::database::DiscAssignment::after_rip(
+                    assignment.as_ref(), show_name, first_match, fingerprint.clone(), disc_key.clone(), disc_episodes,
+                );
+                if let Err(e) = db.save_disc_assignment(&disc_assignment) {
+                    tracing::warn!("Failed to save disc assignment: {}", e);
+                }
+            }
+            
+            // Movie titles are named after the movie, with extras in their extras folders
+            if let (Some(plan), Some(metadata)) = (movie_plan.as_ref(), dvd_metadata.as_ref()) {
+                let journal = crate::rename_journal::RenameJournal::new(db.as_deref(), false);
+                let config = crate::config::Config::load().unwrap_or_default();
+                let files = ripped_files.lock().await.clone();
+                for (path, title_num, _) in &files {
+                    let Some(movie_title) = plan.get(*title_num) else {
+                        continue;
+                    };
+                    let renamed = crate::movie::movie_path(&config, &dvd_dir, path, metadata, movie_title)
+                        .and_then(|dest| {
+                            journal.rename(path, &dest, "movie")?;
+                            Ok(dest)
+                        });
+                    match renamed {
+                        Ok(dest) => add_rename_log(&tui_state, device, format!("  ✓ {}", dest.strip_prefix(&dvd_dir).unwrap_or(&dest).display())).await,
+                        Err(e) => add_rename_log(&tui_state, device, format!("  ⚠️  Failed to rename {}: {}", path.display(), e)).await,
+                    }
+                }
+                add_rename_log(&tui_state, device, format!("↩️  Undo with: ripley rename --undo {}", journal.batch_id())).await;
+            }
+            
+            // Titles confidently matched by runtime or playlist order are named right away; dialogue matching handles the rest
+            let mut renamed: Vec<(std::path::PathBuf, std::path::PathBuf)> = Vec::new();
+            if let Some(metadata) = dvd_metadata.as_ref().filter(|m| m.media_type == crate::dvd_metadata::MediaType::TVShow) {
+                let journal = crate::rename_journal::RenameJournal::new(db.as_deref(), false);
+                let config = crate::config::Config::load().unwrap_or_default();
+                let files = ripped_files.lock().await.clone();
+                for (path, _, matched) in &files {
+                    let Some(assignment) = matched.as_ref().filter(|a| !a.needs_review) else {
+                        continue;
+                    };
+                    let ep_match = &assignment.episode_match;
+                    let new_path = crate::rename::episode_path(&config, path, &metadata.title, metadata.year.as_deref(), ep_match)
+                        .and_then(|new_path| {
+                            journal.rename(path, &new_path, &ep_match.match_method)?;
+                            Ok(new_path)
+                        });
+                    match new_path {
+                        Ok(new_path) => {
+                            add_rename_log(&tui_state, device, format!("  ✓ S{:02}E{:02}: {} (by {})", 
+                                ep_match.season, ep_match.episode, ep_match.title, ep_match.match_method)).await;
+                            renamed.push((path.clone(), new_path));
+                        }
+                        Err(e) => add_rename_log(&tui_state, device, format!("  ⚠️  Failed to rename {}: {}", path.display(), e)).await,
+                    }
+                }
+                if !renamed.is_empty() {
+                    add_rename_log(&tui_state, device, format!("↩️  Undo with: ripley rename --undo {}", journal.batch_id())).await;
+                }
+            }
             
             // Run OCR + Filebot by default (unless --skip-filebot) if we have metadata
             if !args.skip_filebot {
@@ -498,22 +756,36 @@ async fn rip_dvd_disc(
                         add_rename_log(&tui_state, device, "🎤 Analyzing dialogue to identify episodes...".to_string()).await;
                         
                         let mut matched_count = 0;
-                        let mut read_dir = tokio::fs::read_dir(&dvd_dir).await?;
+                        let journal = crate::rename_journal::RenameJournal::new(db.as_deref(), false);
+                        let config = crate::config::Config::load().unwrap_or_default();
                         let mut files = Vec::new();
                         
-                        while let Some(entry) = read_dir.next_entry().await? {
-                            let path = entry.path();
-                            if path.extension().and_then(|s| s.to_str()) == Some("mkv") {
-                                files.push(path);
+                        // The season folder of a box-set session also holds earlier discs, so only match this disc's files
+                        if session.is_some() {
+                            files = ripped_files.lock().await.iter()
+                                .map(|(path, _, _)| path.clone())
+                                .filter(|path| !renamed.iter().any(|(old, _)| old == path))
+                                .collect();
+                        } else {
+                            let mut read_dir = tokio::fs::read_dir(&dvd_dir).await?;
+                            while let Some(entry) = read_dir.next_entry().await? {
+                                let path = entry.path();
+                                if path.extension().and_then(|s| s.to_str()) == Some("mkv") && !renamed.iter().any(|(_, new)| *new == path) {
+                                    files.push(path);
+                                }
                             }
                         }
                         
+                        files.sort();
+                        
+                        // Transcribe and match every file first, then assign episodes across the whole disc
+                        let mut evidence = Vec::new();
                         for (idx, path) in files.iter().enumerate() {
                             // Use separate rename log window
                             add_rename_log(&tui_state, device, format!("[{}/{}] Processing {}...", idx + 1, files.len(), path.file_name().unwrap().to_string_lossy())).await;
                             
                             // Extract and transcribe audio
-                            match crate::speech_match::extract_and_transcribe_audio(path).await {
+                            let transcript_match = match crate::speech_match::extract_and_transcribe_audio(path).await {
                                 Ok(transcript) => {
                                     add_rename_log(&tui_state, device, format!("  Transcribed {} characters", transcript.len())).await;
                                     
@@ -524,33 +796,58 @@ async fn rip_dvd_disc(
                                         &metadata.episodes
                                     ).await {
                                         Ok(ep_match) => {
-                                            // Only rename if confidence is high enough
-                                            if ep_match.confidence >= 85.0 {
-                                                let show_name = metadata.title.replace(' ', ".");
-                                                let episode_title = ep_match.title.replace(' ', ".");
-                                                let new_name = format!("{}.S{:02}E{:02}.{}.mkv", 
-                                                    show_name, ep_match.season, ep_match.episode, 
-                                                    episode_title);
-                                                let new_path = dvd_dir.join(&new_name);
-                                                
-                                                if let Err(e) = tokio::fs::rename(&path, &new_path).await {
-                                                    add_rename_log(&tui_state, device, format!("  ⚠️  Failed to rename: {}", e)).await;
-                                                } else {
-                                                    add_rename_log(&tui_state, device, format!("  ✓ S{:02}E{:02}: {} (confidence: {:.0}%)", 
-                                                        ep_match.season, ep_match.episode, ep_match.title, ep_match.confidence)).await;
-                                                    matched_count += 1;
-                                                }
-                                            } else {
-                                                add_rename_log(&tui_state, device, format!("  ⚠️  Low confidence ({:.0}%), skipping rename", ep_match.confidence)).await;
-                                            }
+                                            add_rename_log(&tui_state, device, format!("  Dialogue suggests S{:02}E{:02}: {} ({:.0}%)", 
+                                                ep_match.season, ep_match.episode, ep_match.title, ep_match.confidence)).await;
+                                            Some(ep_match)
                                         }
                                         Err(e) => {
                                             add_rename_log(&tui_state, device, format!("  ⚠️  Matching failed: {}", e)).await;
+                                            None
                                         }
                                     }
                                 }
                                 Err(e) => {
                                     add_rename_log(&tui_state, device, format!("  ⚠️  Transcription failed: {}", e)).await;
+                                    None
+                                }
+                            };
+                            
+                            evidence.push(crate::episode_assignment::TitleEvidence {
+                                title_index: idx as u32,
+                                minutes: crate::speech_match::video_duration_minutes(path).await.ok(),
+                                transcript_match,
+                            });
+                        }
+                        
+                        let assignments = crate::episode_assignment::assign_episodes(&evidence, &metadata.episodes, selection.tolerance_minutes());
+                        for assignment in &assignments {
+                            let path = &files[assignment.title_index as usize];
+                            let ep_match = &assignment.episode_match;
+                            
+                            // Only rename if confidence is high enough
+                            if !assignment.needs_review {
+                                let renamed = crate::rename::episode_path(&config, path, &metadata.title, metadata.year.as_deref(), ep_match)
+                                    .and_then(|new_path| journal.rename(path, &new_path, "speech"));
+                                
+                                if let Err(e) = renamed {
+                                    add_rename_log(&tui_state, device, format!("  ⚠️  Failed to rename: {}", e)).await;
+                                } else {
+                                    add_rename_log(&tui_state, device, format!("  ✓ S{:02}E{:02}: {} (confidence: {:.0}%)", 
+                                        ep_match.season, ep_match.episode, ep_match.title, ep_match.confidence)).await;
+                                    matched_count += 1;
+                                    if let Some(ref db) = db {
+                                        if let Err(e) = crate::speech_match::record_match(db, &metadata.title, ep_match, None) {
+                                            tracing::warn!("Failed to record episode match: {}", e);
+                                        }
+                                    }
+                                }
+                            } else {
+                                add_rename_log(&tui_state, device, format!("  ⚠️  {}: low confidence ({:.0}%) for S{:02}E{:02}, queued for review", 
+                                    path.file_name().unwrap().to_string_lossy(), ep_match.confidence, ep_match.season, ep_match.episode)).await;
+                                if let Some(ref db) = db {
+                                    if let Err(e) = crate::rename::queue_for_review(db, &metadata.title, path, assignment) {
+                                        tracing::warn!("Failed to queue episode match review: {}", e);
+                                    }
                                 }
                             }
                         }
@@ -558,7 +855,7 @@ async fn rip_dvd_disc(
                         if matched_count > 0 {
                             add_rename_log(&tui_state, device, format!("✅ Matched {} episodes by dialogue", matched_count)).await;
                         } else {
-                            add_rename_log(&tui_state, device, "⚠️  Speech matching unavailable (need Whisper + OpenAI API)".to_string()).await;
+                            add_rename_log(&tui_state, device, "⚠️  No episodes matched confidently by dialogue".to_string()).await;
                         }
                         
                         // Step 2: Run Filebot with OCR-enhanced filenames
@@ -569,9 +866,13 @@ async fn rip_dvd_disc(
                         let tui_state_filebot = Arc::clone(&tui_state);
                         let device_filebot = device.to_string();
                         
+                        let order = db.as_ref().map(|db| db.episode_order_for_show(&show_title)).unwrap_or_default();
                         match crate::filebot::rename_with_filebot(
                             &dvd_dir_clone,
                             &show_title,
+                            order,
+                            &config,
+                            &journal,
                             move |log_msg| {
                                 let device = device_filebot.clone();
                                 let tui_state = Arc::clone(&tui_state_filebot);
@@ -587,6 +888,7 @@ async fn rip_dvd_disc(
                                 add_rename_log(&tui_state, device, format!("⚠️  Filebot failed: {}", e)).await;
                             }
                         }
+                        add_rename_log(&tui_state, device, format!("↩️  Undo with: ripley rename --undo {}", journal.batch_id())).await;
                     }
                 }
             }
@@ -650,6 +952,55 @@ async fn rip_dvd_disc(
     Ok(())
 }
 
+/// Ask in the TUI which show or movie is on the disc when the search can't tell; returns the candidates to try
+///
+/// Esc ("none of these") leaves no candidates, so the disc is named from the entered title. With no answer
+/// within `METADATA_CHOICE_TIMEOUT` every candidate is tried, best first, as in the web UI.
+async fn choose_metadata_candidate(
+    tui_state: &Arc<Mutex<crate::tui::AppState>>,
+    device: &str,
+    candidates: Vec<crate::dvd_metadata::MetadataCandidate>,
+) -> Vec<crate::dvd_metadata::MetadataCandidate> {
+    if !crate::dvd_metadata::is_ambiguous(&candidates) {
+        return candidates;
+    }
+    
+    {
+        let mut state = tui_state.lock().await;
+        state.add_drive_log(device, format!("❓ {} possible matches - please choose one...", candidates.len()));
+        state.chosen_candidate = None;
+        state.input_mode = crate::tui::InputMode::ChoosingMetadata {
+            device: device.to_string(),
+            candidates: candidates.clone(),
+            selected: 0,
+        };
+    }
+    
+    // Wait for this dialog's choice; closing another drive's dialog doesn't answer it
+    let deadline = tokio::time::Instant::now() + crate::dvd_metadata::METADATA_CHOICE_TIMEOUT;
+    let chosen = loop {
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        let mut state = tui_state.lock().await;
+        
+        if state.chosen_candidate.as_ref().is_some_and(|(d, _)| d == device) {
+            break state.chosen_candidate.take().map(|(_, index)| index);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            if matches!(state.input_mode, crate::tui::InputMode::ChoosingMetadata { device: ref d, .. } if d == device) {
+                state.input_mode = crate::tui::InputMode::Normal;
+            }
+            state.add_drive_log(device, format!("⏱️  No choice made, using '{}'", candidates[0].title));
+            break None;
+        }
+    };
+    
+    match chosen {
+        Some(Some(index)) => candidates.into_iter().skip(index).take(1).collect(),
+        Some(None) => Vec::new(),
+        None => candidates,
+    }
+}
+
 async fn get_dvd_volume_name(device: &str) -> Result<String> {
     debug!("Getting volume name for device: {}", device);
     
@@ -688,7 +1039,7 @@ async fn get_dvd_volume_name_macos(device: &str) -> Result<String> {
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty() && s != "Not applicable (no file system)")
         .ok_or_else(|| {
-            warn!("No 'Volume Name:' field found in diskutil output");
+            tracing::warn!("No 'Volume Name:' field found in diskutil output");
             anyhow::anyhow!("No volume name found")
         })?;
     
@@ -3,8 +3,11 @@ use iced::widget::image::Handle;
 use iced::{Alignment, Application, Command, Element, Length, Settings, Theme, Color};
 use iced::widget::container::Appearance;
 use ripley::config::{Config, ShowSeed};
+use ripley::metadata_provider;
+use ripley::naming::{self, MediaKind, NameFields};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use walkdir::WalkDir;
 
 fn main() -> iced::Result {
@@ -44,6 +47,7 @@ struct RenameApp {
     error_message: Option<String>,
     success_message: Option<String>, // Success message that auto-dismisses
     config: Config,
+    db: Option<Arc<ripley::database::Database>>, // Opened once; renames are journaled to it
     show_preview_settings: HashMap<String, (u32, u32)>, // show_name -> (start_time, duration)
     show_name_map: HashMap<String, String>, // normalized -> original name
 }
@@ -113,6 +117,7 @@ impl Application for RenameApp {
             error_message: None,
             success_message: None,
             config,
+            db: ripley::database::Database::new().ok().map(Arc::new),
             show_preview_settings,
             show_name_map,
         };
@@ -277,7 +282,7 @@ impl Application for RenameApp {
                         }
                         
                         let rename_cmd = Command::perform(
-                            rename_file(file, new_name),
+                            rename_file(self.db.clone(), file, new_name),
                             Message::RenameComplete
                         );
                         return rename_cmd;
@@ -753,54 +758,44 @@ impl RenameApp {
     fn generate_new_filename(&self) -> String {
         // Parse episode info from episode_name field (format: "S##E## - Episode Title")
         // Or use episode_info if available
-        if let Some(ref info) = self.episode_info {
-            // Format: Show.S##E##.Episode.Title.ext
-            let show_name = self.show_name.replace(' ', ".");
+        let episode = if let Some(ref info) = self.episode_info {
             let episode_title = info.title.split('/').next().unwrap_or(&info.title).trim();
-            let episode_title_clean = episode_title.replace(' ', ".");
-            
-            if let Some(index) = self.selected_index {
-                if index < self.files.len() {
-                    let file = &self.files[index];
-                    if let Some(ext) = file.path.extension().and_then(|s| s.to_str()) {
-                        return format!("{}.S{:02}E{:02}.{}.{}", 
-                            show_name, info.season, info.episode, episode_title_clean, ext);
-                    }
-                }
-            }
+            Some((info.season, info.episode, episode_title.to_string()))
         } else if !self.episode_name.is_empty() {
             // Try to parse from episode_name field
             // Format: "S##E## - Episode Title" or just "Episode Title"
             let re = regex::Regex::new(r"(?i)S(\d+)E(\d+)").ok();
-            if let Some(re) = re {
-                if let Some(caps) = re.captures(&self.episode_name) {
-                    if let (Ok(season), Ok(episode)) = (caps[1].parse::<u32>(), caps[2].parse::<u32>()) {
-                        // Extract episode title (everything after " - " or after "S##E##")
-                        let episode_title = if let Some(dash_pos) = self.episode_name.find(" - ") {
-                            self.episode_name[dash_pos + 3..].trim()
-                        } else if let Some(m) = re.find(&self.episode_name) {
-                            self.episode_name[m.end()..].trim_start_matches(|c: char| c == ' ' || c == '-' || c == '.')
-                        } else {
-                            &self.episode_name
-                        };
-                        
-                        let show_name = self.show_name.replace(' ', ".");
-                        let episode_title_clean = episode_title.split('/').next().unwrap_or(episode_title).trim().replace(' ', ".");
-                        
-                        if let Some(index) = self.selected_index {
-                            if index < self.files.len() {
-                                let file = &self.files[index];
-                                if let Some(ext) = file.path.extension().and_then(|s| s.to_str()) {
-                                    return format!("{}.S{:02}E{:02}.{}.{}", 
-                                        show_name, season, episode, episode_title_clean, ext);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        String::new()
+            re.and_then(|re| {
+                let caps = re.captures(&self.episode_name)?;
+                let (season, episode) = (caps[1].parse::<u32>().ok()?, caps[2].parse::<u32>().ok()?);
+                // Extract episode title (everything after " - " or after "S##E##")
+                let episode_title = if let Some(dash_pos) = self.episode_name.find(" - ") {
+                    self.episode_name[dash_pos + 3..].trim()
+                } else if let Some(m) = re.find(&self.episode_name) {
+                    self.episode_name[m.end()..].trim_start_matches([' ', '-', '.'])
+                } else {
+                    &self.episode_name
+                };
+                let episode_title = episode_title.split('/').next().unwrap_or(episode_title).trim();
+                Some((season, episode, episode_title.to_string()))
+            })
+        } else {
+            None
+        };
+        
+        let (Some((season, episode, title)), Some(file)) = (episode, self.selected_index.and_then(|i| self.files.get(i))) else {
+            return String::new();
+        };
+        let Some(ext) = file.path.extension().and_then(|s| s.to_str()) else {
+            return String::new();
+        };
+        
+        // Named with the show's template (Show.S##E##.Episode.Title.ext by default), relative to the file's folder
+        let template = self.config.naming_template(MediaKind::Episode, Some(&self.show_name));
+        let fields = NameFields::episode(&self.show_name, season, episode, Some(&title)).with_ext(ext);
+        naming::render(&template, &fields)
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_default()
     }
 }
 
@@ -858,8 +853,7 @@ fn extract_show_name(filename: &str) -> String {
         let before = &name[..caps.start()];
         // Clean up: remove trailing dots, dashes, underscores, spaces, and volume/disc info
         let cleaned = before.trim_end_matches(|c: char| c == '.' || c == '-' || c == '_' || c.is_whitespace())
-            .replace('_', " ")
-            .replace('.', " ");
+            .replace(['_', '.'], " ");
         
         // Remove common patterns like "VOLUME 2 DISC 1", "DISC 1", "VOLUME 2", etc.
         let re_volume = regex::Regex::new(r"(?i)\s*(VOLUME|DISC|V|D)\s*\d+.*$").ok();
@@ -885,8 +879,7 @@ fn extract_show_name(filename: &str) -> String {
                 .to_string()
         };
         
-        cleaned.replace('_', " ")
-            .replace('.', " ")
+        cleaned.replace(['_', '.'], " ")
             .trim()
             .to_string()
     }
@@ -960,93 +953,37 @@ async fn extract_preview_frames_at_position(
 }
 
 async fn find_episode_by_title(show_name: String, episode_title: String, config: Config) -> Result<EpisodeInfo, String> {
-    let api_key = config.tmdb_api_key
-        .ok_or_else(|| "TMDB API key not configured".to_string())?;
-    
-    let client = reqwest::Client::builder()
-        .user_agent("Ripley/0.1.0")
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let provider = metadata_provider::video_provider_from_config(&config)
+        .map_err(|e| format!("Metadata provider not configured: {}", e))?;
     
     // Search for TV show
-    let search_url = format!(
-        "https://api.themoviedb.org/3/search/tv?api_key={}&query={}",
-        api_key,
-        urlencoding::encode(&show_name)
-    );
-    
-    let response = client.get(&search_url)
-        .send()
+    let results = provider.search_show(&show_name)
         .await
-        .map_err(|e| format!("TMDB request failed: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("TMDB API returned error: {}", response.status()));
-    }
-    
-    let json: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse TMDB response: {}", e))?;
-    
-    let results = json.get("results")
-        .and_then(|r| r.as_array())
-        .ok_or_else(|| "No results found".to_string())?;
-    
-    if results.is_empty() {
-        return Err("Show not found in TMDB".to_string());
-    }
-    
-    let show = results[0].as_object()
-        .ok_or_else(|| "Invalid show data".to_string())?;
+        .map_err(|e| format!("{} request failed: {}", provider.name(), e))?;
     
-    let show_id = show.get("id")
-        .and_then(|id| id.as_i64())
-        .ok_or_else(|| "Invalid show ID".to_string())?;
+    let show = results.first()
+        .ok_or_else(|| format!("Show not found on {}", provider.name()))?;
     
     // Search through seasons to find matching episode by title
     // Try first 5 seasons (covers most shows)
     let episode_title_lower = episode_title.to_lowercase();
     
     for season_num in 1..=5 {
-        let season_url = format!(
-            "https://api.themoviedb.org/3/tv/{}/season/{}?api_key={}",
-            show_id, season_num, api_key
-        );
-        
-        if let Ok(season_response) = client.get(&season_url).send().await {
-            if season_response.status().is_success() {
-                if let Ok(season_json) = season_response.json::<serde_json::Value>().await {
-                    if let Some(episodes) = season_json.get("episodes").and_then(|e| e.as_array()) {
-                        for ep in episodes {
-                            if let Some(ep_obj) = ep.as_object() {
-                                let ep_title = ep_obj.get("name")
-                                    .and_then(|n| n.as_str())
-                                    .unwrap_or("")
-                                    .to_string();
-                                
-                                // Check if episode title matches (case-insensitive, partial match)
-                                let ep_title_lower = ep_title.to_lowercase();
-                                if ep_title_lower.contains(&episode_title_lower) || 
-                                   episode_title_lower.contains(&ep_title_lower) ||
-                                   ep_title_lower == episode_title_lower {
-                                    // Found matching episode!
-                                    let episode_num = ep_obj.get("episode_number")
-                                        .and_then(|n| n.as_u64())
-                                        .map(|n| n as u32)
-                                        .unwrap_or(0);
-                                    
-                                    return Ok(EpisodeInfo {
-                                        season: season_num,
-                                        episode: episode_num,
-                                        title: ep_title,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
+        let Ok(episodes) = provider.list_episodes(&show.id, season_num).await else {
+            continue;
+        };
+        for ep in episodes {
+            // Check if episode title matches (case-insensitive, partial match)
+            let ep_title_lower = ep.title.to_lowercase();
+            if ep_title_lower.contains(&episode_title_lower) || 
+               episode_title_lower.contains(&ep_title_lower) ||
+               ep_title_lower == episode_title_lower {
+                // Found matching episode!
+                return Ok(EpisodeInfo {
+                    season: season_num,
+                    episode: ep.episode,
+                    title: ep.title,
+                });
             }
         }
     }
@@ -1094,7 +1031,7 @@ impl container::StyleSheet for SelectedEpisodeStyle {
     }
 }
 
-async fn rename_file(file_path: PathBuf, new_name: String) -> Result<(), String> {
+async fn rename_file(db: Option<Arc<ripley::database::Database>>, file_path: PathBuf, new_name: String) -> Result<(), String> {
     if new_name.is_empty() {
         return Err("New filename is empty".to_string());
     }
@@ -1104,8 +1041,8 @@ async fn rename_file(file_path: PathBuf, new_name: String) -> Result<(), String>
     
     let new_path = parent.join(&new_name);
     
-    tokio::fs::rename(&file_path, &new_path)
-        .await
+    ripley::rename_journal::RenameJournal::new(db.as_deref(), false)
+        .rename(&file_path, &new_path, "gui")
         .map_err(|e| format!("Failed to rename file: {}", e))?;
     
     Ok(())
@@ -0,0 +1,352 @@
+use anyhow::{anyhow, Result};
+use std::cmp::Reverse;
+use std::path::Path;
+use tracing::info;
+
+use crate::disc_scan::{DiscScan, TitleInfo};
+use crate::dvd_metadata::{DvdMetadata, Episode, MediaType};
+use crate::dvd_ripper::{find_redundant_titles, prepare_disc, rip_titles, EpisodeFuture};
+use crate::episode_assignment::Assignment;
+use crate::speech_match::EpisodeMatch;
+use crate::rip_backend::RipBackend;
+use crate::ripper::RipProgress;
+use crate::title_selection::TitleSelection;
+
+/// Playlists within 1/50th (2%) of the longest one compete for main feature
+pub(crate) const MAIN_FEATURE_SLACK: u32 = 50;
+
+/// The playlist picked as a movie's main feature
+#[derive(Debug)]
+pub struct MainFeature<'a> {
+    pub title: &'a TitleInfo,
+    pub decoys: usize, // Other playlists of (nearly) the same length, e.g. obfuscation decoys
+}
+
+/// A Blu-ray playlist matched to a TV episode
+#[derive(Debug, Clone)]
+pub struct PlaylistEpisode {
+    pub title_index: u32,
+    pub playlist: Option<String>,
+    pub episode: Episode,
+    pub unambiguous: bool, // No other run of episodes fits the playlists as well
+}
+
+/// Playlist number from the MakeMKV source file name ("00800.mpls" -> 800)
+pub fn playlist_number(title: &TitleInfo) -> Option<u32> {
+    title.source_file.as_deref()?
+        .strip_suffix(".mpls")?
+        .parse()
+        .ok()
+}
+
+/// How often the clip order jumps backwards; obfuscated playlists shuffle their clips
+fn backward_jumps(title: &TitleInfo) -> usize {
+    let clips: Vec<u32> = title.segments().iter().filter_map(|s| s.parse().ok()).collect();
+    clips.windows(2).filter(|w| w[1] < w[0]).count()
+}
+
+/// Pick the main feature from a movie disc's playlists
+///
+/// Obfuscated discs carry dozens of playlists with the same runtime that stitch short clips
+/// together out of order. Among the longest playlists the one with the fewest backward jumps
+/// and fewest segments wins, then most chapters, then the largest and lowest-numbered.
+pub fn find_main_feature(titles: &[TitleInfo]) -> Option<MainFeature<'_>> {
+    let longest = titles.iter().map(|t| t.duration_seconds).max()?;
+    let threshold = longest - longest / MAIN_FEATURE_SLACK;
+    let candidates: Vec<&TitleInfo> = titles.iter()
+        .filter(|t| t.duration_seconds >= threshold)
+        .collect();
+
+    let title = candidates.iter()
+        .min_by_key(|t| {
+            let segments = if t.segment_count > 0 { t.segment_count as usize } else { t.segments().len() };
+            (
+                backward_jumps(t),
+                segments,
+                Reverse(t.chapter_count),
+                Reverse(t.size_bytes.unwrap_or(0)),
+                playlist_number(t).unwrap_or(u32::MAX),
+                t.index,
+            )
+        })?;
+
+    Some(MainFeature { title, decoys: candidates.len() - 1 })
+}
+
+/// Map episode playlists to episodes in playlist order
+///
+/// Blu-ray TV discs store episodes as consecutive playlists. The run of episodes whose runtimes
+/// best fit the playlists is used, so later discs of a season line up with later episodes.
+/// When several runs fit equally well (e.g. every episode has the same runtime) the first is
+/// used and the mapping is marked ambiguous.
+pub fn map_playlists_to_episodes(titles: &[&TitleInfo], episodes: &[Episode]) -> Vec<PlaylistEpisode> {
+    let mut titles = titles.to_vec();
+    titles.sort_by_key(|t| (playlist_number(t).unwrap_or(u32::MAX), t.index));
+    let mut episodes = episodes.to_vec();
+    episodes.sort_by_key(|e| (e.season, e.episode));
+
+    let count = titles.len().min(episodes.len());
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let cost = |offset: usize| -> u32 {
+        titles.iter().take(count).zip(&episodes[offset..offset + count])
+            .filter_map(|(t, e)| e.runtime_minutes.map(|r| t.duration_minutes().abs_diff(r)))
+            .sum()
+    };
+    let costs: Vec<u32> = (0..=episodes.len() - count).map(cost).collect();
+    let best = costs.iter().copied().min().unwrap_or(0);
+    let offset = costs.iter().position(|&c| c == best).unwrap_or(0);
+    let unambiguous = costs.iter().filter(|&&c| c == best).count() == 1;
+
+    titles.iter().take(count).zip(&episodes[offset..offset + count])
+        .map(|(t, e)| {
+            let mut episode = e.clone();
+            episode.title_index = t.index;
+            PlaylistEpisode {
+                title_index: t.index,
+                playlist: t.source_file.clone(),
+                episode,
+                unambiguous,
+            }
+        })
+        .collect()
+}
+
+/// Rip a Blu-ray title by title using the given backend
+///
+/// Movies (or discs without episode-length playlists) rip the main feature and its other cuts,
+/// plus extras when the selection asks for them (see `movie::plan_movie`). TV discs rip every episode-length playlist and map the
+/// playlists to episodes; each ripped file reaches `episode_callback` with its playlist's episode.
+/// Skipped playlists are reported through `log_callback`. Returns the playlist to episode mapping.
+#[allow(clippy::too_many_arguments)]
+pub async fn rip_bluray<F, L, E>(
+    backend: &dyn RipBackend,
+    device: &str,
+    output_dir: &Path,
+    scan: Option<DiscScan>,
+    selection: &TitleSelection,
+    completed_titles: &[u32],
+    metadata: Option<&DvdMetadata>,
+    mut progress_callback: F,
+    mut log_callback: L,
+    mut episode_callback: E,
+) -> Result<Vec<Assignment>>
+where
+    F: FnMut(RipProgress) + Send,
+    L: FnMut(String) + Send,
+    E: for<'a> FnMut(&'a Path, u32, Option<&'a Assignment>) -> EpisodeFuture<'a> + Send,
+{
+    info!("Starting Blu-ray rip from {} ({} backend)", device, backend.name());
+    log_callback("Starting Blu-ray rip...".to_string());
+
+    let scan = prepare_disc(backend, device, output_dir, scan, "Blu-ray", &mut progress_callback, &mut log_callback).await?;
+    let titles = scan.titles;
+    let title_count = titles.len();
+
+    if title_count == 0 {
+        return Err(anyhow!("No titles found on Blu-ray"));
+    }
+
+    info!("Found {} playlists on Blu-ray", title_count);
+    log_callback(format!("Found {} playlists", title_count));
+
+    for t in &titles {
+        info!("Title {} ({}): {}", t.index, t.source_file.as_deref().unwrap_or("?"), t.duration_string());
+    }
+
+    // Drop play-all and duplicate playlists unless the user picked titles explicitly
+    let redundant = if selection.titles.is_some() {
+        Vec::new()
+    } else {
+        find_redundant_titles(&titles)
+    };
+    for (index, reason) in &redundant {
+        log_callback(format!("⏭️  Skipping title {} - {}", index, reason));
+        info!("Skipping title {} - {}", index, reason);
+    }
+    let titles: Vec<TitleInfo> = titles.into_iter()
+        .filter(|t| !redundant.iter().any(|(index, _)| *index == t.index))
+        .collect();
+
+    let picked = selection.select(&titles);
+    let is_tv = match metadata {
+        Some(meta) => meta.media_type == MediaType::TVShow,
+        None => picked.selected.len() >= 2,
+    };
+    let main_feature_only = selection.titles.is_none() && (selection.longest_only || !is_tv);
+
+    let titles_to_rip: Vec<&TitleInfo> = if main_feature_only {
+        let plan = crate::movie::plan_movie(&titles, selection)
+            .ok_or_else(|| anyhow!("No main feature found on Blu-ray"))?;
+        plan.describe(&titles).into_iter().for_each(&mut log_callback);
+        plan.select(&titles).selected
+    } else {
+        for (title, reason) in &picked.skipped {
+            log_callback(format!("⏭️  Skipping title {} ({} min) - {}", title.index, title.duration_minutes(), reason));
+            info!("Skipping title {} ({} min) - {}", title.index, title.duration_minutes(), reason);
+        }
+        picked.selected
+    };
+
+    // Map episode playlists to episodes
+    let mut matched_episodes = Vec::new();
+    if let Some(meta) = metadata.filter(|m| m.media_type == MediaType::TVShow && !m.episodes.is_empty()) {
+        if !main_feature_only {
+            log_callback("Mapping playlists to episodes...".to_string());
+            for mapped in map_playlists_to_episodes(&titles_to_rip, &meta.episodes) {
+                log_callback(format!(
+                    "📺 {} → S{:02}E{:02} '{}'",
+                    mapped.playlist.unwrap_or_else(|| format!("title {}", mapped.title_index)),
+                    mapped.episode.season,
+                    mapped.episode.episode,
+                    mapped.episode.title
+                ));
+                // Playlist order is either the only run of episodes that fits or a guess
+                matched_episodes.push(Assignment {
+                    title_index: mapped.title_index,
+                    episode_match: EpisodeMatch {
+                        season: mapped.episode.season,
+                        episode: mapped.episode.episode,
+                        title: mapped.episode.title,
+                        confidence: if mapped.unambiguous { 100.0 } else { 0.0 },
+                        match_method: "playlist".to_string(),
+                    },
+                    needs_review: !mapped.unambiguous,
+                    candidates: Vec::new(),
+                });
+            }
+        }
+    }
+
+    log_callback(format!("Ripping {} titles (filtered from {})", titles_to_rip.len(), title_count));
+    info!("Starting Blu-ray rip of titles: {:?}", titles_to_rip.iter().map(|t| t.index).collect::<Vec<_>>());
+
+    rip_titles(backend, device, output_dir, &titles_to_rip, completed_titles, &matched_episodes, &mut progress_callback, &mut log_callback, &mut episode_callback).await?;
+
+    info!("Successfully ripped all Blu-ray titles");
+    log_callback("✅ Blu-ray rip complete".to_string());
+
+    Ok(matched_episodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rip_backend::{FakeBackend, FakeScript, FakeTitle};
+
+    fn playlist(index: u32, mpls: u32, minutes: u32, segments: &str) -> TitleInfo {
+        TitleInfo {
+            index,
+            duration_seconds: minutes * 60,
+            source_file: Some(format!("{:05}.mpls", mpls)),
+            segment_map: Some(segments.to_string()),
+            segment_count: segments.split(',').count() as u32,
+            ..Default::default()
+        }
+    }
+
+    fn episode(number: u32, runtime: u32) -> Episode {
+        Episode {
+            season: 1,
+            episode: number,
+            title: format!("Episode {}", number),
+            title_index: 0,
+            runtime_minutes: Some(runtime),
+            overview: None,
+        }
+    }
+
+    #[test]
+    fn test_playlist_number() {
+        assert_eq!(playlist_number(&playlist(0, 800, 10, "1")), Some(800));
+        assert_eq!(playlist_number(&TitleInfo::default()), None);
+    }
+
+    #[test]
+    fn test_main_feature_skips_obfuscated_playlists() {
+        let titles = vec![
+            playlist(0, 1, 2, "1"),
+            playlist(1, 800, 121, "10,12,11,14,13,15"),
+            playlist(2, 801, 121, "15,14,13,12,11,10"),
+            playlist(3, 802, 121, "10,11,12,13,14,15"),
+            playlist(4, 803, 120, "10,11,13,12,14,15"),
+            playlist(5, 50, 15, "20"),
+        ];
+        let main = find_main_feature(&titles).unwrap();
+        assert_eq!(main.title.index, 3);
+        assert_eq!(main.decoys, 3);
+    }
+
+    #[test]
+    fn test_map_playlists_to_later_episodes() {
+        let titles = [playlist(0, 3, 45, "3"), playlist(1, 1, 22, "1"), playlist(2, 2, 23, "2")];
+        let refs: Vec<&TitleInfo> = titles.iter().collect();
+        let episodes = vec![episode(1, 23), episode(2, 23), episode(3, 22), episode(4, 22), episode(5, 44)];
+
+        let mapped = map_playlists_to_episodes(&refs, &episodes);
+        let pairs: Vec<(Option<&str>, u32)> = mapped.iter()
+            .map(|m| (m.playlist.as_deref(), m.episode.episode))
+            .collect();
+        assert_eq!(pairs, vec![(Some("00001.mpls"), 3), (Some("00002.mpls"), 4), (Some("00003.mpls"), 5)]);
+        assert_eq!(mapped[0].episode.title_index, 1);
+        assert!(mapped.iter().all(|m| m.unambiguous));
+
+        // Same runtimes throughout: any run of episodes fits
+        let same: Vec<Episode> = (1..=5).map(|e| episode(e, 22)).collect();
+        let mapped = map_playlists_to_episodes(&refs[1..], &same);
+        assert_eq!(mapped[0].episode.episode, 1);
+        assert!(mapped.iter().all(|m| !m.unambiguous));
+    }
+
+    fn fake_title(index: u32, mpls: u32, minutes: u32, segments: &str) -> FakeTitle {
+        FakeTitle {
+            index,
+            name: None,
+            duration_seconds: minutes * 60,
+            chapter_count: 8,
+            segments: Some(segments.to_string()),
+            source_file: Some(format!("{:05}.mpls", mpls)),
+            size_bytes: 1024,
+            fail: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rip_bluray_movie_main_feature() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = FakeScript {
+            media_type: Some(crate::drive::MediaType::BluRay),
+            titles: vec![
+                fake_title(0, 800, 118, "5,3,4,1,2"),
+                fake_title(1, 801, 118, "1,2,3,4,5"),
+                fake_title(2, 10, 12, "9"),
+            ],
+            ..FakeScript::default()
+        };
+        let backend = FakeBackend::new(script);
+        let selection = TitleSelection { include_extras: true, ..Default::default() };
+        let mut ripped = Vec::new();
+        let mut logs = Vec::new();
+
+        rip_bluray(
+            &backend,
+            "/dev/fake",
+            dir.path(),
+            None,
+            &selection,
+            &[],
+            None,
+            |_| {},
+            |line| logs.push(line),
+            |_: &Path, title_num: u32, _| {
+                ripped.push(title_num);
+                Box::pin(async { Ok(()) })
+            },
+        ).await.unwrap();
+
+        assert_eq!(ripped, vec![1, 2]);
+        assert!(logs.iter().any(|l| l.starts_with("🎬 Main feature: title 1 (00801.mpls")));
+    }
+}
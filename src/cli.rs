@@ -48,6 +48,21 @@ pub enum Command {
         #[arg(long)]
         #[arg(help = "Don't run Filebot after speech matching")]
         skip_filebot: bool,
+
+        /// Rip profile from config.yaml
+        #[arg(short, long, value_name = "PROFILE")]
+        #[arg(help = "Rip profile to use (default: the profile marked is_default)")]
+        profile: Option<String>,
+
+        /// Box-set session to continue
+        #[arg(long, value_name = "ID")]
+        #[arg(help = "Rip discs into this box-set session (see the web UI's sessions)")]
+        session: Option<i64>,
+
+        /// Titles to rip instead of the title selection rules
+        #[arg(long, value_name = "TITLES", value_delimiter = ',')]
+        #[arg(help = "Rip only these title numbers (e.g. 1,3,4)")]
+        titles: Vec<u32>,
     },
 
     /// 📝 Rename existing video files using AI episode matching + Filebot
@@ -72,6 +87,21 @@ pub enum Command {
         #[arg(long)]
         #[arg(help = "Only use speech/subtitle matching")]
         skip_filebot: bool,
+        
+        /// Print the planned renames without touching any files
+        #[arg(long)]
+        #[arg(help = "Show what would be renamed without renaming")]
+        dry_run: bool,
+        
+        /// Episode numbering to rename into
+        #[arg(long, value_name = "ORDER")]
+        #[arg(help = "aired, dvd, absolute, story-arc, production or digital (default: the show's saved order)")]
+        order: Option<String>,
+        
+        /// Revert a previous rename run
+        #[arg(long, value_name = "BATCH")]
+        #[arg(help = "Undo the renames in a batch (the ID is printed after each run)")]
+        undo: Option<String>,
     },
 
     /// 🌐 Start REST API server for remote control (for web UI)
@@ -129,6 +159,9 @@ pub struct RipArgs {
     pub skip_metadata: bool,
     pub title: Option<String>,
     pub skip_filebot: bool,
+    pub profile: Option<String>,
+    pub session_id: Option<i64>,
+    pub titles: Option<Vec<u32>>, // Explicit titles to rip (None = use title selection rules)
     // Legacy audio CD fields
     pub quality: u8,
     pub eject_when_done: bool,
@@ -3,8 +3,12 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
+use crate::title_selection::TitleSelection;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    #[serde(default)]
+    pub openai_api_key: Option<String>,
     pub tmdb_api_key: Option<String>,
     pub notifications: NotificationConfig,
     pub rsync: RsyncConfig,
@@ -14,6 +18,10 @@ pub struct Config {
     pub rip_profiles: Vec<RipProfile>,
     #[serde(default)]
     pub seed: SeedConfig,
+    #[serde(default)]
+    pub naming: NamingConfig,
+    #[serde(default)]
+    pub metadata: MetadataConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,8 +40,35 @@ pub struct RsyncConfig {
 pub struct SpeechMatchConfig {
     pub enabled: bool,
     pub audio_duration: u32,
-    pub whisper_model: String,
+    pub whisper_model: String, // Model name ("base"), or a ggml model path for whisper.cpp
     pub use_openai_api: bool,
+    #[serde(default)]
+    pub transcriber: Option<String>, // "whisper.cpp" (default) or "faster-whisper"
+    #[serde(default)]
+    pub whisper_binary: Option<String>, // Defaults to whisper-cli / whisper-ctranslate2 on PATH
+    #[serde(default)]
+    pub whisper_language: Option<String>, // e.g. "en"; the engine's default when unset
+    #[serde(default)]
+    pub matcher: Option<String>, // "overview" (default, offline) or "llm"
+    #[serde(default)]
+    pub llm: Option<LlmMatcherConfig>, // Endpoint for the "llm" matcher
+    #[serde(default)]
+    pub tesseract_binary: Option<String>, // OCR for DVD/Blu-ray image subtitles; defaults to tesseract on PATH
+    #[serde(default)]
+    pub ocr_language: Option<String>, // tesseract language code; "eng" when unset
+    #[serde(default)]
+    pub reference_subtitles: Option<String>, // Directory of per-episode .srt files (default: ~/.cache/ripley/subtitles)
+}
+
+/// OpenAI-compatible chat completions endpoint (OpenAI, llama.cpp server, Ollama, ...) used to match episodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmMatcherConfig {
+    pub base_url: String, // e.g. http://localhost:11434/v1
+    pub model: String,
+    #[serde(default)]
+    pub api_key_env: Option<String>, // Environment variable holding the API key; no auth header when unset
+    #[serde(default)]
+    pub prompt_template: Option<String>, // Placeholders: {show}, {episodes}, {transcript}
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +79,44 @@ pub struct FilebotConfig {
     pub use_for_music: bool,  // Whether to use Filebot to standardize music filenames
 }
 
+/// File name templates (see `naming` for placeholders); a show's own `naming` in `seed.shows` wins
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NamingConfig {
+    #[serde(default)]
+    pub layout: Option<String>, // "plex" or "jellyfin" folder layout; flat Show.Name.S01E01.Title.mkv when unset
+    #[serde(default)]
+    pub episode: Option<String>, // e.g. "{show}/Season {season:02}/{show} - S{season:02}E{episode:02} - {title}.{ext}"
+    #[serde(default)]
+    pub movie: Option<String>,
+    #[serde(default)]
+    pub music: Option<String>,
+}
+
+/// Where show, movie and CD metadata comes from (see `metadata_provider`); unset endpoints use the public services
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataConfig {
+    #[serde(default)]
+    pub provider: Option<String>, // "tmdb" (default) or "tvdb" for show and movie lookups
+    #[serde(default)]
+    pub cache_ttl_hours: Option<u64>, // How long cached lookups are reused (default 168); stale ones still serve offline
+    #[serde(default)]
+    pub tmdb: MetadataEndpoint,
+    #[serde(default)]
+    pub tvdb: MetadataEndpoint,
+    #[serde(default)]
+    pub musicbrainz: MetadataEndpoint,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataEndpoint {
+    #[serde(default)]
+    pub base_url: Option<String>, // e.g. http://localhost:8080/3 for a mock server
+    #[serde(default)]
+    pub api_key: Option<String>, // For TMDB, the top-level tmdb_api_key is used when unset
+    #[serde(default)]
+    pub pin: Option<String>, // TheTVDB subscriber PIN
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RetryConfig {
     pub enabled: bool,
@@ -61,6 +134,12 @@ pub struct RipProfile {
     pub audio_quality: Option<u8>, // 0-9 for FLAC quality (audio CDs)
     pub makemkv_profile: Option<String>, // MakeMKV profile name (DVDs/Blu-rays)
     pub is_default: bool,
+    #[serde(default)]
+    pub backend: Option<String>, // Rip backend: makemkv, abcde or fake (default: by media type)
+    #[serde(default)]
+    pub fake_script: Option<PathBuf>, // Script replayed by the fake backend
+    #[serde(default)]
+    pub title_selection: Option<TitleSelection>, // Which disc titles to rip (default: 18-70 min)
 }
 
 /// Topaz profile seed entry
@@ -95,6 +174,8 @@ pub enum ShowSeed {
         topaz_profiles: Vec<String>, // Profile names to associate
         #[serde(default)]
         preview: Option<ShowPreview>, // Preview settings for ripley-rename
+        #[serde(default)]
+        naming: Option<String>, // Episode file name template for this show
     },
 }
 
@@ -110,6 +191,7 @@ pub struct SeedConfig {
 impl Default for Config {
     fn default() -> Self {
         Config {
+            openai_api_key: None, // All values should come from config.yaml
             tmdb_api_key: None, // All values should come from config.yaml
             notifications: NotificationConfig {
                 enabled: false,
@@ -124,6 +206,14 @@ impl Default for Config {
                 audio_duration: 0,
                 whisper_model: String::new(),
                 use_openai_api: false,
+                transcriber: None,
+                whisper_binary: None,
+                whisper_language: None,
+                matcher: None,
+                llm: None,
+                tesseract_binary: None,
+                ocr_language: None,
+                reference_subtitles: None,
             },
             filebot: FilebotConfig {
                 skip_by_default: true,
@@ -140,6 +230,8 @@ impl Default for Config {
             },
             rip_profiles: vec![], // All values should come from config.yaml
             seed: SeedConfig::default(),
+            naming: NamingConfig::default(),
+            metadata: MetadataConfig::default(),
         }
     }
 }
@@ -192,14 +284,12 @@ impl Config {
     
 
     
-    /// Get TMDB API key from config
-    #[allow(dead_code)]
+    /// Get TMDB API key from config (`metadata.tmdb.api_key`, then `tmdb_api_key`)
     pub fn get_tmdb_api_key(&self) -> Option<String> {
-        self.tmdb_api_key.clone()
+        self.metadata.tmdb.api_key.clone().or_else(|| self.tmdb_api_key.clone())
     }
     
     /// Get the default rip profile
-    #[allow(dead_code)]
     pub fn get_default_profile(&self) -> Option<&RipProfile> {
         self.rip_profiles.iter()
             .find(|p| p.is_default)
@@ -207,10 +297,17 @@ impl Config {
     }
     
     /// Get a rip profile by name
-    #[allow(dead_code)]
     pub fn get_profile(&self, name: &str) -> Option<&RipProfile> {
         self.rip_profiles.iter().find(|p| p.name == name)
     }
+    
+    /// Get the named rip profile, or the default profile when no name is given
+    pub fn resolve_profile(&self, name: Option<&str>) -> Option<&RipProfile> {
+        match name {
+            Some(name) => self.get_profile(name),
+            None => self.get_default_profile(),
+        }
+    }
 }
 
 /// Save config to the default config file
@@ -256,6 +353,14 @@ mod tests {
             audio_duration: 240,
             whisper_model: "base".to_string(),
             use_openai_api: true,
+            transcriber: None,
+            whisper_binary: None,
+            whisper_language: None,
+            matcher: None,
+            llm: None,
+            tesseract_binary: None,
+            ocr_language: None,
+            reference_subtitles: None,
         };
 
         assert_eq!(speech_config.audio_duration, 240);
@@ -288,6 +393,14 @@ mod tests {
         };
 
         assert_eq!(config.get_tmdb_api_key(), Some("tmdb_key".to_string()));
+
+        let yaml = "provider: tvdb\ntmdb:\n  base_url: http://localhost:8080/3\n  api_key: local_key\n";
+        let metadata: MetadataConfig = serde_yaml::from_str(yaml).unwrap();
+        let config = Config { metadata, ..config };
+        assert_eq!(config.get_tmdb_api_key(), Some("local_key".to_string()));
+        assert_eq!(config.metadata.provider.as_deref(), Some("tvdb"));
+        assert_eq!(config.metadata.tmdb.base_url.as_deref(), Some("http://localhost:8080/3"));
+        assert!(config.metadata.tvdb.api_key.is_none());
     }
 
     #[test]
@@ -299,6 +412,14 @@ mod tests {
                 audio_duration: 200,
                 whisper_model: "base".to_string(),
                 use_openai_api: true,
+                transcriber: Some("faster-whisper".to_string()),
+                whisper_binary: None,
+                whisper_language: None,
+                matcher: None,
+                llm: None,
+                tesseract_binary: None,
+                ocr_language: None,
+                reference_subtitles: None,
             },
             filebot: FilebotConfig {
                 skip_by_default: false,
@@ -312,6 +433,7 @@ mod tests {
         let yaml = serde_yaml::to_string(&config).unwrap();
         assert!(yaml.contains("tmdb_api_key"));
         assert!(yaml.contains("audio_duration: 200"));
+        assert!(yaml.contains("transcriber: faster-whisper"));
         assert!(yaml.contains("database: Custom"));
     }
 
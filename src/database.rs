@@ -118,6 +118,10 @@ pub struct Show {
     pub name: String,
     pub created_at: DateTime<Utc>,
     pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub title_selection: Option<crate::title_selection::TitleSelection>, // Overrides the rip profile's rules
+    #[serde(default)]
+    pub episode_order: Option<crate::dvd_metadata::EpisodeOrder>, // Aired order when unset
 }
 
 /// Rip history entry
@@ -136,6 +140,8 @@ pub struct RipHistory {
     pub error_message: Option<String>,
     pub avg_speed_mbps: Option<f32>, // Average ripping speed in MB/s
     pub checksum: Option<String>, // SHA-256 checksum of ripped files
+    #[serde(default)]
+    pub fingerprint: Option<String>, // Disc fingerprint (see fingerprint.rs), to recognise re-inserted discs
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -207,12 +213,75 @@ pub struct EpisodeMatchResult {
     pub season: u32,
     pub episode: u32,
     pub episode_title: Option<String>,
-    pub match_method: String, // "duration", "transcript", "manual"
+    pub match_method: String, // "duration", "transcript", "llm", "subtitle_reference", "manual"
     pub confidence: Option<f32>,
     pub title_index: Option<u32>,
     pub rip_history_id: Option<i64>,
 }
 
+/// A low-confidence episode match waiting for the user to accept its suggestion or pick another episode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpisodeMatchReview {
+    pub id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub show_name: String,
+    pub file_path: String,
+    pub candidates: Vec<crate::episode_assignment::EpisodeCandidate>, // Best first; the first is the suggestion
+    pub confidence: f32, // Confidence of the suggestion
+    pub status: ReviewStatus,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_season: Option<u32>,
+    pub resolved_episode: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewStatus {
+    #[default]
+    Pending,
+    Accepted,   // The suggested episode was right
+    Overridden, // The user picked a different episode
+}
+
+impl ReviewStatus {
+    fn to_string(self) -> &'static str {
+        match self {
+            ReviewStatus::Pending => "pending",
+            ReviewStatus::Accepted => "accepted",
+            ReviewStatus::Overridden => "overridden",
+        }
+    }
+
+    fn from_string(s: &str) -> Self {
+        match s {
+            "accepted" => ReviewStatus::Accepted,
+            "overridden" => ReviewStatus::Overridden,
+            _ => ReviewStatus::Pending,
+        }
+    }
+}
+
+/// One file rename, grouped into batches so a whole run can be undone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenameJournalEntry {
+    pub id: Option<i64>,
+    pub batch_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub old_path: String,
+    pub new_path: String,
+    pub method: String, // "speech", "filebot", "review", "gui", "disc_label"
+    pub undone_at: Option<DateTime<Utc>>,
+}
+
+/// A cached metadata response (search results, episode list, disc lookup) as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedMetadata {
+    pub provider: String, // "tmdb", "tvdb", "musicbrainz"
+    pub cache_key: String, // e.g. "episodes:1437:1"
+    pub value_json: String,
+    pub fetched_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RipQueueEntry {
     pub id: Option<i64>,
@@ -226,6 +295,176 @@ pub struct RipQueueEntry {
     pub priority: i32, // Higher number = higher priority (default 0)
     pub status: QueueStatus,
     pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub titles: Option<Vec<u32>>, // Explicit titles to rip (None = use title selection rules)
+    #[serde(default)]
+    pub session_id: Option<i64>, // Box-set session the rip joins
+    #[serde(default)]
+    pub reuse_fingerprint: Option<String>, // Known disc whose stored assignment the rip reuses
+}
+
+/// A title that finished ripping, so an interrupted rip can resume after it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RipCheckpoint {
+    pub id: Option<i64>,
+    pub fingerprint: String, // Identifies the disc when it's reinserted: its fingerprint, or volume label without one
+    pub device: String,
+    pub output_dir: String,
+    pub title_index: u32,
+    pub output_file: String,
+    pub file_size_bytes: Option<i64>,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Show, season and first episode of a disc, remembered so reinserting it (or the next disc of a box set) needs no input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiscAssignment {
+    #[serde(default)]
+    pub id: Option<i64>,
+    #[serde(default)]
+    pub fingerprint: Option<String>, // Preferred key (see fingerprint.rs)
+    #[serde(default)]
+    pub volume_label: Option<String>, // Fallback key for discs without a fingerprint
+    pub show_name: String,
+    pub season: u32,
+    pub first_episode: u32,
+    #[serde(default)]
+    pub episode_count: u32, // Episodes ripped from this disc; the next disc continues after them
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DiscAssignment {
+    /// Season and first episode for the disc that follows this one in a box set
+    pub fn next_disc(&self) -> (u32, u32) {
+        (self.season, self.first_episode + self.episode_count)
+    }
+
+    /// A disc ripped into a box-set session continues the session's running episode counter
+    pub fn for_session(session: &RipSession, fingerprint: Option<String>, volume_label: Option<String>) -> Self {
+        DiscAssignment {
+            id: None,
+            fingerprint,
+            volume_label,
+            show_name: session.show_name.clone(),
+            season: session.season,
+            first_episode: session.next_episode,
+            episode_count: 0,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// What to remember about a disc once it's ripped: the assignment it was ripped under, or else a new one
+    /// starting at the first episode its titles confidently matched (the label's season and episode 1 without
+    /// a match), keyed by this disc and holding the episodes it had
+    pub fn after_rip(
+        previous: Option<&DiscAssignment>,
+        show_name: String,
+        first_match: Option<(u32, u32)>,
+        fingerprint: Option<String>,
+        volume_label: Option<String>,
+        episode_count: u32,
+    ) -> Self {
+        let mut assignment = previous.cloned().unwrap_or_else(|| {
+            let (season, first_episode) = first_match.unwrap_or_else(|| {
+                (volume_label.as_deref().and_then(crate::dvd_metadata::extract_season_from_volume).unwrap_or(1), 1)
+            });
+            DiscAssignment {
+                id: None,
+                fingerprint: None,
+                volume_label: None,
+                show_name,
+                season,
+                first_episode,
+                episode_count: 0,
+                updated_at: Utc::now(),
+            }
+        });
+        assignment.fingerprint = fingerprint.or(assignment.fingerprint);
+        assignment.volume_label = volume_label.or(assignment.volume_label);
+        assignment.episode_count = episode_count;
+        assignment
+    }
+}
+
+/// Box-set session: consecutive discs of one season share a running episode counter and a season folder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RipSession {
+    #[serde(default)]
+    pub id: Option<i64>,
+    pub show_name: String,
+    pub season: u32,
+    pub expected_discs: u32,
+    #[serde(default)]
+    pub discs_ripped: u32,
+    #[serde(default = "default_next_episode")]
+    pub next_episode: u32, // First episode of the next disc
+    #[serde(default)]
+    pub output_dir: Option<String>, // Season folder override (see season_folder)
+    #[serde(default)]
+    pub status: RipSessionStatus,
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+fn default_next_episode() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RipSessionStatus {
+    #[default]
+    Active,
+    Completed,
+    Cancelled,
+}
+
+impl RipSessionStatus {
+    fn to_string(self) -> &'static str {
+        match self {
+            RipSessionStatus::Active => "active",
+            RipSessionStatus::Completed => "completed",
+            RipSessionStatus::Cancelled => "cancelled",
+        }
+    }
+
+    fn from_string(s: &str) -> Self {
+        match s {
+            "completed" => RipSessionStatus::Completed,
+            "cancelled" => RipSessionStatus::Cancelled,
+            _ => RipSessionStatus::Active,
+        }
+    }
+}
+
+impl RipSession {
+    /// Number of the disc currently being ripped (1-based)
+    pub fn current_disc(&self) -> u32 {
+        self.discs_ripped + 1
+    }
+
+    /// Session progress for display, e.g. "disc 3 of 5, episodes 9–12"
+    pub fn summary(&self, disc_episodes: u32) -> String {
+        let discs = format!("disc {} of {}", self.current_disc(), self.expected_discs);
+        match disc_episodes {
+            0 => format!("{}, starting at episode {}", discs, self.next_episode),
+            1 => format!("{}, episode {}", discs, self.next_episode),
+            n => format!("{}, episodes {}–{}", discs, self.next_episode, self.next_episode + n - 1),
+        }
+    }
+
+    /// Folder every disc of the session rips into: `<media output>/<show>/Season NN` unless overridden
+    pub fn season_folder(&self, media_output: &std::path::Path) -> std::path::PathBuf {
+        match self.output_dir {
+            Some(ref dir) => std::path::PathBuf::from(dir),
+            None => media_output
+                .join(crate::ripper::sanitize_filename(&self.show_name))
+                .join(format!("Season {:02}", self.season)),
+        }
+    }
 }
 
 /// Agent information
@@ -1185,106 +1424,610 @@ impl Database {
             )?;
         }
 
-        Ok(())
-    }
-
-    /// Record an episode match result for statistics tracking
-    #[allow(dead_code)]
-    pub fn record_episode_match(&self, match_result: &EpisodeMatchResult) -> Result<i64> {
-        let conn = self.conn.lock().unwrap();
-        
-        conn.execute(
-            "INSERT INTO episode_match_results (timestamp, show_name, season, episode, episode_title, match_method, confidence, title_index, rip_history_id)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-            params![
-                match_result.timestamp.to_rfc3339(),
-                match_result.show_name,
-                match_result.season as i64,
-                match_result.episode as i64,
-                match_result.episode_title,
-                match_result.match_method,
-                match_result.confidence,
-                match_result.title_index.map(|t| t as i64),
-                match_result.rip_history_id,
-            ],
-        )?;
-
-        Ok(conn.last_insert_rowid())
-    }
+        // Migration 13: Add disc_scans table
+        if current_version < 13 {
+            info!("Applying migration 13: add_disc_scans_table");
+            
+            let table_exists: Result<i64, _> = conn.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='disc_scans'",
+                [],
+                |row| row.get(0),
+            );
+            
+            if table_exists.unwrap_or(0) == 0 {
+                conn.execute(
+                    "CREATE TABLE disc_scans (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        device TEXT NOT NULL,
+                        disc_type TEXT,
+                        volume_label TEXT,
+                        fingerprint TEXT,
+                        title_count INTEGER NOT NULL,
+                        scan_json TEXT NOT NULL,
+                        scanned_at TEXT NOT NULL
+                    )",
+                    [],
+                )?;
+                
+                conn.execute(
+                    "CREATE INDEX IF NOT EXISTS idx_disc_scans_device ON disc_scans(device, scanned_at DESC)",
+                    [],
+                )?;
+                conn.execute(
+                    "CREATE INDEX IF NOT EXISTS idx_disc_scans_fingerprint ON disc_scans(fingerprint, scanned_at DESC)",
+                    [],
+                )?;
+            }
+            
+            conn.execute(
+                "INSERT INTO migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![13, "add_disc_scans_table", chrono::Utc::now().to_rfc3339()],
+            )?;
+        }
 
-    /// Get episode matching statistics
-    pub fn get_episode_match_statistics(&self) -> Result<serde_json::Value> {
-        let conn = self.conn.lock().unwrap();
-        
-        // Check if episode_match_results table exists
-        let table_exists: bool = conn.query_row(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='episode_match_results'",
-            [],
-            |row| Ok(row.get::<_, i64>(0)? > 0),
-        ).unwrap_or(false);
-        
-        if !table_exists {
-            return Ok(serde_json::json!({
-                "total_matches": 0,
-                "average_confidence": None::<f64>,
-                "by_method": {},
-                "confidence_distribution": {},
-                "over_time": [],
-                "top_shows": [],
-            }));
+        // Migration 14: Add title selection rules to shows, and explicit titles, box-set session and reused disc to rip queue
+        if current_version < 14 {
+            info!("Applying migration 14: add_title_selection");
+            let selection_exists: Result<i64, _> = conn.query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('shows') WHERE name='title_selection'",
+                [],
+                |row| row.get(0),
+            );
+            
+            if selection_exists.unwrap_or(0) == 0 {
+                conn.execute(
+                    "ALTER TABLE shows ADD COLUMN title_selection TEXT",
+                    [],
+                )?;
+            }
+            
+            for (column, column_type) in [("titles", "TEXT"), ("session_id", "INTEGER"), ("reuse_fingerprint", "TEXT")] {
+                let column_exists: Result<i64, _> = conn.query_row(
+                    "SELECT COUNT(*) FROM pragma_table_info('rip_queue') WHERE name=?1",
+                    [column],
+                    |row| row.get(0),
+                );
+                
+                if column_exists.unwrap_or(0) == 0 {
+                    conn.execute(
+                        &format!("ALTER TABLE rip_queue ADD COLUMN {} {}", column, column_type),
+                        [],
+                    )?;
+                }
+            }
+            
+            conn.execute(
+                "INSERT INTO migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![14, "add_title_selection", chrono::Utc::now().to_rfc3339()],
+            )?;
         }
-        
-        // Total matches
-        let total_matches: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM episode_match_results",
-            [],
-            |row| row.get(0),
-        )?;
-        
-        // Average confidence
-        let avg_confidence: Option<f64> = conn.query_row(
-            "SELECT AVG(confidence) FROM episode_match_results WHERE confidence IS NOT NULL",
-            [],
-            |row| row.get(0),
-        ).ok();
-        
-        // Matches by method
-        let mut method_stats = std::collections::HashMap::new();
-        let mut stmt = conn.prepare(
-            "SELECT match_method, COUNT(*) as count, AVG(confidence) as avg_conf
-             FROM episode_match_results
-             GROUP BY match_method"
-        )?;
-        
-        let method_rows = stmt.query_map([], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, i64>(1)?,
-                row.get::<_, Option<f64>>(2)?,
-            ))
-        })?;
-        
-        for row in method_rows {
-            let (method, count, avg_conf) = row?;
-            method_stats.insert(method, serde_json::json!({
-                "count": count,
-                "avg_confidence": avg_conf,
-            }));
+
+        // Migration 15: Per-title checkpoints so interrupted rips can resume
+        if current_version < 15 {
+            info!("Applying migration 15: add_rip_checkpoints_table");
+            
+            let table_exists: Result<i64, _> = conn.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='rip_checkpoints'",
+                [],
+                |row| row.get(0),
+            );
+            
+            if table_exists.unwrap_or(0) == 0 {
+                conn.execute(
+                    "CREATE TABLE rip_checkpoints (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        fingerprint TEXT NOT NULL,
+                        device TEXT NOT NULL,
+                        output_dir TEXT NOT NULL,
+                        title_index INTEGER NOT NULL,
+                        output_file TEXT NOT NULL,
+                        file_size_bytes INTEGER,
+                        completed_at TEXT NOT NULL,
+                        UNIQUE(fingerprint, title_index)
+                    )",
+                    [],
+                )?;
+            }
+            
+            conn.execute(
+                "INSERT INTO migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![15, "add_rip_checkpoints_table", chrono::Utc::now().to_rfc3339()],
+            )?;
         }
-        
-        // Confidence distribution (grouped into buckets)
-        let mut confidence_dist = std::collections::HashMap::new();
-        let buckets = vec![(0.0, 50.0), (50.0, 70.0), (70.0, 85.0), (85.0, 95.0), (95.0, 100.0)];
-        
-        for (min, max) in buckets {
-            let bucket_label = format!("{:.0}-{:.0}", min, max);
-            let count: i64 = conn.query_row(
-                "SELECT COUNT(*) FROM episode_match_results WHERE confidence >= ?1 AND confidence < ?2",
-                params![min, max],
+
+        // Migration 16: Disc fingerprints on rip history so known discs are recognised, and the other fingerprints
+        // a disc is known by (e.g. its scan when it isn't mounted)
+        if current_version < 16 {
+            info!("Applying migration 16: add_rip_history_fingerprint");
+            let fingerprint_exists: Result<i64, _> = conn.query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('rip_history') WHERE name='fingerprint'",
+                [],
                 |row| row.get(0),
-            ).unwrap_or(0);
+            );
             
-            confidence_dist.insert(bucket_label, count);
+            if fingerprint_exists.unwrap_or(0) == 0 {
+                conn.execute(
+                    "ALTER TABLE rip_history ADD COLUMN fingerprint TEXT",
+                    [],
+                )?;
+            }
+            
+            conn.execute(
+                "CREATE INDEX IF NOT EXISTS idx_rip_history_fingerprint ON rip_history(fingerprint)",
+                [],
+            )?;
+            
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS disc_fingerprint_aliases (
+                    alias TEXT PRIMARY KEY,
+                    fingerprint TEXT NOT NULL
+                )",
+                [],
+            )?;
+            
+            conn.execute(
+                "INSERT INTO migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![16, "add_rip_history_fingerprint", chrono::Utc::now().to_rfc3339()],
+            )?;
+        }
+
+        // Migration 17: Remember each disc's show, season and first episode
+        if current_version < 17 {
+            info!("Applying migration 17: add_disc_assignments_table");
+            
+            let table_exists: Result<i64, _> = conn.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='disc_assignments'",
+                [],
+                |row| row.get(0),
+            );
+            
+            if table_exists.unwrap_or(0) == 0 {
+                conn.execute(
+                    "CREATE TABLE disc_assignments (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        fingerprint TEXT UNIQUE,
+                        volume_label TEXT,
+                        show_name TEXT NOT NULL,
+                        season INTEGER NOT NULL,
+                        first_episode INTEGER NOT NULL,
+                        episode_count INTEGER NOT NULL DEFAULT 0,
+                        updated_at TEXT NOT NULL
+                    )",
+                    [],
+                )?;
+                conn.execute(
+                    "CREATE INDEX IF NOT EXISTS idx_disc_assignments_volume_label ON disc_assignments(volume_label)",
+                    [],
+                )?;
+            }
+            
+            conn.execute(
+                "INSERT INTO migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![17, "add_disc_assignments_table", chrono::Utc::now().to_rfc3339()],
+            )?;
+        }
+
+        // Migration 18: Box-set sessions that carry episode numbering across discs
+        if current_version < 18 {
+            info!("Applying migration 18: add_rip_sessions_table");
+            
+            let table_exists: Result<i64, _> = conn.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='rip_sessions'",
+                [],
+                |row| row.get(0),
+            );
+            
+            if table_exists.unwrap_or(0) == 0 {
+                conn.execute(
+                    "CREATE TABLE rip_sessions (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        show_name TEXT NOT NULL,
+                        season INTEGER NOT NULL,
+                        expected_discs INTEGER NOT NULL,
+                        discs_ripped INTEGER NOT NULL DEFAULT 0,
+                        next_episode INTEGER NOT NULL DEFAULT 1,
+                        output_dir TEXT,
+                        status TEXT NOT NULL DEFAULT 'active',
+                        created_at TEXT NOT NULL,
+                        updated_at TEXT NOT NULL
+                    )",
+                    [],
+                )?;
+                conn.execute(
+                    "CREATE INDEX IF NOT EXISTS idx_rip_sessions_status ON rip_sessions(status)",
+                    [],
+                )?;
+            }
+            
+            conn.execute(
+                "INSERT INTO migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![18, "add_rip_sessions_table", chrono::Utc::now().to_rfc3339()],
+            )?;
+        }
+
+        // Migration 19: Episode matches too close to call, queued for review
+        if current_version < 19 {
+            info!("Applying migration 19: add_episode_match_reviews_table");
+            
+            let table_exists: Result<i64, _> = conn.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='episode_match_reviews'",
+                [],
+                |row| row.get(0),
+            );
+            
+            if table_exists.unwrap_or(0) == 0 {
+                conn.execute(
+                    "CREATE TABLE episode_match_reviews (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        created_at TEXT NOT NULL,
+                        show_name TEXT NOT NULL,
+                        file_path TEXT NOT NULL,
+                        candidates_json TEXT NOT NULL,
+                        confidence REAL NOT NULL,
+                        status TEXT NOT NULL DEFAULT 'pending',
+                        resolved_at TEXT,
+                        resolved_season INTEGER,
+                        resolved_episode INTEGER
+                    )",
+                    [],
+                )?;
+                conn.execute(
+                    "CREATE INDEX IF NOT EXISTS idx_episode_match_reviews_status ON episode_match_reviews(status)",
+                    [],
+                )?;
+            }
+            
+            conn.execute(
+                "INSERT INTO migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![19, "add_episode_match_reviews_table", chrono::Utc::now().to_rfc3339()],
+            )?;
+        }
+
+        // Migration 20: Journal of renames so a batch can be undone
+        if current_version < 20 {
+            info!("Applying migration 20: add_rename_journal_table");
+            
+            let table_exists: Result<i64, _> = conn.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='rename_journal'",
+                [],
+                |row| row.get(0),
+            );
+            
+            if table_exists.unwrap_or(0) == 0 {
+                conn.execute(
+                    "CREATE TABLE rename_journal (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        batch_id TEXT NOT NULL,
+                        timestamp TEXT NOT NULL,
+                        old_path TEXT NOT NULL,
+                        new_path TEXT NOT NULL,
+                        method TEXT NOT NULL,
+                        undone_at TEXT
+                    )",
+                    [],
+                )?;
+                conn.execute(
+                    "CREATE INDEX IF NOT EXISTS idx_rename_journal_batch ON rename_journal(batch_id)",
+                    [],
+                )?;
+            }
+            
+            conn.execute(
+                "INSERT INTO migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![20, "add_rename_journal_table", chrono::Utc::now().to_rfc3339()],
+            )?;
+        }
+
+        // Migration 21: Per-show episode order (DVD, absolute, story arc...)
+        if current_version < 21 {
+            info!("Applying migration 21: add_show_episode_order");
+            let order_exists: Result<i64, _> = conn.query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('shows') WHERE name='episode_order'",
+                [],
+                |row| row.get(0),
+            );
+            
+            if order_exists.unwrap_or(0) == 0 {
+                conn.execute(
+                    "ALTER TABLE shows ADD COLUMN episode_order TEXT",
+                    [],
+                )?;
+            }
+            
+            conn.execute(
+                "INSERT INTO migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![21, "add_show_episode_order", chrono::Utc::now().to_rfc3339()],
+            )?;
+        }
+        
+        // Migration 22: Cache metadata lookups so repeat rips skip the network (and work offline)
+        if current_version < 22 {
+            info!("Applying migration 22: add_metadata_cache_table");
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS metadata_cache (
+                    provider TEXT NOT NULL,
+                    cache_key TEXT NOT NULL,
+                    value_json TEXT NOT NULL,
+                    fetched_at TEXT NOT NULL,
+                    PRIMARY KEY (provider, cache_key)
+                )",
+                [],
+            )?;
+            
+            conn.execute(
+                "INSERT INTO migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                params![22, "add_metadata_cache_table", chrono::Utc::now().to_rfc3339()],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Record an episode match result for statistics tracking
+    pub fn record_episode_match(&self, match_result: &EpisodeMatchResult) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        
+        conn.execute(
+            "INSERT INTO episode_match_results (timestamp, show_name, season, episode, episode_title, match_method, confidence, title_index, rip_history_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                match_result.timestamp.to_rfc3339(),
+                match_result.show_name,
+                match_result.season as i64,
+                match_result.episode as i64,
+                match_result.episode_title,
+                match_result.match_method,
+                match_result.confidence,
+                match_result.title_index.map(|t| t as i64),
+                match_result.rip_history_id,
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Queue a low-confidence match for manual review; a file already pending review is replaced
+    pub fn add_episode_match_review(&self, review: &EpisodeMatchReview) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM episode_match_reviews WHERE file_path = ?1 AND status = 'pending'",
+            [&review.file_path],
+        )?;
+        conn.execute(
+            "INSERT INTO episode_match_reviews (created_at, show_name, file_path, candidates_json, confidence, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                review.created_at.to_rfc3339(),
+                review.show_name,
+                review.file_path,
+                serde_json::to_string(&review.candidates)?,
+                review.confidence,
+                ReviewStatus::Pending.to_string(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Reviews still waiting for a decision, oldest first
+    pub fn get_pending_episode_match_reviews(&self) -> Result<Vec<EpisodeMatchReview>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, created_at, show_name, file_path, candidates_json, confidence, status, resolved_at, resolved_season, resolved_episode
+             FROM episode_match_reviews WHERE status = 'pending' ORDER BY created_at ASC, id ASC"
+        )?;
+        let reviews = stmt.query_map([], Self::episode_match_review_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(reviews)
+    }
+
+    pub fn get_episode_match_review(&self, id: i64) -> Result<Option<EpisodeMatchReview>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT id, created_at, show_name, file_path, candidates_json, confidence, status, resolved_at, resolved_season, resolved_episode
+             FROM episode_match_reviews WHERE id = ?1",
+            [id],
+            Self::episode_match_review_from_row,
+        );
+
+        match result {
+            Ok(review) => Ok(Some(review)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Close a review with the episode the user settled on
+    pub fn resolve_episode_match_review(&self, id: i64, status: ReviewStatus, season: u32, episode: u32) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE episode_match_reviews SET status = ?1, resolved_at = ?2, resolved_season = ?3, resolved_episode = ?4
+             WHERE id = ?5 AND status = 'pending'",
+            params![status.to_string(), Utc::now().to_rfc3339(), season, episode, id],
+        )?;
+        if updated == 0 {
+            return Err(anyhow::anyhow!("No pending review with ID {}", id));
+        }
+        Ok(())
+    }
+
+    fn episode_match_review_from_row(row: &rusqlite::Row) -> rusqlite::Result<EpisodeMatchReview> {
+        let candidates_json: String = row.get(4)?;
+        Ok(EpisodeMatchReview {
+            id: Some(row.get(0)?),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(1)?)
+                .unwrap()
+                .with_timezone(&Utc),
+            show_name: row.get(2)?,
+            file_path: row.get(3)?,
+            candidates: serde_json::from_str(&candidates_json).unwrap_or_default(),
+            confidence: row.get(5)?,
+            status: ReviewStatus::from_string(&row.get::<_, String>(6)?),
+            resolved_at: row.get::<_, Option<String>>(7)?
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            resolved_season: row.get(8)?,
+            resolved_episode: row.get(9)?,
+        })
+    }
+
+    pub fn add_rename_journal_entry(&self, entry: &RenameJournalEntry) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO rename_journal (batch_id, timestamp, old_path, new_path, method)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                entry.batch_id,
+                entry.timestamp.to_rfc3339(),
+                entry.old_path,
+                entry.new_path,
+                entry.method,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Renames in a batch, in the order they were made
+    pub fn get_rename_batch(&self, batch_id: &str) -> Result<Vec<RenameJournalEntry>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, batch_id, timestamp, old_path, new_path, method, undone_at
+             FROM rename_journal WHERE batch_id = ?1 ORDER BY id ASC"
+        )?;
+        let entries = stmt.query_map([batch_id], |row| {
+            Ok(RenameJournalEntry {
+                id: Some(row.get(0)?),
+                batch_id: row.get(1)?,
+                timestamp: DateTime::parse_from_rfc3339(&row.get::<_, String>(2)?)
+                    .unwrap()
+                    .with_timezone(&Utc),
+                old_path: row.get(3)?,
+                new_path: row.get(4)?,
+                method: row.get(5)?,
+                undone_at: row.get::<_, Option<String>>(6)?
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    pub fn mark_rename_undone(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE rename_journal SET undone_at = ?1 WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Cached metadata response, however old; callers decide whether it's still fresh
+    pub fn get_cached_metadata(&self, provider: &str, cache_key: &str) -> Result<Option<CachedMetadata>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT provider, cache_key, value_json, fetched_at FROM metadata_cache WHERE provider = ?1 AND cache_key = ?2",
+            params![provider, cache_key],
+            |row| Ok(CachedMetadata {
+                provider: row.get(0)?,
+                cache_key: row.get(1)?,
+                value_json: row.get(2)?,
+                fetched_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_default(), // Unparseable entries count as expired
+            }),
+        );
+
+        match result {
+            Ok(entry) => Ok(Some(entry)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Store (or refresh) a metadata response
+    pub fn put_cached_metadata(&self, provider: &str, cache_key: &str, value_json: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO metadata_cache (provider, cache_key, value_json, fetched_at) VALUES (?1, ?2, ?3, ?4)",
+            params![provider, cache_key, value_json, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Get episode matching statistics
+    pub fn get_episode_match_statistics(&self) -> Result<serde_json::Value> {
+        let conn = self.conn.lock().unwrap();
+        
+        // Check if episode_match_results table exists
+        let table_exists: bool = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='episode_match_results'",
+            [],
+            |row| Ok(row.get::<_, i64>(0)? > 0),
+        ).unwrap_or(false);
+        
+        if !table_exists {
+            return Ok(serde_json::json!({
+                "total_matches": 0,
+                "average_confidence": None::<f64>,
+                "by_method": {},
+                "confidence_distribution": {},
+                "over_time": [],
+                "top_shows": [],
+                "reviews": { "pending": 0, "accepted": 0, "overridden": 0, "accuracy": None::<f64> },
+            }));
+        }
+        
+        // Total matches
+        let total_matches: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM episode_match_results",
+            [],
+            |row| row.get(0),
+        )?;
+        
+        // Average confidence
+        let avg_confidence: Option<f64> = conn.query_row(
+            "SELECT AVG(confidence) FROM episode_match_results WHERE confidence IS NOT NULL",
+            [],
+            |row| row.get(0),
+        ).ok();
+        
+        // Matches by method
+        let mut method_stats = std::collections::HashMap::new();
+        let mut stmt = conn.prepare(
+            "SELECT match_method, COUNT(*) as count, AVG(confidence) as avg_conf
+             FROM episode_match_results
+             GROUP BY match_method"
+        )?;
+        
+        let method_rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, Option<f64>>(2)?,
+            ))
+        })?;
+        
+        for row in method_rows {
+            let (method, count, avg_conf) = row?;
+            method_stats.insert(method, serde_json::json!({
+                "count": count,
+                "avg_confidence": avg_conf,
+            }));
+        }
+        
+        // Confidence distribution (grouped into buckets)
+        let mut confidence_dist = std::collections::HashMap::new();
+        let buckets = vec![(0.0, 50.0), (50.0, 70.0), (70.0, 85.0), (85.0, 95.0), (95.0, 100.0)];
+        
+        for (min, max) in buckets {
+            let bucket_label = format!("{:.0}-{:.0}", min, max);
+            let count: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM episode_match_results WHERE confidence >= ?1 AND confidence < ?2",
+                params![min, max],
+                |row| row.get(0),
+            ).unwrap_or(0);
+            
+            confidence_dist.insert(bucket_label, count);
         }
         
         // Matches over time (last 30 days)
@@ -1342,6 +2085,22 @@ impl Database {
             }));
         }
         
+        // Reviewed matches: how often the suggested episode turned out to be right
+        let review_count = |status: ReviewStatus| -> i64 {
+            conn.query_row(
+                "SELECT COUNT(*) FROM episode_match_reviews WHERE status = ?1",
+                [status.to_string()],
+                |row| row.get(0),
+            ).unwrap_or(0)
+        };
+        let (pending, accepted, overridden) = (
+            review_count(ReviewStatus::Pending),
+            review_count(ReviewStatus::Accepted),
+            review_count(ReviewStatus::Overridden),
+        );
+        let accuracy = (accepted + overridden > 0)
+            .then(|| accepted as f64 / (accepted + overridden) as f64 * 100.0);
+        
         Ok(serde_json::json!({
             "total_matches": total_matches,
             "average_confidence": avg_confidence,
@@ -1349,6 +2108,12 @@ impl Database {
             "confidence_distribution": confidence_dist,
             "over_time": time_series,
             "top_shows": top_shows,
+            "reviews": {
+                "pending": pending,
+                "accepted": accepted,
+                "overridden": overridden,
+                "accuracy": accuracy,
+            },
         }))
     }
 
@@ -2529,6 +3294,7 @@ impl Database {
                     .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
                     .map(|dt| dt.with_timezone(&Utc)),
                 error: row.get(9)?,
+                session: None,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -3246,7 +4012,7 @@ impl Database {
     pub fn get_shows(&self) -> Result<Vec<Show>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, name, created_at, last_used_at FROM shows ORDER BY name ASC"
+            "SELECT id, name, created_at, last_used_at, title_selection, episode_order FROM shows ORDER BY name ASC"
         )?;
 
         let shows = stmt.query_map([], |row| {
@@ -3259,6 +4025,10 @@ impl Database {
                 last_used_at: row.get::<_, Option<String>>(3)?
                     .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
                     .map(|dt| dt.with_timezone(&Utc)),
+                title_selection: row.get::<_, Option<String>>(4)?
+                    .and_then(|s| serde_json::from_str(&s).ok()),
+                episode_order: row.get::<_, Option<String>>(5)?
+                    .and_then(|s| crate::dvd_metadata::EpisodeOrder::parse(&s)),
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -3270,7 +4040,7 @@ impl Database {
     pub fn get_show(&self, id: i64) -> Result<Option<Show>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, name, created_at, last_used_at FROM shows WHERE id = ?1"
+            "SELECT id, name, created_at, last_used_at, title_selection, episode_order FROM shows WHERE id = ?1"
         )?;
 
         let result = stmt.query_row([id], |row| {
@@ -3283,6 +4053,10 @@ impl Database {
                 last_used_at: row.get::<_, Option<String>>(3)?
                     .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
                     .map(|dt| dt.with_timezone(&Utc)),
+                title_selection: row.get::<_, Option<String>>(4)?
+                    .and_then(|s| serde_json::from_str(&s).ok()),
+                episode_order: row.get::<_, Option<String>>(5)?
+                    .and_then(|s| crate::dvd_metadata::EpisodeOrder::parse(&s)),
             })
         });
 
@@ -3305,6 +4079,43 @@ impl Database {
         Ok(())
     }
 
+    /// Set or clear a show's episode order, returning false if there is no such show
+    pub fn set_show_episode_order(&self, id: i64, order: Option<crate::dvd_metadata::EpisodeOrder>) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let value = order.map(serde_json::to_value).transpose()?
+            .and_then(|v| v.as_str().map(String::from));
+        
+        let updated = conn.execute(
+            "UPDATE shows SET episode_order = ?1 WHERE id = ?2",
+            params![value, id],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    /// Episode order for a show by name; aired order when the show isn't saved or has none set
+    pub fn episode_order_for_show(&self, name: &str) -> crate::dvd_metadata::EpisodeOrder {
+        self.get_shows().ok()
+            .and_then(|shows| shows.into_iter().find(|s| s.name.eq_ignore_ascii_case(name)))
+            .and_then(|show| show.episode_order)
+            .unwrap_or_default()
+    }
+
+    /// Set or clear a show's title selection rules
+    ///
+    /// Returns false when there's no show with that ID.
+    pub fn set_show_title_selection(&self, id: i64, selection: Option<&crate::title_selection::TitleSelection>) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let json = selection.map(serde_json::to_string).transpose()?;
+        
+        let updated = conn.execute(
+            "UPDATE shows SET title_selection = ?1 WHERE id = ?2",
+            params![json, id],
+        )?;
+
+        Ok(updated > 0)
+    }
+
     /// Delete a show
     pub fn delete_show(&self, id: i64) -> Result<()> {
         let conn = self.conn.lock().unwrap();
@@ -3393,8 +4204,8 @@ impl Database {
         }
         
         conn.execute(
-            "INSERT INTO rip_history (timestamp, drive, disc, title, disc_type, status, duration_seconds, file_size_bytes, output_path, error_message, avg_speed_mbps, checksum)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            "INSERT INTO rip_history (timestamp, drive, disc, title, disc_type, status, duration_seconds, file_size_bytes, output_path, error_message, avg_speed_mbps, checksum, fingerprint)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
                 entry.timestamp.to_rfc3339(),
                 entry.drive,
@@ -3408,6 +4219,7 @@ impl Database {
                 entry.error_message,
                 entry.avg_speed_mbps,
                 entry.checksum,
+                entry.fingerprint,
             ],
         )?;
 
@@ -3432,6 +4244,7 @@ impl Database {
                 "failed_rips": 0,
                 "success_rate": 0.0,
                 "total_storage_bytes": 0,
+                "by_disc_type": {},
             }));
         }
         
@@ -3470,12 +4283,43 @@ impl Database {
             0.0
         };
         
+        // Breakdown by disc type (cd, dvd, bluray)
+        let mut by_disc_type = serde_json::Map::new();
+        let mut stmt = conn.prepare(
+            "SELECT LOWER(COALESCE(disc_type, 'unknown')),
+                    COUNT(*),
+                    SUM(CASE WHEN status = 'success' THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END),
+                    COALESCE(SUM(CASE WHEN status = 'success' THEN file_size_bytes ELSE 0 END), 0)
+             FROM rip_history
+             GROUP BY LOWER(COALESCE(disc_type, 'unknown'))"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?;
+        for row in rows {
+            let (disc_type, total, successful, failed, storage) = row?;
+            by_disc_type.insert(disc_type, serde_json::json!({
+                "total_rips": total,
+                "successful_rips": successful,
+                "failed_rips": failed,
+                "total_storage_bytes": storage,
+            }));
+        }
+        
         Ok(serde_json::json!({
             "total_rips": total_rips,
             "successful_rips": successful_rips,
             "failed_rips": failed_rips,
             "success_rate": success_rate,
             "total_storage_bytes": total_storage,
+            "by_disc_type": by_disc_type,
         }))
     }
 
@@ -3609,8 +4453,8 @@ impl Database {
         let conn = self.conn.lock().unwrap();
         
         conn.execute(
-            "INSERT INTO rip_queue (created_at, drive, output_path, title, skip_metadata, skip_filebot, profile, priority, status)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            "INSERT INTO rip_queue (created_at, drive, output_path, title, skip_metadata, skip_filebot, profile, priority, status, titles, session_id, reuse_fingerprint)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
             params![
                 entry.created_at.to_rfc3339(),
                 entry.drive,
@@ -3621,6 +4465,9 @@ impl Database {
                 entry.profile,
                 entry.priority,
                 entry.status.to_string(),
+                entry.titles.as_ref().map(serde_json::to_string).transpose()?,
+                entry.session_id,
+                entry.reuse_fingerprint,
             ],
         )?;
 
@@ -3628,13 +4475,12 @@ impl Database {
     }
 
     /// Get next pending queue entry (highest priority first, then oldest)
-    #[allow(dead_code)]
     pub fn get_next_queue_entry(&self, drive: Option<&str>) -> Result<Option<RipQueueEntry>> {
         let conn = self.conn.lock().unwrap();
         
         let entry = if let Some(d) = drive {
             let mut stmt = conn.prepare(
-                "SELECT id, created_at, drive, output_path, title, skip_metadata, skip_filebot, profile, priority, status, started_at
+                "SELECT id, created_at, drive, output_path, title, skip_metadata, skip_filebot, profile, priority, status, started_at, titles, session_id, reuse_fingerprint
                  FROM rip_queue
                  WHERE status = 'pending' AND (drive IS NULL OR drive = ?1)
                  ORDER BY priority DESC, created_at ASC
@@ -3657,11 +4503,15 @@ impl Database {
                     started_at: row.get::<_, Option<String>>(10)?
                         .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
                         .map(|dt| dt.with_timezone(&Utc)),
+                    titles: row.get::<_, Option<String>>(11)?
+                        .and_then(|s| serde_json::from_str(&s).ok()),
+                    session_id: row.get(12)?,
+                    reuse_fingerprint: row.get(13)?,
                 })
             }).ok()
         } else {
             let mut stmt = conn.prepare(
-                "SELECT id, created_at, drive, output_path, title, skip_metadata, skip_filebot, profile, priority, status, started_at
+                "SELECT id, created_at, drive, output_path, title, skip_metadata, skip_filebot, profile, priority, status, started_at, titles, session_id, reuse_fingerprint
                  FROM rip_queue
                  WHERE status = 'pending'
                  ORDER BY priority DESC, created_at ASC
@@ -3684,113 +4534,568 @@ impl Database {
                     started_at: row.get::<_, Option<String>>(10)?
                         .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
                         .map(|dt| dt.with_timezone(&Utc)),
+                    titles: row.get::<_, Option<String>>(11)?
+                        .and_then(|s| serde_json::from_str(&s).ok()),
+                    session_id: row.get(12)?,
+                    reuse_fingerprint: row.get(13)?,
                 })
             }).ok()
         };
 
-        Ok(entry)
+        Ok(entry)
+    }
+
+    /// Update queue entry status
+    pub fn update_queue_status(&self, id: i64, status: QueueStatus, started_at: Option<DateTime<Utc>>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        
+        if let Some(started) = started_at {
+            conn.execute(
+                "UPDATE rip_queue SET status = ?1, started_at = ?2 WHERE id = ?3",
+                params![status.to_string(), started.to_rfc3339(), id],
+            )?;
+        } else {
+            conn.execute(
+                "UPDATE rip_queue SET status = ?1 WHERE id = ?2",
+                params![status.to_string(), id],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Get all queue entries
+    pub fn get_queue_entries(&self, include_completed: bool) -> Result<Vec<RipQueueEntry>> {
+        let conn = self.conn.lock().unwrap();
+        
+        let sql = if include_completed {
+            "SELECT id, created_at, drive, output_path, title, skip_metadata, skip_filebot, profile, priority, status, started_at, titles, session_id, reuse_fingerprint
+             FROM rip_queue
+             ORDER BY priority DESC, created_at ASC"
+        } else {
+            "SELECT id, created_at, drive, output_path, title, skip_metadata, skip_filebot, profile, priority, status, started_at, titles, session_id, reuse_fingerprint
+             FROM rip_queue
+             WHERE status != 'completed'
+             ORDER BY priority DESC, created_at ASC"
+        };
+
+        let mut stmt = conn.prepare(sql)?;
+        let entries = stmt.query_map([], |row| {
+            Ok(RipQueueEntry {
+                id: Some(row.get(0)?),
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(1)?)
+                    .unwrap()
+                    .with_timezone(&Utc),
+                drive: row.get(2)?,
+                output_path: row.get(3)?,
+                title: row.get(4)?,
+                skip_metadata: row.get::<_, i64>(5)? != 0,
+                skip_filebot: row.get::<_, i64>(6)? != 0,
+                profile: row.get(7)?,
+                priority: row.get(8)?,
+                status: QueueStatus::from_string(&row.get::<_, String>(9)?),
+                started_at: row.get::<_, Option<String>>(10)?
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                titles: row.get::<_, Option<String>>(11)?
+                    .and_then(|s| serde_json::from_str(&s).ok()),
+                session_id: row.get(12)?,
+                reuse_fingerprint: row.get(13)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+
+    /// Remove queue entry (for cancellation)
+    #[allow(dead_code)]
+    pub fn remove_queue_entry(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM rip_queue WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Put queue entries left in `processing` by a previous server run back to `pending`
+    ///
+    /// Returns how many entries were requeued.
+    pub fn reconcile_stale_queue_entries(&self) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let count = conn.execute(
+            "UPDATE rip_queue SET status = 'pending', started_at = NULL WHERE status = 'processing'",
+            [],
+        )?;
+        Ok(count)
+    }
+
+    /// Record a ripped title (replaces an earlier checkpoint for the same title)
+    pub fn add_rip_checkpoint(&self, checkpoint: &RipCheckpoint) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        
+        conn.execute(
+            "INSERT OR REPLACE INTO rip_checkpoints (fingerprint, device, output_dir, title_index, output_file, file_size_bytes, completed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                checkpoint.fingerprint,
+                checkpoint.device,
+                checkpoint.output_dir,
+                checkpoint.title_index,
+                checkpoint.output_file,
+                checkpoint.file_size_bytes,
+                checkpoint.completed_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Get checkpoints for a disc, oldest first (None = all discs)
+    pub fn get_rip_checkpoints(&self, fingerprint: Option<&str>) -> Result<Vec<RipCheckpoint>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, fingerprint, device, output_dir, title_index, output_file, file_size_bytes, completed_at
+             FROM rip_checkpoints
+             WHERE ?1 IS NULL OR fingerprint = ?1
+             ORDER BY completed_at ASC, title_index ASC"
+        )?;
+
+        let checkpoints = stmt.query_map([fingerprint], |row| {
+            Ok(RipCheckpoint {
+                id: Some(row.get(0)?),
+                fingerprint: row.get(1)?,
+                device: row.get(2)?,
+                output_dir: row.get(3)?,
+                title_index: row.get(4)?,
+                output_file: row.get(5)?,
+                file_size_bytes: row.get(6)?,
+                completed_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
+                    .unwrap()
+                    .with_timezone(&Utc),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(checkpoints)
+    }
+
+    /// Forget a disc's checkpoints once its rip has finished (or the user wants a fresh rip)
+    pub fn clear_rip_checkpoints(&self, fingerprint: &str) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let count = conn.execute("DELETE FROM rip_checkpoints WHERE fingerprint = ?1", [fingerprint])?;
+        Ok(count)
+    }
+
+    /// Create or update a disc assignment, returning its ID
+    ///
+    /// Without an ID the existing assignment for the same fingerprint (or, lacking one, volume label) is replaced.
+    pub fn save_disc_assignment(&self, assignment: &DiscAssignment) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        
+        let existing_id: Option<i64> = match assignment.id {
+            Some(id) => Some(id),
+            None => match conn.query_row(
+                "SELECT id FROM disc_assignments
+                 WHERE (?1 IS NOT NULL AND fingerprint = ?1)
+                    OR (?1 IS NULL AND fingerprint IS NULL AND volume_label = ?2)
+                 LIMIT 1",
+                params![assignment.fingerprint, assignment.volume_label],
+                |row| row.get(0),
+            ) {
+                Ok(id) => Some(id),
+                Err(rusqlite::Error::QueryReturnedNoRows) => None,
+                Err(e) => return Err(e.into()),
+            },
+        };
+        let updated_at = Utc::now().to_rfc3339();
+        
+        match existing_id {
+            Some(id) => {
+                if !Self::update_disc_assignment_row(&conn, id, assignment)? {
+                    return Err(anyhow::anyhow!("Disc assignment {} not found", id));
+                }
+                Ok(id)
+            }
+            None => {
+                conn.execute(
+                    "INSERT INTO disc_assignments (fingerprint, volume_label, show_name, season, first_episode, episode_count, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        assignment.fingerprint,
+                        assignment.volume_label,
+                        assignment.show_name,
+                        assignment.season,
+                        assignment.first_episode,
+                        assignment.episode_count,
+                        updated_at,
+                    ],
+                )?;
+                Ok(conn.last_insert_rowid())
+            }
+        }
+    }
+
+    /// Replace the disc assignment with the given ID, returning false if there is none
+    pub fn update_disc_assignment(&self, id: i64, assignment: &DiscAssignment) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        Self::update_disc_assignment_row(&conn, id, assignment)
+    }
+
+    fn update_disc_assignment_row(conn: &Connection, id: i64, assignment: &DiscAssignment) -> Result<bool> {
+        let updated = conn.execute(
+            "UPDATE disc_assignments
+             SET fingerprint = ?1, volume_label = ?2, show_name = ?3, season = ?4, first_episode = ?5, episode_count = ?6, updated_at = ?7
+             WHERE id = ?8",
+            params![
+                assignment.fingerprint,
+                assignment.volume_label,
+                assignment.show_name,
+                assignment.season,
+                assignment.first_episode,
+                assignment.episode_count,
+                Utc::now().to_rfc3339(),
+                id,
+            ],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    /// Look up a disc's assignment by fingerprint, falling back to its volume label
+    ///
+    /// A label only matches assignments without a fingerprint, since generic labels ("DVD_VIDEO") are shared by many discs.
+    pub fn get_disc_assignment(&self, fingerprint: Option<&str>, volume_label: Option<&str>) -> Result<Option<DiscAssignment>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT id, fingerprint, volume_label, show_name, season, first_episode, episode_count, updated_at
+             FROM disc_assignments
+             WHERE (?1 IS NOT NULL AND fingerprint = ?1)
+                OR (?2 IS NOT NULL AND volume_label = ?2 AND (?1 IS NULL OR fingerprint IS NULL))
+             ORDER BY (?1 IS NOT NULL AND fingerprint = ?1) DESC, updated_at DESC
+             LIMIT 1",
+            params![fingerprint, volume_label],
+            Self::disc_assignment_from_row,
+        );
+
+        match result {
+            Ok(assignment) => Ok(Some(assignment)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// All disc assignments, most recently updated first (optionally for one show)
+    pub fn get_disc_assignments(&self, show_name: Option<&str>) -> Result<Vec<DiscAssignment>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, fingerprint, volume_label, show_name, season, first_episode, episode_count, updated_at
+             FROM disc_assignments
+             WHERE ?1 IS NULL OR show_name = ?1 COLLATE NOCASE
+             ORDER BY updated_at DESC, id DESC"
+        )?;
+        let assignments = stmt.query_map([show_name], Self::disc_assignment_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(assignments)
+    }
+
+    /// The stored assignment for a disc, or one continuing the show's box set after its last ripped disc
+    ///
+    /// An explicit title that names a different show than the stored assignment takes precedence.
+    pub fn resolve_disc_assignment(&self, fingerprint: Option<&str>, volume_label: Option<&str>, title: Option<&str>) -> Option<DiscAssignment> {
+        match self.get_disc_assignment(fingerprint, volume_label) {
+            Ok(Some(assignment)) if title.is_none_or(|t| t.eq_ignore_ascii_case(&assignment.show_name)) => return Some(assignment),
+            Ok(_) => {}
+            Err(e) => warn!("Failed to load disc assignment: {}", e),
+        }
+        
+        let show_name = title?;
+        let label_season = volume_label.and_then(crate::dvd_metadata::extract_season_from_volume);
+        let previous = self.get_disc_assignments(Some(show_name)).ok()?
+            .into_iter()
+            .filter(|a| label_season.is_none_or(|season| a.season == season))
+            .max_by_key(|a| a.next_disc())?;
+        let (season, first_episode) = previous.next_disc();
+        Some(DiscAssignment {
+            id: None,
+            fingerprint: fingerprint.map(String::from),
+            volume_label: volume_label.map(String::from),
+            show_name: previous.show_name,
+            season,
+            first_episode,
+            episode_count: 0,
+            updated_at: Utc::now(),
+        })
+    }
+
+    /// Delete a disc assignment
+    pub fn delete_disc_assignment(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM disc_assignments WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    fn disc_assignment_from_row(row: &rusqlite::Row) -> rusqlite::Result<DiscAssignment> {
+        Ok(DiscAssignment {
+            id: Some(row.get(0)?),
+            fingerprint: row.get(1)?,
+            volume_label: row.get(2)?,
+            show_name: row.get(3)?,
+            season: row.get(4)?,
+            first_episode: row.get(5)?,
+            episode_count: row.get(6)?,
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+
+    /// Start a box-set session, returning its ID
+    pub fn create_rip_session(&self, session: &RipSession) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO rip_sessions (show_name, season, expected_discs, discs_ripped, next_episode, output_dir, status, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)",
+            params![
+                session.show_name,
+                session.season,
+                session.expected_discs,
+                session.discs_ripped,
+                session.next_episode,
+                session.output_dir,
+                session.status.to_string(),
+                now,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Edit a box-set session (e.g. to correct the episode counter or end it early)
+    pub fn update_rip_session(&self, session: &RipSession) -> Result<()> {
+        let id = session.id.ok_or_else(|| anyhow::anyhow!("Rip session has no ID"))?;
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE rip_sessions
+             SET show_name = ?1, season = ?2, expected_discs = ?3, discs_ripped = ?4, next_episode = ?5, output_dir = ?6, status = ?7, updated_at = ?8
+             WHERE id = ?9",
+            params![
+                session.show_name,
+                session.season,
+                session.expected_discs,
+                session.discs_ripped,
+                session.next_episode,
+                session.output_dir,
+                session.status.to_string(),
+                Utc::now().to_rfc3339(),
+                id,
+            ],
+        )?;
+        if updated == 0 {
+            return Err(anyhow::anyhow!("Rip session {} not found", id));
+        }
+        Ok(())
+    }
+
+    /// Get a box-set session by ID
+    pub fn get_rip_session(&self, id: i64) -> Result<Option<RipSession>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT id, show_name, season, expected_discs, discs_ripped, next_episode, output_dir, status, created_at, updated_at
+             FROM rip_sessions WHERE id = ?1",
+            [id],
+            Self::rip_session_from_row,
+        );
+
+        match result {
+            Ok(session) => Ok(Some(session)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
     }
 
-    /// Update queue entry status
-    pub fn update_queue_status(&self, id: i64, status: QueueStatus, started_at: Option<DateTime<Utc>>) -> Result<()> {
+    /// All box-set sessions, newest first
+    pub fn get_rip_sessions(&self) -> Result<Vec<RipSession>> {
         let conn = self.conn.lock().unwrap();
-        
-        if let Some(started) = started_at {
-            conn.execute(
-                "UPDATE rip_queue SET status = ?1, started_at = ?2 WHERE id = ?3",
-                params![status.to_string(), started.to_rfc3339(), id],
-            )?;
-        } else {
-            conn.execute(
-                "UPDATE rip_queue SET status = ?1 WHERE id = ?2",
-                params![status.to_string(), id],
-            )?;
+        let mut stmt = conn.prepare(
+            "SELECT id, show_name, season, expected_discs, discs_ripped, next_episode, output_dir, status, created_at, updated_at
+             FROM rip_sessions ORDER BY created_at DESC, id DESC"
+        )?;
+        let sessions = stmt.query_map([], Self::rip_session_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(sessions)
+    }
+
+    /// Count a finished disc against its session: advances the episode counter and completes the session after the last disc
+    ///
+    /// A single statement, so two drives finishing discs of the same session can't lose each other's counts.
+    pub fn record_rip_session_disc(&self, id: i64, episode_count: u32) -> Result<RipSession> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "UPDATE rip_sessions
+             SET discs_ripped = discs_ripped + 1,
+                 next_episode = next_episode + ?2,
+                 status = CASE WHEN discs_ripped + 1 >= expected_discs THEN 'completed' ELSE status END,
+                 updated_at = ?3
+             WHERE id = ?1
+             RETURNING id, show_name, season, expected_discs, discs_ripped, next_episode, output_dir, status, created_at, updated_at",
+            params![id, episode_count, Utc::now().to_rfc3339()],
+            Self::rip_session_from_row,
+        );
+
+        match result {
+            Ok(session) => Ok(session),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Err(anyhow::anyhow!("Rip session {} not found", id)),
+            Err(e) => Err(e.into()),
         }
+    }
 
+    /// Delete a box-set session
+    pub fn delete_rip_session(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM rip_sessions WHERE id = ?1", [id])?;
         Ok(())
     }
 
-    /// Get all queue entries
-    pub fn get_queue_entries(&self, include_completed: bool) -> Result<Vec<RipQueueEntry>> {
+    fn rip_session_from_row(row: &rusqlite::Row) -> rusqlite::Result<RipSession> {
+        Ok(RipSession {
+            id: Some(row.get(0)?),
+            show_name: row.get(1)?,
+            season: row.get(2)?,
+            expected_discs: row.get(3)?,
+            discs_ripped: row.get(4)?,
+            next_episode: row.get(5)?,
+            output_dir: row.get(6)?,
+            status: RipSessionStatus::from_string(&row.get::<_, String>(7)?),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(8)?)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(9)?)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+
+    /// Save a disc scan under the fingerprint of the disc it was taken from, returning its ID
+    pub fn save_disc_scan(&self, scan: &crate::disc_scan::DiscScan, fingerprint: Option<&str>) -> Result<i64> {
         let conn = self.conn.lock().unwrap();
+        let scanned_at = scan.scanned_at.unwrap_or_else(Utc::now);
         
-        let sql = if include_completed {
-            "SELECT id, created_at, drive, output_path, title, skip_metadata, skip_filebot, profile, priority, status, started_at
-             FROM rip_queue
-             ORDER BY priority DESC, created_at ASC"
-        } else {
-            "SELECT id, created_at, drive, output_path, title, skip_metadata, skip_filebot, profile, priority, status, started_at
-             FROM rip_queue
-             WHERE status != 'completed'
-             ORDER BY priority DESC, created_at ASC"
-        };
-
-        let mut stmt = conn.prepare(sql)?;
-        let entries = stmt.query_map([], |row| {
-            Ok(RipQueueEntry {
-                id: Some(row.get(0)?),
-                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(1)?)
-                    .unwrap()
-                    .with_timezone(&Utc),
-                drive: row.get(2)?,
-                output_path: row.get(3)?,
-                title: row.get(4)?,
-                skip_metadata: row.get::<_, i64>(5)? != 0,
-                skip_filebot: row.get::<_, i64>(6)? != 0,
-                profile: row.get(7)?,
-                priority: row.get(8)?,
-                status: QueueStatus::from_string(&row.get::<_, String>(9)?),
-                started_at: row.get::<_, Option<String>>(10)?
-                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|dt| dt.with_timezone(&Utc)),
-            })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
+        conn.execute(
+            "INSERT INTO disc_scans (device, disc_type, volume_label, title_count, scan_json, scanned_at, fingerprint)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                scan.device,
+                scan.disc_type,
+                scan.volume_label,
+                scan.titles.len() as i64,
+                serde_json::to_string(scan)?,
+                scanned_at.to_rfc3339(),
+                fingerprint,
+            ],
+        )?;
 
-        Ok(entries)
+        Ok(conn.last_insert_rowid())
     }
 
-    /// Remove queue entry (for cancellation)
-    #[allow(dead_code)]
-    pub fn remove_queue_entry(&self, id: i64) -> Result<()> {
+    /// Get the most recent scan of a disc, by fingerprint
+    pub fn get_latest_disc_scan(&self, fingerprint: &str) -> Result<Option<crate::disc_scan::DiscScan>> {
         let conn = self.conn.lock().unwrap();
-        conn.execute("DELETE FROM rip_queue WHERE id = ?1", params![id])?;
-        Ok(())
+        let result = conn.query_row(
+            "SELECT scan_json FROM disc_scans WHERE fingerprint = ?1 ORDER BY scanned_at DESC, id DESC LIMIT 1",
+            [fingerprint],
+            |row| row.get::<_, String>(0),
+        );
+
+        match result {
+            Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
     }
 
     /// Get recent rip history
     pub fn get_rip_history(&self, limit: i64) -> Result<Vec<RipHistory>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, timestamp, drive, disc, title, disc_type, status, duration_seconds, file_size_bytes, output_path, error_message, avg_speed_mbps, checksum
+            "SELECT id, timestamp, drive, disc, title, disc_type, status, duration_seconds, file_size_bytes, output_path, error_message, avg_speed_mbps, checksum, fingerprint
              FROM rip_history
              ORDER BY timestamp DESC
              LIMIT ?1"
         )?;
 
-        let history = stmt.query_map([limit], |row| {
-            Ok(RipHistory {
-                id: Some(row.get(0)?),
-                timestamp: DateTime::parse_from_rfc3339(&row.get::<_, String>(1)?)
-                    .unwrap()
-                    .with_timezone(&Utc),
-                drive: row.get(2)?,
-                disc: row.get(3)?,
-                title: row.get(4)?,
-                disc_type: row.get(5)?,
-                status: RipStatus::from_string(&row.get::<_, String>(6)?),
-                duration_seconds: row.get(7)?,
-                file_size_bytes: row.get(8)?,
-                output_path: row.get(9)?,
-                error_message: row.get(10)?,
-                avg_speed_mbps: row.get(11)?,
-                checksum: row.get(12)?,
-            })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
+        let history = stmt.query_map([limit], Self::rip_history_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(history)
     }
+
+    /// Most recent successful rip of the disc with this fingerprint
+    pub fn find_rip_by_fingerprint(&self, fingerprint: &str) -> Result<Option<RipHistory>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT id, timestamp, drive, disc, title, disc_type, status, duration_seconds, file_size_bytes, output_path, error_message, avg_speed_mbps, checksum, fingerprint
+             FROM rip_history
+             WHERE fingerprint = ?1 AND status = 'success'
+             ORDER BY timestamp DESC
+             LIMIT 1",
+            [fingerprint],
+            Self::rip_history_from_row,
+        );
+
+        match result {
+            Ok(history) => Ok(Some(history)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Record that `alias` is another fingerprint of the disc stored under `fingerprint`
+    pub fn add_fingerprint_alias(&self, alias: &str, fingerprint: &str) -> Result<()> {
+        if alias == fingerprint {
+            return Ok(());
+        }
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO disc_fingerprint_aliases (alias, fingerprint) VALUES (?1, ?2)",
+            params![alias, fingerprint],
+        )?;
+        Ok(())
+    }
+
+    /// The fingerprint a disc is stored under, given any of its fingerprints
+    pub fn canonical_fingerprint(&self, fingerprint: &str) -> Result<String> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT fingerprint FROM disc_fingerprint_aliases WHERE alias = ?1",
+            [fingerprint],
+            |row| row.get(0),
+        );
+        match result {
+            Ok(canonical) => Ok(canonical),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(fingerprint.to_string()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn rip_history_from_row(row: &rusqlite::Row) -> rusqlite::Result<RipHistory> {
+        Ok(RipHistory {
+            id: Some(row.get(0)?),
+            timestamp: DateTime::parse_from_rfc3339(&row.get::<_, String>(1)?)
+                .unwrap()
+                .with_timezone(&Utc),
+            drive: row.get(2)?,
+            disc: row.get(3)?,
+            title: row.get(4)?,
+            disc_type: row.get(5)?,
+            status: RipStatus::from_string(&row.get::<_, String>(6)?),
+            duration_seconds: row.get(7)?,
+            file_size_bytes: row.get(8)?,
+            output_path: row.get(9)?,
+            error_message: row.get(10)?,
+            avg_speed_mbps: row.get(11)?,
+            checksum: row.get(12)?,
+            fingerprint: row.get(13)?,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -3936,6 +5241,57 @@ mod tests {
 
         let show = db.get_show(id).unwrap().unwrap();
         assert_eq!(show.name, unique_name);
+        assert!(show.title_selection.is_none());
+    }
+
+    #[test]
+    fn test_show_title_selection() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+        let id = db.add_show(&format!("Cartoon Show {}", chrono::Utc::now().timestamp_millis())).unwrap();
+
+        let selection = crate::title_selection::TitleSelection {
+            min_minutes: Some(5),
+            max_minutes: Some(15),
+            ..Default::default()
+        };
+        db.set_show_title_selection(id, Some(&selection)).unwrap();
+        assert_eq!(db.get_show(id).unwrap().unwrap().title_selection, Some(selection));
+
+        db.set_show_title_selection(id, None).unwrap();
+        assert!(db.get_show(id).unwrap().unwrap().title_selection.is_none());
+        assert!(!db.set_show_title_selection(id + 1_000_000, None).unwrap());
+    }
+
+    #[test]
+    fn test_show_episode_order() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+        let name = format!("Firefly {}", chrono::Utc::now().timestamp_millis());
+        let id = db.add_show(&name).unwrap();
+        assert_eq!(db.episode_order_for_show(&name), crate::dvd_metadata::EpisodeOrder::Aired);
+
+        assert!(db.set_show_episode_order(id, Some(crate::dvd_metadata::EpisodeOrder::StoryArc)).unwrap());
+        assert_eq!(db.get_show(id).unwrap().unwrap().episode_order, Some(crate::dvd_metadata::EpisodeOrder::StoryArc));
+        assert_eq!(db.episode_order_for_show(&name.to_uppercase()), crate::dvd_metadata::EpisodeOrder::StoryArc);
+
+        assert!(db.set_show_episode_order(id, None).unwrap());
+        assert!(db.get_show(id).unwrap().unwrap().episode_order.is_none());
+        assert!(!db.set_show_episode_order(id + 1_000_000, None).unwrap());
+    }
+
+    #[test]
+    fn test_metadata_cache() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+        assert!(db.get_cached_metadata("tmdb", "episodes:1437:1").unwrap().is_none());
+
+        db.put_cached_metadata("tmdb", "episodes:1437:1", "[]").unwrap();
+        db.put_cached_metadata("tmdb", "episodes:1437:1", "[1]").unwrap();
+        let entry = db.get_cached_metadata("tmdb", "episodes:1437:1").unwrap().unwrap();
+        assert_eq!(entry.value_json, "[1]");
+        assert!(Utc::now() - entry.fetched_at < chrono::Duration::minutes(1));
+        assert!(db.get_cached_metadata("tvdb", "episodes:1437:1").unwrap().is_none());
     }
 
     #[test]
@@ -3970,12 +5326,193 @@ mod tests {
             error_message: None,
             avg_speed_mbps: None, // May not be in schema yet
             checksum: None,
+            fingerprint: None,
         };
 
         let id = db.add_rip_history(&history).unwrap();
         assert!(id > 0);
     }
 
+    #[test]
+    fn test_find_rip_by_fingerprint() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+        let rip = |title: &str, status: RipStatus, minutes_ago: i64| RipHistory {
+            id: None,
+            timestamp: Utc::now() - chrono::Duration::minutes(minutes_ago),
+            drive: "/dev/sr0".to_string(),
+            disc: None,
+            title: Some(title.to_string()),
+            disc_type: Some("dvd".to_string()),
+            status,
+            duration_seconds: Some(1800),
+            file_size_bytes: None,
+            output_path: None,
+            error_message: None,
+            avg_speed_mbps: None,
+            checksum: None,
+            fingerprint: Some("dvd:0123abcd".to_string()),
+        };
+        db.add_rip_history(&rip("Old Show", RipStatus::Success, 60)).unwrap();
+        db.add_rip_history(&rip("Futurama", RipStatus::Success, 30)).unwrap();
+        db.add_rip_history(&rip("Failed Attempt", RipStatus::Failed, 5)).unwrap();
+
+        let known = db.find_rip_by_fingerprint("dvd:0123abcd").unwrap().unwrap();
+        assert_eq!(known.title.as_deref(), Some("Futurama"));
+        assert_eq!(known.fingerprint.as_deref(), Some("dvd:0123abcd"));
+        assert!(db.find_rip_by_fingerprint("dvd:ffff").unwrap().is_none());
+        assert_eq!(db.get_rip_history(10).unwrap()[0].fingerprint.as_deref(), Some("dvd:0123abcd"));
+
+        // A mounted disc's structure fingerprint leads back to the fingerprint it was ripped under
+        db.add_fingerprint_alias("bluray:5678", "dvd:0123abcd").unwrap();
+        assert_eq!(db.canonical_fingerprint("bluray:5678").unwrap(), "dvd:0123abcd");
+        assert_eq!(db.canonical_fingerprint("dvd:ffff").unwrap(), "dvd:ffff");
+    }
+
+    #[test]
+    fn test_disc_assignments() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+        let assignment = DiscAssignment {
+            id: None,
+            fingerprint: Some("dvd:0123abcd".to_string()),
+            volume_label: Some("FUTURAMA_S3_D1".to_string()),
+            show_name: "Futurama".to_string(),
+            season: 3,
+            first_episode: 1,
+            episode_count: 4,
+            updated_at: Utc::now(),
+        };
+        let id = db.save_disc_assignment(&assignment).unwrap();
+
+        // Saving the same disc again updates it rather than adding another row
+        let updated = DiscAssignment { episode_count: 5, ..assignment.clone() };
+        assert_eq!(db.save_disc_assignment(&updated).unwrap(), id);
+        let stored = db.get_disc_assignment(Some("dvd:0123abcd"), None).unwrap().unwrap();
+        assert_eq!(stored.id, Some(id));
+        assert_eq!(stored.episode_count, 5);
+        assert_eq!(stored.next_disc(), (3, 6));
+
+        // A fingerprinted assignment isn't matched by label alone from a different disc
+        assert!(db.get_disc_assignment(Some("dvd:ffff"), Some("FUTURAMA_S3_D1")).unwrap().is_none());
+        assert!(db.get_disc_assignment(None, Some("FUTURAMA_S3_D1")).unwrap().is_some());
+
+        // Discs without a fingerprint are keyed by label
+        let unfingerprinted = DiscAssignment { fingerprint: None, volume_label: Some("FUTURAMA_S3_D2".to_string()), first_episode: 6, ..assignment };
+        let other_id = db.save_disc_assignment(&unfingerprinted).unwrap();
+        assert_ne!(other_id, id);
+        assert_eq!(db.get_disc_assignment(Some("dvd:ffff"), Some("FUTURAMA_S3_D2")).unwrap().unwrap().id, Some(other_id));
+
+        assert_eq!(db.get_disc_assignments(Some("futurama")).unwrap().len(), 2);
+        assert!(db.get_disc_assignments(Some("Other Show")).unwrap().is_empty());
+        db.delete_disc_assignment(id).unwrap();
+        assert_eq!(db.get_disc_assignments(None).unwrap().len(), 1);
+        assert!(db.save_disc_assignment(&DiscAssignment { id: Some(id), ..unfingerprinted.clone() }).is_err());
+        assert!(!db.update_disc_assignment(id, &unfingerprinted).unwrap());
+        assert!(db.update_disc_assignment(other_id, &DiscAssignment { episode_count: 3, ..unfingerprinted }).unwrap());
+        assert_eq!(db.get_disc_assignment(None, Some("FUTURAMA_S3_D2")).unwrap().unwrap().episode_count, 3);
+    }
+
+    #[test]
+    fn test_resolve_disc_assignment_continues_box_set() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+        db.save_disc_assignment(&DiscAssignment {
+            id: None,
+            fingerprint: Some("dvd:disc1".to_string()),
+            volume_label: Some("SHOW_S3_D1".to_string()),
+            show_name: "Show".to_string(),
+            season: 3,
+            first_episode: 1,
+            episode_count: 4,
+            updated_at: Utc::now(),
+        }).unwrap();
+
+        // Reinserting disc 1 finds its assignment
+        let disc1 = db.resolve_disc_assignment(Some("dvd:disc1"), None, None).unwrap();
+        assert_eq!((disc1.season, disc1.first_episode), (3, 1));
+
+        // Disc 2 of the same season continues where disc 1 stopped
+        let disc2 = db.resolve_disc_assignment(Some("dvd:disc2"), Some("SHOW_S3_D2"), Some("show")).unwrap();
+        assert_eq!((disc2.id, disc2.season, disc2.first_episode), (None, 3, 5));
+        assert_eq!(disc2.fingerprint.as_deref(), Some("dvd:disc2"));
+
+        // Unknown shows and other seasons have nothing to continue from
+        assert!(db.resolve_disc_assignment(Some("dvd:disc2"), None, None).is_none());
+        assert!(db.resolve_disc_assignment(Some("dvd:other"), Some("SHOW_S4_D1"), Some("Show")).is_none());
+    }
+
+    #[test]
+    fn test_disc_assignment_after_rip() {
+        // A new disc starts at its first confident match, or the label's season without one
+        let matched = DiscAssignment::after_rip(None, "Show".to_string(), Some((2, 5)), Some("dvd:disc2".to_string()), None, 4);
+        assert_eq!((matched.season, matched.first_episode, matched.episode_count), (2, 5, 4));
+        let unmatched = DiscAssignment::after_rip(None, "Show".to_string(), None, None, Some("SHOW_S3_D1".to_string()), 0);
+        assert_eq!((unmatched.season, unmatched.first_episode), (3, 1));
+
+        // A disc ripped under an assignment keeps its numbering and picks up this disc's keys
+        let session = RipSession {
+            id: Some(1),
+            show_name: "Show".to_string(),
+            season: 2,
+            expected_discs: 3,
+            discs_ripped: 1,
+            next_episode: 9,
+            output_dir: None,
+            status: RipSessionStatus::Active,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let previous = DiscAssignment::for_session(&session, None, Some("SHOW_D2".to_string()));
+        let ripped = DiscAssignment::after_rip(Some(&previous), "Other".to_string(), Some((2, 10)), Some("dvd:disc2".to_string()), None, 4);
+        assert_eq!((ripped.show_name.as_str(), ripped.season, ripped.first_episode, ripped.episode_count), ("Show", 2, 9, 4));
+        assert_eq!((ripped.fingerprint.as_deref(), ripped.volume_label.as_deref()), (Some("dvd:disc2"), Some("SHOW_D2")));
+        assert_eq!(ripped.next_disc(), (2, 13));
+    }
+
+    #[test]
+    fn test_rip_sessions() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+        let session = RipSession {
+            id: None,
+            show_name: "Futurama".to_string(),
+            season: 3,
+            expected_discs: 2,
+            discs_ripped: 0,
+            next_episode: 1,
+            output_dir: None,
+            status: RipSessionStatus::Active,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let id = db.create_rip_session(&session).unwrap();
+        let active = db.get_rip_session(id).unwrap().unwrap();
+        assert_eq!(active.id, Some(id));
+        assert_eq!(active.summary(0), "disc 1 of 2, starting at episode 1");
+        assert_eq!(active.summary(4), "disc 1 of 2, episodes 1–4");
+        assert_eq!(
+            active.season_folder(std::path::Path::new("/rips")),
+            std::path::PathBuf::from("/rips/Futurama/Season 03")
+        );
+
+        // Each disc continues the numbering; the last disc completes the session
+        let after_first = db.record_rip_session_disc(id, 4).unwrap();
+        assert_eq!((after_first.discs_ripped, after_first.next_episode), (1, 5));
+        assert_eq!(after_first.summary(3), "disc 2 of 2, episodes 5–7");
+        let after_second = db.record_rip_session_disc(id, 3).unwrap();
+        assert_eq!(after_second.status, RipSessionStatus::Completed);
+        assert_eq!(db.get_rip_session(id).unwrap().unwrap().status, RipSessionStatus::Completed);
+
+        // Sessions can be edited and deleted
+        db.update_rip_session(&RipSession { expected_discs: 3, status: RipSessionStatus::Active, ..after_second }).unwrap();
+        assert_eq!(db.get_rip_session(id).unwrap().unwrap().expected_discs, 3);
+        assert_eq!(db.get_rip_sessions().unwrap().len(), 1);
+        db.delete_rip_session(id).unwrap();
+        assert!(db.get_rip_session(id).unwrap().is_none());
+        assert!(db.record_rip_session_disc(id, 1).is_err());
+    }
+
     #[test]
     fn test_get_statistics() {
         setup_test_db();
@@ -3997,12 +5534,42 @@ mod tests {
                 error_message: None,
                 avg_speed_mbps: None,
                 checksum: None,
+                fingerprint: None,
             };
             db.add_rip_history(&history).unwrap();
         }
 
         let stats = db.get_statistics().unwrap();
         assert!(stats["total_rips"].as_i64().unwrap() >= 5);
+        assert!(stats["by_disc_type"]["dvd"]["total_rips"].as_i64().unwrap() >= 5);
+    }
+
+    #[test]
+    fn test_statistics_separate_bluray() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+        let history = RipHistory {
+            id: None,
+            timestamp: Utc::now(),
+            drive: "/dev/sr0".to_string(),
+            disc: None,
+            title: Some("Movie".to_string()),
+            disc_type: crate::drive::MediaType::BluRay.history_label().map(String::from),
+            status: RipStatus::Success,
+            duration_seconds: Some(3600),
+            file_size_bytes: Some(30_000_000_000),
+            output_path: None,
+            error_message: None,
+            avg_speed_mbps: None,
+            checksum: None,
+            fingerprint: None,
+        };
+        db.add_rip_history(&history).unwrap();
+
+        let stats = db.get_statistics().unwrap();
+        let bluray = &stats["by_disc_type"]["bluray"];
+        assert_eq!(bluray["successful_rips"].as_i64(), Some(1));
+        assert_eq!(bluray["total_storage_bytes"].as_i64(), Some(30_000_000_000));
     }
 
     #[test]
@@ -4026,6 +5593,55 @@ mod tests {
         assert!(id > 0);
     }
 
+    #[test]
+    fn test_episode_match_reviews() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+        let candidate = |episode: u32, score: f32| crate::episode_assignment::EpisodeCandidate {
+            season: 1,
+            episode,
+            title: format!("Episode {}", episode),
+            score,
+        };
+        let review = EpisodeMatchReview {
+            id: None,
+            created_at: Utc::now(),
+            show_name: "Test Show".to_string(),
+            file_path: "/rips/Test Show/title_t00.mkv".to_string(),
+            candidates: vec![candidate(3, 100.0), candidate(4, 80.0)],
+            confidence: 42.0,
+            status: ReviewStatus::Pending,
+            resolved_at: None,
+            resolved_season: None,
+            resolved_episode: None,
+        };
+        db.add_episode_match_review(&review).unwrap();
+        let second = db.add_episode_match_review(&EpisodeMatchReview {
+            file_path: "/rips/Test Show/title_t01.mkv".to_string(),
+            ..review.clone()
+        }).unwrap();
+        // Re-matching a file replaces its pending review
+        let first = {
+            db.add_episode_match_review(&review).unwrap();
+            db.get_pending_episode_match_reviews().unwrap().iter()
+                .find(|r| r.file_path == review.file_path).unwrap().id.unwrap()
+        };
+        assert_eq!(db.get_pending_episode_match_reviews().unwrap().len(), 2);
+        assert_eq!(db.get_episode_match_review(first).unwrap().unwrap().candidates[1], candidate(4, 80.0));
+
+        db.resolve_episode_match_review(first, ReviewStatus::Accepted, 1, 3).unwrap();
+        db.resolve_episode_match_review(second, ReviewStatus::Overridden, 1, 5).unwrap();
+        assert!(db.resolve_episode_match_review(second, ReviewStatus::Accepted, 1, 5).is_err());
+        assert!(db.get_pending_episode_match_reviews().unwrap().is_empty());
+        let resolved = db.get_episode_match_review(second).unwrap().unwrap();
+        assert_eq!((resolved.status, resolved.resolved_episode), (ReviewStatus::Overridden, Some(5)));
+
+        let stats = db.get_episode_match_statistics().unwrap();
+        assert_eq!(stats["reviews"]["accepted"], 1);
+        assert_eq!(stats["reviews"]["overridden"], 1);
+        assert_eq!(stats["reviews"]["accuracy"], 50.0);
+    }
+
     #[test]
     fn test_queue_operations() {
         setup_test_db();
@@ -4043,6 +5659,9 @@ mod tests {
             priority: 5,
             status: QueueStatus::Pending,
             started_at: None,
+            titles: Some(vec![1, 3]),
+            session_id: Some(7),
+            reuse_fingerprint: Some("dvd:abc123".to_string()),
         };
 
         let id = db.add_to_queue(&entry).unwrap();
@@ -4051,7 +5670,87 @@ mod tests {
         db.update_queue_status(id, QueueStatus::Processing, Some(Utc::now())).unwrap();
         
         let entries = db.get_queue_entries(false).unwrap();
-        assert!(entries.iter().any(|e| e.id == Some(id)));
+        let stored = entries.iter().find(|e| e.id == Some(id)).unwrap();
+        assert_eq!(stored.titles, Some(vec![1, 3]));
+        assert_eq!(stored.session_id, Some(7));
+        assert_eq!(stored.reuse_fingerprint.as_deref(), Some("dvd:abc123"));
+    }
+
+    #[test]
+    fn test_rip_checkpoints_and_reconcile() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+
+        for title_index in [1, 2] {
+            let checkpoint = RipCheckpoint {
+                id: None,
+                fingerprint: "SHOW_S1_D2".to_string(),
+                device: "/dev/sr0".to_string(),
+                output_dir: "/tmp/rips/Show".to_string(),
+                title_index,
+                output_file: format!("/tmp/rips/Show/title_t{:02}.mkv", title_index),
+                file_size_bytes: Some(1024),
+                completed_at: Utc::now(),
+            };
+            db.add_rip_checkpoint(&checkpoint).unwrap();
+            // Re-ripping a title replaces its checkpoint
+            db.add_rip_checkpoint(&checkpoint).unwrap();
+        }
+
+        let checkpoints = db.get_rip_checkpoints(Some("SHOW_S1_D2")).unwrap();
+        assert_eq!(checkpoints.iter().map(|c| c.title_index).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(db.get_rip_checkpoints(Some("OTHER_DISC")).unwrap().is_empty());
+        assert_eq!(db.clear_rip_checkpoints("SHOW_S1_D2").unwrap(), 2);
+
+        let entry = RipQueueEntry {
+            id: None,
+            created_at: Utc::now(),
+            drive: Some("/dev/sr0".to_string()),
+            output_path: None,
+            title: None,
+            skip_metadata: false,
+            skip_filebot: false,
+            profile: None,
+            priority: 0,
+            status: QueueStatus::Pending,
+            started_at: None,
+            titles: None,
+            session_id: None,
+            reuse_fingerprint: None,
+        };
+        let id = db.add_to_queue(&entry).unwrap();
+        db.update_queue_status(id, QueueStatus::Processing, Some(Utc::now())).unwrap();
+
+        assert_eq!(db.reconcile_stale_queue_entries().unwrap(), 1);
+        let entry = db.get_next_queue_entry(Some("/dev/sr0")).unwrap().unwrap();
+        assert_eq!(entry.id, Some(id));
+        assert!(entry.started_at.is_none());
+    }
+
+    #[test]
+    fn test_disc_scan_persistence() {
+        setup_test_db();
+        let db = Database::new().unwrap();
+        let scan = crate::disc_scan::DiscScan {
+            device: "/dev/sr9".to_string(),
+            disc_type: Some("DVD disc".to_string()),
+            volume_label: Some("SHOW_S1_D1".to_string()),
+            titles: vec![crate::disc_scan::TitleInfo {
+                index: 0,
+                duration_seconds: 1320,
+                segment_map: Some("1-3".to_string()),
+                ..Default::default()
+            }],
+            scanned_at: Some(Utc::now()),
+            ..Default::default()
+        };
+
+        assert!(db.get_latest_disc_scan("dvd:disc1").unwrap().is_none());
+        db.save_disc_scan(&scan, Some("dvd:disc1")).unwrap();
+        let loaded = db.get_latest_disc_scan("dvd:disc1").unwrap().unwrap();
+        assert_eq!(loaded, scan);
+        // Another disc in the same drive doesn't get this disc's scan
+        assert!(db.get_latest_disc_scan("dvd:disc2").unwrap().is_none());
     }
 
     #[test]
@@ -0,0 +1,283 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Everything a backend found on a disc before ripping
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiscScan {
+    pub device: String,
+    pub disc_type: Option<String>,    // e.g. "DVD disc", "Blu-ray disc"
+    pub disc_name: Option<String>,
+    pub volume_label: Option<String>,
+    pub titles: Vec<TitleInfo>,
+    #[serde(default)]
+    pub scanned_at: Option<DateTime<Utc>>,
+}
+
+/// A single title (video) or track (audio) on a disc
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TitleInfo {
+    pub index: u32,
+    pub name: Option<String>,
+    pub duration_seconds: u32,
+    pub size_bytes: Option<u64>,
+    #[serde(default)]
+    pub chapter_count: u32,
+    #[serde(default)]
+    pub source_file: Option<String>,  // e.g. "00800.mpls" on Blu-ray
+    #[serde(default)]
+    pub segment_count: u32,
+    #[serde(default)]
+    pub segment_map: Option<String>,  // e.g. "1-5,7" (DVD cells) or "55,56" (BD clips)
+    #[serde(default)]
+    pub angle: Option<String>,
+    #[serde(default)]
+    pub video_streams: Vec<StreamInfo>,
+    #[serde(default)]
+    pub audio_streams: Vec<StreamInfo>,
+    #[serde(default)]
+    pub subtitle_streams: Vec<StreamInfo>,
+}
+
+/// An audio, video or subtitle stream within a title
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StreamInfo {
+    pub index: u32,
+    pub codec: Option<String>,
+    pub codec_long: Option<String>,
+    pub language_code: Option<String>,
+    pub language_name: Option<String>,
+    pub channels: Option<u32>,
+    pub description: Option<String>,
+}
+
+impl TitleInfo {
+    /// Duration formatted as "H:MM:SS" (the format MakeMKV reports)
+    pub fn duration_string(&self) -> String {
+        let secs = self.duration_seconds;
+        format!("{}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+    }
+
+    /// Duration in whole minutes, rounding to the nearest minute
+    pub fn duration_minutes(&self) -> u32 {
+        (self.duration_seconds + 30) / 60
+    }
+
+    /// Segments (cells or clips) this title plays, in order, with ranges expanded
+    pub fn segments(&self) -> Vec<String> {
+        let Some(map) = self.segment_map.as_deref() else { return Vec::new() };
+        let mut segments = Vec::new();
+        for part in map.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match part.split_once('-').map(|(a, b)| (a.trim().parse::<u32>(), b.trim().parse::<u32>())) {
+                Some((Ok(start), Ok(end))) if start <= end && end - start < 10_000 => {
+                    segments.extend((start..=end).map(|n| n.to_string()));
+                }
+                _ => segments.push(part.parse::<u32>().map(|n| n.to_string()).unwrap_or_else(|_| part.to_string())),
+            }
+        }
+        segments
+    }
+}
+
+/// MakeMKV attribute ids (see apdefs.h in the MakeMKV SDK)
+mod attr {
+    pub const TYPE: u32 = 1;
+    pub const NAME: u32 = 2;
+    pub const LANG_CODE: u32 = 3;
+    pub const LANG_NAME: u32 = 4;
+    pub const CODEC_SHORT: u32 = 6;
+    pub const CODEC_LONG: u32 = 7;
+    pub const CHAPTER_COUNT: u32 = 8;
+    pub const DURATION: u32 = 9;
+    pub const DISK_SIZE_BYTES: u32 = 11;
+    pub const AUDIO_CHANNELS: u32 = 14;
+    pub const ANGLE_INFO: u32 = 15;
+    pub const SOURCE_FILE_NAME: u32 = 16;
+    pub const SEGMENTS_COUNT: u32 = 25;
+    pub const SEGMENTS_MAP: u32 = 26;
+    pub const TREE_INFO: u32 = 30;
+    pub const VOLUME_NAME: u32 = 32;
+}
+
+/// Parse "H:MM:SS" into seconds
+pub fn parse_hms(duration: &str) -> Option<u32> {
+    let parts: Vec<&str> = duration.split(':').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let hours: u32 = parts[0].parse().ok()?;
+    let minutes: u32 = parts[1].parse().ok()?;
+    let seconds: u32 = parts[2].parse().ok()?;
+
+    Some(hours * 3600 + minutes * 60 + seconds)
+}
+
+/// Split the comma separated fields of a robot line, honouring quoted values
+fn split_robot_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in line.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+impl DiscScan {
+    /// Parse `makemkvcon -r info` output (CINFO/TINFO/SINFO lines)
+    pub fn parse_makemkv(device: &str, output: &str) -> DiscScan {
+        let mut scan = DiscScan {
+            device: device.to_string(),
+            scanned_at: Some(Utc::now()),
+            ..Default::default()
+        };
+        let mut titles: BTreeMap<u32, TitleInfo> = BTreeMap::new();
+        // (title, stream) -> (type, attributes)
+        let mut streams: BTreeMap<(u32, u32), (String, StreamInfo)> = BTreeMap::new();
+
+        for line in output.lines() {
+            let Some((kind, rest)) = line.split_once(':') else { continue };
+            let fields = split_robot_fields(rest);
+            let num = |i: usize| fields.get(i).and_then(|f| f.trim().parse::<u32>().ok());
+
+            match kind {
+                "CINFO" => {
+                    let (Some(id), Some(value)) = (num(0), fields.get(2)) else { continue };
+                    match id {
+                        attr::TYPE => scan.disc_type = Some(value.clone()),
+                        attr::NAME => scan.disc_name = Some(value.clone()),
+                        attr::VOLUME_NAME => scan.volume_label = Some(value.clone()),
+                        _ => {}
+                    }
+                }
+                "TINFO" => {
+                    let (Some(idx), Some(id), Some(value)) = (num(0), num(1), fields.get(3)) else { continue };
+                    let title = titles.entry(idx).or_insert_with(|| TitleInfo { index: idx, ..Default::default() });
+                    match id {
+                        attr::NAME => title.name = Some(value.clone()),
+                        attr::CHAPTER_COUNT => title.chapter_count = value.parse().unwrap_or(0),
+                        attr::DURATION => title.duration_seconds = parse_hms(value).unwrap_or(0),
+                        attr::DISK_SIZE_BYTES => title.size_bytes = value.parse().ok(),
+                        attr::ANGLE_INFO => title.angle = Some(value.clone()),
+                        attr::SOURCE_FILE_NAME => title.source_file = Some(value.clone()),
+                        attr::SEGMENTS_COUNT => title.segment_count = value.parse().unwrap_or(0),
+                        attr::SEGMENTS_MAP => title.segment_map = Some(value.clone()),
+                        _ => {}
+                    }
+                }
+                "SINFO" => {
+                    let (Some(idx), Some(stream_idx), Some(id), Some(value)) =
+                        (num(0), num(1), num(2), fields.get(4)) else { continue };
+                    let (stream_type, stream) = streams.entry((idx, stream_idx))
+                        .or_insert_with(|| (String::new(), StreamInfo { index: stream_idx, ..Default::default() }));
+                    match id {
+                        attr::TYPE => *stream_type = value.clone(),
+                        attr::LANG_CODE => stream.language_code = Some(value.clone()),
+                        attr::LANG_NAME => stream.language_name = Some(value.clone()),
+                        attr::CODEC_SHORT => stream.codec = Some(value.clone()),
+                        attr::CODEC_LONG => stream.codec_long = Some(value.clone()),
+                        attr::AUDIO_CHANNELS => stream.channels = value.parse().ok(),
+                        attr::TREE_INFO => stream.description = Some(value.clone()),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for ((idx, _), (stream_type, stream)) in streams {
+            let Some(title) = titles.get_mut(&idx) else { continue };
+            match stream_type.as_str() {
+                "Video" => title.video_streams.push(stream),
+                "Audio" => title.audio_streams.push(stream),
+                "Subtitles" => title.subtitle_streams.push(stream),
+                _ => {}
+            }
+        }
+
+        scan.titles = titles.into_values().collect();
+        scan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAKEMKV_INFO: &str = r#"MSG:1005,0,1,"MakeMKV v1.17.5 linux(x64-release) started","%1 started","MakeMKV v1.17.5 linux(x64-release)"
+DRV:0,2,999,1,"DVD+R-DL HL-DT-ST","SHOW_S1_D1","/dev/sr0"
+TCOUNT:2
+CINFO:1,6206,"DVD disc"
+CINFO:2,0,"SHOW_S1_D1"
+CINFO:32,0,"SHOW_S1_D1"
+TINFO:0,2,0,"Play All"
+TINFO:0,8,0,"12"
+TINFO:0,9,0,"1:28:12"
+TINFO:0,11,0,"4312345600"
+TINFO:0,25,0,"4"
+TINFO:0,26,0,"1-4"
+TINFO:1,2,0,"Episode, Part 1"
+TINFO:1,8,0,"3"
+TINFO:1,9,0,"0:22:03"
+TINFO:1,11,0,"1073741824"
+TINFO:1,15,0,"1"
+TINFO:1,25,0,"1"
+TINFO:1,26,0,"1"
+SINFO:1,0,1,6201,"Video"
+SINFO:1,0,6,0,"Mpeg2"
+SINFO:1,1,1,6202,"Audio"
+SINFO:1,1,3,0,"eng"
+SINFO:1,1,4,0,"English"
+SINFO:1,1,6,0,"AC3"
+SINFO:1,1,7,0,"Dolby Digital"
+SINFO:1,1,14,0,"6"
+SINFO:1,2,1,6203,"Subtitles"
+SINFO:1,2,3,0,"fra"
+SINFO:1,2,6,0,"DVDSub"
+"#;
+
+    #[test]
+    fn test_parse_makemkv_scan() {
+        let scan = DiscScan::parse_makemkv("/dev/sr0", MAKEMKV_INFO);
+        assert_eq!(scan.disc_type.as_deref(), Some("DVD disc"));
+        assert_eq!(scan.volume_label.as_deref(), Some("SHOW_S1_D1"));
+        assert_eq!(scan.titles.len(), 2);
+
+        let play_all = &scan.titles[0];
+        assert_eq!(play_all.chapter_count, 12);
+        assert_eq!(play_all.duration_seconds, 3600 + 28 * 60 + 12);
+        assert_eq!(play_all.size_bytes, Some(4312345600));
+        assert_eq!(play_all.segments(), vec!["1", "2", "3", "4"]);
+
+        let episode = &scan.titles[1];
+        assert_eq!(episode.name.as_deref(), Some("Episode, Part 1"));
+        assert_eq!(episode.angle.as_deref(), Some("1"));
+        assert_eq!(episode.video_streams.len(), 1);
+        assert_eq!(episode.audio_streams.len(), 1);
+        assert_eq!(episode.audio_streams[0].language_code.as_deref(), Some("eng"));
+        assert_eq!(episode.audio_streams[0].codec.as_deref(), Some("AC3"));
+        assert_eq!(episode.audio_streams[0].channels, Some(6));
+        assert_eq!(episode.subtitle_streams[0].language_code.as_deref(), Some("fra"));
+    }
+
+    #[test]
+    fn test_title_durations() {
+        let title = TitleInfo { duration_seconds: 22 * 60 + 31, ..Default::default() };
+        assert_eq!(title.duration_string(), "0:22:31");
+        assert_eq!(title.duration_minutes(), 23);
+        assert_eq!(parse_hms("1:02:03"), Some(3723));
+        assert_eq!(parse_hms("garbage"), None);
+    }
+
+    #[test]
+    fn test_bluray_segment_map() {
+        let title = TitleInfo { segment_map: Some("00055,00056, 00101".to_string()), ..Default::default() };
+        assert_eq!(title.segments(), vec!["55", "56", "101"]);
+    }
+}
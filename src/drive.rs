@@ -13,6 +13,18 @@ pub enum MediaType {
     None,
 }
 
+impl MediaType {
+    /// Disc type recorded in rip history, so statistics can tell media apart
+    pub fn history_label(&self) -> Option<&'static str> {
+        match self {
+            MediaType::AudioCD => Some("cd"),
+            MediaType::DVD => Some("dvd"),
+            MediaType::BluRay => Some("bluray"),
+            MediaType::None => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct DriveInfo {
     pub device: String,
@@ -737,6 +749,44 @@ async fn eject_disc_linux(device: &str) -> Result<()> {
     Ok(())
 }
 
+/// Find where the disc in `device` is mounted, if it is (cross-platform)
+pub fn find_mount_point(device: &str) -> Option<std::path::PathBuf> {
+    #[cfg(target_os = "macos")]
+    let mount = Command::new("diskutil")
+        .arg("info")
+        .arg(device)
+        .output()
+        .ok()
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stdout).lines()
+                .find(|line| line.trim().starts_with("Mount Point:"))
+                .and_then(|line| line.split_once(':'))
+                .map(|(_, mount)| mount.trim().to_string())
+        });
+
+    #[cfg(target_os = "linux")]
+    let mount = Command::new("lsblk")
+        .arg("-n")
+        .arg("-o")
+        .arg("MOUNTPOINT")
+        .arg(device)
+        .output()
+        .ok()
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stdout).lines()
+                .next()
+                .map(|line| line.trim().to_string())
+        });
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    let mount: Option<String> = None;
+
+    mount
+        .filter(|m| !m.is_empty() && m != "null" && !m.starts_with("Not applicable"))
+        .map(std::path::PathBuf::from)
+        .filter(|path| path.is_dir())
+}
+
 /// Unmount a disc from the specified drive (cross-platform)
 pub async fn unmount_disc(device: &str) -> Result<()> {
     info!("Unmounting disc from {}", device);
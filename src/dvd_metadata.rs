@@ -1,10 +1,8 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
 use tracing::{debug, info, warn};
 
-const TMDB_API_BASE: &str = "https://api.themoviedb.org/3";
-const TMDB_API_KEY: &str = "fef1285fb85a74350b3292b5fac37fce"; // Users need to set this
+use crate::metadata_provider::{MetadataProvider, SearchResult};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DvdMetadata {
@@ -31,6 +29,58 @@ pub struct Episode {
     pub overview: Option<String>, // Episode summary/description from TMDB
 }
 
+/// How a show's episodes are numbered
+///
+/// Anything but aired order comes from a TMDB episode group, where each group becomes a season and episodes
+/// are numbered by their position in it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EpisodeOrder {
+    #[default]
+    Aired,
+    Dvd,
+    Absolute,
+    StoryArc,
+    Production,
+    Digital,
+}
+
+impl EpisodeOrder {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().replace(['-', ' '], "_").as_str() {
+            "aired" | "airdate" | "broadcast" => Some(EpisodeOrder::Aired),
+            "dvd" => Some(EpisodeOrder::Dvd),
+            "absolute" => Some(EpisodeOrder::Absolute),
+            "story_arc" | "storyarc" => Some(EpisodeOrder::StoryArc),
+            "production" => Some(EpisodeOrder::Production),
+            "digital" => Some(EpisodeOrder::Digital),
+            _ => None,
+        }
+    }
+
+    /// TMDB episode group type; aired order uses the regular seasons
+    pub fn tmdb_group_type(self) -> Option<u64> {
+        match self {
+            EpisodeOrder::Aired => None,
+            EpisodeOrder::Absolute => Some(2),
+            EpisodeOrder::Dvd => Some(3),
+            EpisodeOrder::Digital => Some(4),
+            EpisodeOrder::StoryArc => Some(5),
+            EpisodeOrder::Production => Some(6),
+        }
+    }
+
+    /// Filebot's `--order` name, if Filebot knows this order
+    pub fn filebot_order(self) -> Option<&'static str> {
+        match self {
+            EpisodeOrder::Aired => Some("Airdate"),
+            EpisodeOrder::Dvd => Some("DVD"),
+            EpisodeOrder::Absolute => Some("Absolute"),
+            EpisodeOrder::StoryArc | EpisodeOrder::Production | EpisodeOrder::Digital => None,
+        }
+    }
+}
+
 /// Get DVD/Blu-ray disc ID using multiple identification methods
 #[allow(dead_code)]
 pub async fn get_dvd_id(device: &str) -> Result<String> {
@@ -164,12 +214,19 @@ pub async fn get_dvd_id(device: &str) -> Result<String> {
 /// - "SEASON 3" -> Some(3)
 /// - "S02" -> Some(2)
 /// - "VOL 1" -> Some(1)
-#[allow(dead_code)]
+/// - "SEASONS 1-3" -> None (the disc spans seasons)
 pub fn extract_season_from_volume(name: &str) -> Option<u32> {
+    // Labels separate words with spaces or underscores
+    let spans_seasons = regex::Regex::new(r"(?i)(?:SEASONS?|S)[\s_]*\d+[\s_]*(?:-|TO)[\s_]*(?:SEASON[\s_]*|S)?\d+")
+        .is_ok_and(|re| re.is_match(name));
+    if spans_seasons {
+        return None;
+    }
+    
     let patterns = [
-        r"(?i)VOLUME\s*(\d+)",
-        r"(?i)VOL\s*(\d+)",
-        r"(?i)SEASON\s*(\d+)",
+        r"(?i)VOLUME[\s_]*(\d+)",
+        r"(?i)VOL[\s_]*(\d+)",
+        r"(?i)SEASON[\s_]*(\d+)",
         r"(?i)S(\d+)",
     ];
     
@@ -278,169 +335,226 @@ fn generate_search_variations(name: &str) -> Vec<String> {
     variations
 }
 
-/// Fetch DVD metadata from TMDB by searching with title
-pub async fn fetch_dvd_metadata(_disc_id: &str, volume_name: Option<&str>) -> Result<DvdMetadata> {
-    fetch_dvd_metadata_with_episode(_disc_id, volume_name, None).await
+/// A show or movie a disc might hold, from a metadata search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetadataCandidate {
+    pub media_type: MediaType,
+    pub id: String, // Provider's own ID
+    pub title: String,
+    pub year: Option<String>,
+    pub overview: Option<String>,
+    pub poster_url: Option<String>,
+    pub score: i32, // Match quality against the volume name; candidates come best first
 }
 
-/// Fetch DVD metadata from TMDB with optional starting episode hint
-pub async fn fetch_dvd_metadata_with_episode(_disc_id: &str, volume_name: Option<&str>, start_episode: Option<u32>) -> Result<DvdMetadata> {
-    debug!("fetch_dvd_metadata called with volume_name: {:?}, start_episode: {:?}", volume_name, start_episode);
-    
-    // Check if TMDB API key is configured
-    #[allow(clippy::const_is_empty)]
-    if TMDB_API_KEY.is_empty() {
-        warn!("⚠️  TMDB API key is empty");
-        warn!("⚠️  Update TMDB_API_KEY in src/dvd_metadata.rs to enable metadata lookup");
+/// Seasons fetched for a disc whose season is unknown; longer-running shows need the season on the label or
+/// disc assignment
+const MAX_UNKNOWN_SEASONS: usize = 10;
+
+/// At most this many candidates are offered
+const MAX_CANDIDATES: usize = 8;
+
+/// The best candidate must beat the runner-up by this much to be picked without asking (an exact title
+/// match is worth 1000, so two exact matches - a remake, or a show and a movie of the same name - are a tie)
+const CLEAR_LEAD: i32 = 500;
+
+/// How long a rip waits for the user to pick between metadata candidates before taking the best one
+pub const METADATA_CHOICE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Look up a disc's show or movie by volume name, with optional season and starting episode hints (e.g. from
+/// a disc assignment)
+///
+/// Takes the best-ranked candidate whose details can be fetched. Seasons and episode numbers follow `order`,
+/// falling back to aired order when the show has no such order.
+pub async fn fetch_dvd_metadata_with_episode(
+    provider: &dyn MetadataProvider,
+    volume_name: Option<&str>,
+    season: Option<u32>,
+    start_episode: Option<u32>,
+    order: EpisodeOrder,
+) -> Result<DvdMetadata> {
+    debug!("fetch_dvd_metadata called with volume_name: {:?}, season: {:?}, start_episode: {:?}, order: {:?}", volume_name, season, start_episode, order);
+    
+    let Some(name) = volume_name else {
+        warn!("⚠️  No volume name provided, cannot search {}", provider.name());
         return create_dummy_dvd_metadata(volume_name);
+    };
+    let candidates = search_candidates(provider, name).await;
+    fetch_metadata_from_candidates(provider, &candidates, name, season, start_episode, order).await
+}
+
+/// Search shows and movies for every variation of a volume name, best match first
+pub async fn search_candidates(provider: &dyn MetadataProvider, volume_name: &str) -> Vec<MetadataCandidate> {
+    let cleaned_name = clean_volume_name(volume_name);
+    info!("📡 Raw volume name: '{}', cleaned: '{}'", volume_name, cleaned_name);
+    
+    // Generate search variations to try
+    let search_terms = generate_search_variations(&cleaned_name);
+    info!("🔍 Will try search terms: {:?}", search_terms);
+    let name_words = cleaned_name.split_whitespace().count().max(1) as i32;
+    
+    let mut candidates: Vec<MetadataCandidate> = Vec::new();
+    for search_term in &search_terms {
+        // Hits for a shortened name ("Star" for "Star Trek") count for less
+        let term_words = search_term.split_whitespace().count() as i32;
+        for media_type in [MediaType::TVShow, MediaType::Movie] {
+            info!("🔎 Trying {:?} search on {}: '{}'", media_type, provider.name(), search_term);
+            let results = match media_type {
+                MediaType::TVShow => provider.search_show(search_term).await,
+                _ => provider.search_movie(search_term).await,
+            };
+            let results = match results {
+                Ok(results) => results,
+                Err(e) => {
+                    debug!("{:?} search for '{}' failed: {}", media_type, search_term, e);
+                    continue;
+                }
+            };
+            
+            for result in results {
+                let score = match_score(&result, search_term) * term_words.min(name_words) / name_words;
+                match candidates.iter_mut().find(|c| c.media_type == media_type && c.id == result.id) {
+                    Some(existing) => existing.score = existing.score.max(score),
+                    None => candidates.push(MetadataCandidate {
+                        media_type: media_type.clone(),
+                        id: result.id,
+                        title: result.title,
+                        year: result.year,
+                        overview: result.overview,
+                        poster_url: result.poster_url,
+                        score,
+                    }),
+                }
+            }
+        }
     }
     
-    info!("✅ TMDB API key configured, proceeding with metadata lookup");
-    
-    let client = reqwest::Client::builder()
-        .user_agent("Ripley/0.1.0")
-        .timeout(Duration::from_secs(10))
-        .build()?;
-    
-    // If we have a volume name, search for it
-    if let Some(name) = volume_name {
-        let cleaned_name = clean_volume_name(name);
-        info!("📡 Raw volume name: '{}', cleaned: '{}'", name, cleaned_name);
-        
-        // Generate search variations to try
-        let search_terms = generate_search_variations(&cleaned_name);
-        info!("🔍 Will try search terms: {:?}", search_terms);
-        
-        // Try each search variation
-        for search_term in &search_terms {
-            info!("🔎 Trying TV search: '{}'", search_term);
-            if let Ok(metadata) = search_tv_show_with_episode(&client, search_term, start_episode).await {
-                info!("✅ Found TV show: {}", metadata.title);
-                return Ok(metadata);
-            }
-            
-            info!("🔎 Trying movie search: '{}'", search_term);
-            if let Ok(metadata) = search_movie(&client, search_term).await {
-                info!("✅ Found movie: {}", metadata.title);
+    // Shows win ties, as disc sets are mostly TV
+    candidates.sort_by_key(|c| (std::cmp::Reverse(c.score), c.media_type != MediaType::TVShow));
+    candidates.truncate(MAX_CANDIDATES);
+    if candidates.is_empty() {
+        warn!("⚠️  No results found on {} for any variation of '{}'", provider.name(), volume_name);
+    }
+    candidates
+}
+
+/// Whether the user should confirm the match: several candidates and none clearly ahead
+pub fn is_ambiguous(candidates: &[MetadataCandidate]) -> bool {
+    match candidates {
+        [first, second, ..] => first.score - second.score < CLEAR_LEAD,
+        _ => false,
+    }
+}
+
+/// Metadata for the first candidate whose details can be fetched, or placeholder metadata named `title`
+pub async fn fetch_metadata_from_candidates(
+    provider: &dyn MetadataProvider,
+    candidates: &[MetadataCandidate],
+    title: &str,
+    season: Option<u32>,
+    start_episode: Option<u32>,
+    order: EpisodeOrder,
+) -> Result<DvdMetadata> {
+    for candidate in candidates {
+        match fetch_candidate_metadata(provider, candidate, season, start_episode, order).await {
+            Ok(metadata) => {
+                info!("✅ Found {:?}: {}", metadata.media_type, metadata.title);
                 return Ok(metadata);
             }
+            Err(e) => debug!("Skipping {} ({}): {}", candidate.title, candidate.id, e),
         }
-        
-        warn!("⚠️  No results found on TMDB for any variation of '{}'", name);
-    } else {
-        warn!("⚠️  No volume name provided, cannot search TMDB");
     }
     
     // If no metadata found, return dummy
-    create_dummy_dvd_metadata(volume_name)
+    create_dummy_dvd_metadata(Some(title))
 }
 
-/// Search TMDB for a TV show with optional starting episode hint
-async fn search_tv_show_with_episode(client: &reqwest::Client, query: &str, start_episode: Option<u32>) -> Result<DvdMetadata> {
-    let url = format!(
-        "{}/search/tv?api_key={}&query={}",
-        TMDB_API_BASE,
-        TMDB_API_KEY,
-        urlencoding::encode(query)
-    );
-    
-    debug!("TMDB TV search URL: {}", url.replace(TMDB_API_KEY, "***"));
+/// Score how well a search result matches `query` (higher is better)
+///
+/// For "Fosters", prefer "Foster's Home..." over "The Fosters" since the query is at the start.
+fn match_score(result: &SearchResult, query: &str) -> i32 {
+    let query_lower = query.to_lowercase().replace("'", "");
+    let name_lower = result.title.to_lowercase().replace("'", "");
     
-    let response = client.get(&url).send().await?;
+    let mut score = 0;
     
-    if !response.status().is_success() {
-        warn!("TMDB API returned error: {}", response.status());
-        return Err(anyhow!("TMDB search failed: {}", response.status()));
+    // Exact match gets highest score
+    if name_lower == query_lower {
+        score += 1000;
     }
     
-    let data: serde_json::Value = response.json().await?;
-    debug!("TMDB TV search response: {}", serde_json::to_string_pretty(&data).unwrap_or_default());
-    
-    let results = data["results"].as_array()
-        .ok_or_else(|| anyhow!("No results"))?;
-    
-    if results.is_empty() {
-        return Err(anyhow!("No TV shows found"));
+    // Contains exact query gets high score
+    if name_lower.contains(&query_lower) {
+        score += 500;
     }
     
-    // Find the best match - prefer titles that contain the search term more prominently
-    // For "Fosters", prefer "Foster's Home..." over "The Fosters" since the query is at the start
-    let query_lower = query.to_lowercase().replace("'", "");
-    let best_match = results.iter()
-        .max_by_key(|show| {
-            if let Some(name) = show["name"].as_str() {
-                let name_lower = name.to_lowercase().replace("'", "");
-                
-                // Score based on match quality (higher is better)
-                let mut score = 0;
-                
-                // Exact match gets highest score
-                if name_lower == query_lower {
-                    score += 1000;
-                }
-                
-                // Contains exact query gets high score
-                if name_lower.contains(&query_lower) {
-                    score += 500;
-                }
-                
-                // All query words present
-                if query_lower.split_whitespace().all(|word| name_lower.contains(word)) {
-                    score += 100;
-                }
-                
-                // Prefer matches where query appears earlier in title
-                if let Some(pos) = name_lower.find(&query_lower) {
-                    score += (100 - pos.min(99)) as i32;
-                }
-                
-                // Use popularity as tiebreaker
-                if let Some(popularity) = show["popularity"].as_f64() {
-                    score += (popularity as i32).min(10);
-                }
-                
-                score
-            } else {
-                0
-            }
-        })
-        .or_else(|| results.first()); // Fall back to first result if scoring fails
+    // All query words present
+    if query_lower.split_whitespace().all(|word| name_lower.contains(word)) {
+        score += 100;
+    }
     
-    let show = best_match.ok_or_else(|| anyhow!("No show found"))?;
-    let show_id = show["id"].as_i64()
-        .ok_or_else(|| anyhow!("No show ID"))?;
+    // Prefer matches where query appears earlier in title
+    if let Some(pos) = name_lower.find(&query_lower) {
+        score += (100 - pos.min(99)) as i32;
+    }
     
-    let title = show["name"].as_str()
-        .unwrap_or("Unknown Show")
-        .to_string();
+    // Use popularity as tiebreaker
+    if let Some(popularity) = result.popularity {
+        score += (popularity as i32).min(10);
+    }
     
-    let year = show["first_air_date"].as_str()
-        .and_then(|d| d.split('-').next())
-        .map(String::from);
+    score
+}
+
+/// Details for one candidate: its episodes (within the season hints) for a show
+async fn fetch_candidate_metadata(
+    provider: &dyn MetadataProvider,
+    candidate: &MetadataCandidate,
+    season: Option<u32>,
+    start_episode: Option<u32>,
+    order: EpisodeOrder,
+) -> Result<DvdMetadata> {
+    let title = candidate.title.clone();
+    let year = candidate.year.clone();
+    if candidate.media_type != MediaType::TVShow {
+        info!("Found movie: {} ({})", title, year.as_deref().unwrap_or("unknown year"));
+        return Ok(DvdMetadata {
+            title,
+            year,
+            media_type: MediaType::Movie,
+            episodes: Vec::new(), // Movies don't have episodes
+        });
+    }
     
-    info!("Found TV show: {} (ID: {})", title, show_id);
+    info!("Found TV show: {} (ID: {})", title, candidate.id);
     
-    // Determine which seasons to fetch based on starting episode
-    let seasons_to_fetch = if let Some(start_ep) = start_episode {
-        // Estimate which season this episode might be in
-        // Assume ~13-26 episodes per season
-        let estimated_season = ((start_ep - 1) / 20) + 1;
-        info!("Starting episode {} hints at season {}, fetching seasons {}-{}", 
-              start_ep, estimated_season, estimated_season.saturating_sub(1).max(1), estimated_season + 1);
-        vec![
-            estimated_season.saturating_sub(1).max(1), 
-            estimated_season,
-            estimated_season + 1
-        ]
+    // Determine which seasons to fetch: the known season, or all of them so matching can pick across seasons
+    let seasons_to_fetch = if let Some(season) = season {
+        info!("Disc is assigned to season {}", season);
+        vec![season]
     } else {
-        // Default: fetch first 3 seasons to cover most disc sets
-        vec![1, 2, 3]
+        all_seasons(provider, &candidate.id, start_episode).await
     };
     
-    // Fetch episodes from multiple seasons
+    // Other orders cover the whole show, so keep just the seasons we would have fetched
     let mut all_episodes = Vec::new();
+    if order != EpisodeOrder::Aired {
+        match provider.list_episodes_in_order(&candidate.id, order).await {
+            Ok(eps) if !eps.is_empty() => {
+                all_episodes = eps.into_iter()
+                    .filter(|ep| seasons_to_fetch.contains(&ep.season))
+                    .collect();
+                info!("✅ Using {:?} order: {} episodes", order, all_episodes.len());
+            }
+            Ok(_) => warn!("No {:?} order on {} for {}, using aired order", order, provider.name(), title),
+            Err(e) => warn!("Could not fetch {:?} order for {}: {}, using aired order", order, title, e),
+        }
+    }
+    
+    // Fetch episodes from multiple seasons
+    let seasons_to_fetch = if all_episodes.is_empty() { seasons_to_fetch } else { Vec::new() };
     for season in seasons_to_fetch {
-        match fetch_tv_episodes(client, show_id, season).await {
+        match provider.list_episodes(&candidate.id, season).await {
             Ok(mut eps) => {
                 info!("✅ Fetched {} episodes from season {}", eps.len(), season);
                 all_episodes.append(&mut eps);
@@ -463,7 +577,7 @@ async fn search_tv_show_with_episode(client: &reqwest::Client, query: &str, star
             .filter(|ep| {
                 // Calculate absolute episode number (for multi-season)
                 let abs_episode = ep.episode;
-                abs_episode >= start_ep || ep.season > 1
+                abs_episode >= start_ep || (season.is_none() && ep.season > 1)
             })
             .collect()
     } else {
@@ -480,65 +594,51 @@ async fn search_tv_show_with_episode(client: &reqwest::Client, query: &str, star
     })
 }
 
-/// Fetch TV show episodes for a season
-async fn fetch_tv_episodes(client: &reqwest::Client, show_id: i64, season: u32) -> Result<Vec<Episode>> {
-    let url = format!(
-        "{}/tv/{}/season/{}?api_key={}",
-        TMDB_API_BASE,
-        show_id,
-        season,
-        TMDB_API_KEY
-    );
-    
-    let response = client.get(&url).send().await?;
-    
-    if !response.status().is_success() {
-        return Ok(Vec::new());
+/// Every season of a show (up to `MAX_UNKNOWN_SEASONS`), for a disc whose season isn't known
+///
+/// When the provider can't list seasons, the starting episode hints at a few seasons to try.
+async fn all_seasons(provider: &dyn MetadataProvider, show_id: &str, start_episode: Option<u32>) -> Vec<u32> {
+    match provider.list_seasons(show_id).await {
+        Ok(mut seasons) if !seasons.is_empty() => {
+            if seasons.len() > MAX_UNKNOWN_SEASONS {
+                warn!("Show has {} seasons, only matching against the first {}", seasons.len(), MAX_UNKNOWN_SEASONS);
+                seasons.truncate(MAX_UNKNOWN_SEASONS);
+            }
+            info!("Season unknown, fetching seasons {:?}", seasons);
+            return seasons;
+        }
+        Ok(_) => debug!("{} lists no seasons for show {}", provider.name(), show_id),
+        Err(e) => debug!("Could not list seasons of show {}: {}", show_id, e),
     }
     
-    let data: serde_json::Value = response.json().await?;
-    
-    let episode_list = data["episodes"].as_array()
-        .ok_or_else(|| anyhow!("No episodes"))?;
-    
-    let mut episodes = Vec::new();
-    
-    for (idx, ep) in episode_list.iter().enumerate() {
-        let ep_num = ep["episode_number"].as_u64()
-            .unwrap_or((idx + 1) as u64) as u32;
-        
-        let title = ep["name"].as_str()
-            .unwrap_or("Unknown Episode")
-            .to_string();
-        
-        let runtime_minutes = ep["runtime"].as_u64().map(|r| r as u32);
-        
-        let overview = ep["overview"].as_str()
-            .map(|s| s.to_string())
-            .filter(|s| !s.is_empty());
-        
-        episodes.push(Episode {
-            season,
-            episode: ep_num,
-            title,
-            title_index: idx as u32, // Will be updated by duration matching
-            runtime_minutes,
-            overview,
-        });
+    if let Some(start_ep) = start_episode {
+        // Estimate which season this episode might be in
+        // Assume ~13-26 episodes per season
+        let estimated_season = (start_ep.saturating_sub(1) / 20) + 1;
+        info!("Starting episode {} hints at season {}, fetching seasons {}-{}", 
+              start_ep, estimated_season, estimated_season.saturating_sub(1).max(1), estimated_season + 1);
+        vec![
+            estimated_season.saturating_sub(1).max(1), 
+            estimated_season,
+            estimated_season + 1
+        ]
+    } else {
+        // Default: fetch first 3 seasons to cover most disc sets
+        vec![1, 2, 3]
     }
-    
-    info!("Found {} episodes for season {}", episodes.len(), season);
-    
-    Ok(episodes)
 }
 
 /// Match disc title durations to episodes by runtime
-/// Returns updated episodes with correct title_index values
-#[allow(unused_mut)]
+/// Returns each matched title's assignment, in season and episode order
+///
+/// Only titles inside the selection's duration window are considered, within its runtime tolerance.
+/// Titles are assigned globally (see episode_assignment.rs) so one early bad pick can't push later titles
+/// onto the wrong episodes.
 pub fn match_episodes_by_duration(
-    mut episodes: Vec<Episode>,
+    episodes: Vec<Episode>,
     title_durations: &[(usize, String)], // (title_index, "HH:MM:SS")
-) -> Vec<Episode> {
+    selection: &crate::title_selection::TitleSelection,
+) -> Vec<crate::episode_assignment::Assignment> {
     info!("Matching {} episodes to {} disc titles by duration", episodes.len(), title_durations.len());
     
     // Convert title durations to minutes
@@ -550,51 +650,37 @@ pub fn match_episodes_by_duration(
         }
     }
     
-    // Filter titles that are likely episodes (typically 18-50 minutes for TV)
-    let episode_titles: Vec<(usize, u32)> = title_minutes.into_iter()
-        .filter(|(_, min)| *min >= 18 && *min <= 50)
+    // Filter titles that are likely episodes (explicitly picked titles are always considered)
+    let episode_titles: Vec<crate::episode_assignment::TitleEvidence> = title_minutes.into_iter()
+        .filter(|(idx, min)| match selection.titles {
+            Some(ref picked) => picked.contains(&(*idx as u32)),
+            None => selection.in_window(*min),
+        })
+        .map(|(idx, min)| crate::episode_assignment::TitleEvidence {
+            title_index: idx as u32,
+            minutes: Some(min),
+            transcript_match: None,
+        })
         .collect();
     
-    info!("Found {} titles that look like TV episodes (18-50 min)", episode_titles.len());
-    
-    // Match TITLES to EPISODES (not episodes to titles)
-    // For each disc title, find the best matching episode by runtime
-    let mut matched_episodes = Vec::new();
-    let mut used_episodes: std::collections::HashSet<u32> = std::collections::HashSet::new();
-    
-    for (title_idx, title_min) in &episode_titles {
-        // Find best matching episode by runtime (within 5 minutes tolerance)
-        let best_match = episodes.iter()
-            .filter(|ep| !used_episodes.contains(&ep.episode))
-            .filter(|ep| ep.runtime_minutes.is_some())
-            .min_by_key(|ep| {
-                let ep_runtime = ep.runtime_minutes.unwrap();
-                (*title_min as i32 - ep_runtime as i32).abs()
-            })
-            .filter(|ep| {
-                let ep_runtime = ep.runtime_minutes.unwrap();
-                let diff = (*title_min as i32 - ep_runtime as i32).abs();
-                diff <= 5 // Within 5 minutes tolerance
-            });
-        
-        if let Some(episode) = best_match {
-            let ep_runtime = episode.runtime_minutes.unwrap();
-            info!("Matched Title {} ({} min) to S{}E{:02} '{}' ({} min)", 
-                  title_idx, title_min, episode.season, episode.episode, episode.title, ep_runtime);
-            
-            let mut matched_ep = episode.clone();
-            matched_ep.title_index = *title_idx as u32;
-            matched_episodes.push(matched_ep);
-            used_episodes.insert(episode.episode);
-        } else {
-            warn!("Could not match Title {} ({} min) to any episode", title_idx, title_min);
-        }
+    info!("Found {} titles that look like TV episodes", episode_titles.len());
+    
+    let mut assignments = crate::episode_assignment::assign_episodes(&episode_titles, &episodes, selection.tolerance_minutes());
+    for title in &episode_titles {
+        let Some(assignment) = assignments.iter().find(|a| a.title_index == title.title_index) else {
+            warn!("Could not match Title {} ({} min) to any episode", title.title_index, title.minutes.unwrap_or(0));
+            continue;
+        };
+        let ep = &assignment.episode_match;
+        info!("Matched Title {} ({} min) to S{}E{:02} '{}' (confidence {:.0}%{})", 
+              title.title_index, title.minutes.unwrap_or(0), ep.season, ep.episode, ep.title, ep.confidence,
+              if assignment.needs_review { ", needs review" } else { "" });
     }
     
-    // Sort by episode number to maintain order
-    matched_episodes.sort_by_key(|ep| ep.episode);
+    // Sort by season and episode number to maintain order
+    assignments.sort_by_key(|a| (a.episode_match.season, a.episode_match.episode));
     
-    matched_episodes
+    assignments
 }
 
 /// Parse duration string "H:MM:SS" or "HH:MM:SS" to minutes
@@ -611,50 +697,6 @@ fn parse_duration_to_minutes(duration: &str) -> Option<u32> {
     Some(hours * 60 + minutes + if seconds >= 30 { 1 } else { 0 })
 }
 
-/// Search TMDB for a movie
-async fn search_movie(client: &reqwest::Client, query: &str) -> Result<DvdMetadata> {
-    let url = format!(
-        "{}/search/movie?api_key={}&query={}",
-        TMDB_API_BASE,
-        TMDB_API_KEY,
-        urlencoding::encode(query)
-    );
-    
-    let response = client.get(&url).send().await?;
-    
-    if !response.status().is_success() {
-        return Err(anyhow!("TMDB search failed: {}", response.status()));
-    }
-    
-    let data: serde_json::Value = response.json().await?;
-    
-    let results = data["results"].as_array()
-        .ok_or_else(|| anyhow!("No results"))?;
-    
-    if results.is_empty() {
-        return Err(anyhow!("No movies found"));
-    }
-    
-    let movie = &results[0];
-    
-    let title = movie["title"].as_str()
-        .unwrap_or("Unknown Movie")
-        .to_string();
-    
-    let year = movie["release_date"].as_str()
-        .and_then(|d| d.split('-').next())
-        .map(String::from);
-    
-    info!("Found movie: {} ({})", title, year.as_deref().unwrap_or("unknown year"));
-    
-    Ok(DvdMetadata {
-        title,
-        year,
-        media_type: MediaType::Movie,
-        episodes: Vec::new(), // Movies don't have episodes
-    })
-}
-
 /// Create dummy metadata when lookup fails
 fn create_dummy_dvd_metadata(volume_name: Option<&str>) -> Result<DvdMetadata> {
     Ok(DvdMetadata {
@@ -698,4 +740,143 @@ mod tests {
         assert_eq!(MediaType::TVShow, MediaType::TVShow);
         assert_ne!(MediaType::Movie, MediaType::TVShow);
     }
+
+    /// Answers searches from fixed lists: `shows` and `movies` are (query, title, year) triples, and every show
+    /// has `episodes`
+    struct StaticProvider {
+        shows: Vec<(&'static str, &'static str, &'static str)>,
+        movies: Vec<(&'static str, &'static str, &'static str)>,
+        episodes: Vec<Episode>,
+    }
+
+    fn results(entries: &[(&str, &str, &str)], query: &str) -> Vec<SearchResult> {
+        entries.iter()
+            .filter(|(q, _, _)| q.eq_ignore_ascii_case(query))
+            .map(|(_, title, year)| SearchResult {
+                id: format!("{}-{}", title, year),
+                title: title.to_string(),
+                year: Some(year.to_string()),
+                overview: None,
+                poster_url: None,
+                popularity: None,
+            })
+            .collect()
+    }
+
+    impl MetadataProvider for StaticProvider {
+        fn name(&self) -> &'static str {
+            "static"
+        }
+
+        fn search_show<'a>(&'a self, query: &'a str) -> crate::metadata_provider::MetadataFuture<'a, Vec<SearchResult>> {
+            Box::pin(async move { Ok(results(&self.shows, query)) })
+        }
+
+        fn search_movie<'a>(&'a self, query: &'a str) -> crate::metadata_provider::MetadataFuture<'a, Vec<SearchResult>> {
+            Box::pin(async move { Ok(results(&self.movies, query)) })
+        }
+
+        fn list_seasons<'a>(&'a self, _show_id: &'a str) -> crate::metadata_provider::MetadataFuture<'a, Vec<u32>> {
+            let mut seasons: Vec<u32> = self.episodes.iter().map(|ep| ep.season).collect();
+            seasons.dedup();
+            Box::pin(async move { Ok(seasons) })
+        }
+
+        fn list_episodes<'a>(&'a self, _show_id: &'a str, season: u32) -> crate::metadata_provider::MetadataFuture<'a, Vec<Episode>> {
+            let episodes = self.episodes.iter().filter(|ep| ep.season == season).cloned().collect();
+            Box::pin(async move { Ok(episodes) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_candidates_ranking() {
+        let provider = StaticProvider {
+            shows: vec![
+                ("Foster's One", "The Fosters", "2013"),
+                ("Foster's", "The Fosters", "2013"),
+                ("Foster's", "Foster's Home for Imaginary Friends", "2004"),
+                ("Battlestar Galactica", "Battlestar Galactica", "2004"),
+                ("Battlestar Galactica", "Battlestar Galactica", "1978"),
+                ("Battlestar", "Battlestar", "1999"),
+            ],
+            movies: vec![("Battlestar Galactica", "Battlestar Galactica: The Plan", "2009")],
+            episodes: Vec::new(),
+        };
+
+        // Found again by a shorter search term: the best score counts, and the candidate is listed once
+        let fosters = search_candidates(&provider, "FOSTERS_ONE").await;
+        assert_eq!(fosters.iter().map(|c| c.title.as_str()).collect::<Vec<_>>(), vec!["Foster's Home for Imaginary Friends", "The Fosters"]);
+        assert!(is_ambiguous(&fosters));
+
+        // Two exact matches: the user has to pick; the one-word "Battlestar" match ranks below both
+        let bsg = search_candidates(&provider, "BATTLESTAR_GALACTICA_S1").await;
+        assert_eq!(bsg.len(), 4);
+        assert!(bsg[..2].iter().all(|c| c.title == "Battlestar Galactica" && c.media_type == MediaType::TVShow));
+        assert!(is_ambiguous(&bsg));
+        assert!(bsg[2].score > bsg[3].score);
+
+        // A single exact match is clear
+        assert!(!is_ambiguous(&bsg[1..]));
+        assert!(!is_ambiguous(&[]));
+
+        // "None of these" leaves placeholder metadata named after the title
+        let none = fetch_metadata_from_candidates(&provider, &[], "Battlestar Galactica", None, None, EpisodeOrder::Aired).await.unwrap();
+        assert_eq!((none.title.as_str(), none.media_type), ("Battlestar Galactica", MediaType::Unknown));
+    }
+
+    #[test]
+    fn test_extract_season_from_volume() {
+        assert_eq!(extract_season_from_volume("SHOW_SEASON_3_DISC_1"), Some(3));
+        assert_eq!(extract_season_from_volume("SHOW_S02_D1"), Some(2));
+        assert_eq!(extract_season_from_volume("SHOW_DISC_1"), None);
+        // Complete-series discs span seasons
+        assert_eq!(extract_season_from_volume("SHOW SEASONS 1-3"), None);
+        assert_eq!(extract_season_from_volume("SHOW_S1_TO_S2"), None);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_season_matches_across_seasons() {
+        let episode = |season: u32, episode: u32, runtime: u32| Episode {
+            season,
+            episode,
+            title: format!("Episode {}x{}", season, episode),
+            title_index: 0,
+            runtime_minutes: Some(runtime),
+            overview: None,
+        };
+        let provider = StaticProvider {
+            shows: vec![("Show", "Show", "2010")],
+            movies: Vec::new(),
+            // Half-hour first season, hour-long second season
+            episodes: vec![episode(1, 1, 22), episode(1, 2, 22), episode(2, 1, 44), episode(2, 2, 44)],
+        };
+
+        // No season on the label: both seasons are fetched
+        let metadata = fetch_dvd_metadata_with_episode(&provider, Some("SHOW_DISC_1"), None, None, EpisodeOrder::Aired).await.unwrap();
+        assert_eq!(metadata.episodes.len(), 4);
+
+        // Runtimes pick the season
+        let titles = vec![(3, "0:44:10".to_string()), (4, "0:43:50".to_string())];
+        let matched = match_episodes_by_duration(metadata.episodes, &titles, &crate::title_selection::TitleSelection::default());
+        assert_eq!(matched.iter().map(|a| (a.title_index, a.episode_match.season, a.episode_match.episode)).collect::<Vec<_>>(), vec![(3, 2, 1), (4, 2, 2)]);
+
+        // A known season is the only one fetched
+        let metadata = fetch_dvd_metadata_with_episode(&provider, Some("SHOW_DISC_1"), Some(1), None, EpisodeOrder::Aired).await.unwrap();
+        assert!(metadata.episodes.iter().all(|ep| ep.season == 1));
+
+        // Without a season list, the starting episode hints at the seasons; episode 0 doesn't underflow
+        let unlisted = StaticProvider { shows: Vec::new(), movies: Vec::new(), episodes: Vec::new() };
+        assert_eq!(all_seasons(&unlisted, "show", Some(45)).await, vec![2, 3, 4]);
+        assert_eq!(all_seasons(&unlisted, "show", Some(0)).await, vec![1, 1, 2]);
+    }
+
+    #[test]
+    fn test_episode_order_parse() {
+        assert_eq!(EpisodeOrder::parse("Airdate"), Some(EpisodeOrder::Aired));
+        assert_eq!(EpisodeOrder::parse("DVD"), Some(EpisodeOrder::Dvd));
+        assert_eq!(EpisodeOrder::parse("story-arc"), Some(EpisodeOrder::StoryArc));
+        assert_eq!(EpisodeOrder::parse("random"), None);
+        assert_eq!(EpisodeOrder::Dvd.filebot_order(), Some("DVD"));
+        assert_eq!(EpisodeOrder::StoryArc.filebot_order(), None);
+    }
 }
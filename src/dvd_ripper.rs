@@ -1,200 +1,196 @@
 use anyhow::{anyhow, Result};
 use std::path::Path;
-use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
-use tracing::{debug, info, warn};
+use tracing::{info, warn};
 
+use crate::disc_scan::{DiscScan, TitleInfo};
 use crate::dvd_metadata::{DvdMetadata, MediaType};
+use crate::episode_assignment::Assignment;
+use crate::rip_backend::{RipBackend, RipEvent, RipState};
 use crate::ripper::RipProgress;
+use crate::title_selection::TitleSelection;
 
-/// Parse duration string "H:MM:SS" or "HH:MM:SS" to minutes
-fn parse_duration_to_minutes(duration: &str) -> Option<u32> {
-    let parts: Vec<&str> = duration.split(':').collect();
-    if parts.len() != 3 {
-        return None;
-    }
-    
-    let hours: u32 = parts[0].parse().ok()?;
-    let minutes: u32 = parts[1].parse().ok()?;
-    let seconds: u32 = parts[2].parse().ok()?;
-    
-    Some(hours * 60 + minutes + if seconds >= 30 { 1 } else { 0 })
-}
+/// Future returned by the per-title episode callback
+pub type EpisodeFuture<'a> = std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>>;
 
-/// Rip a DVD using makemkvcon
+/// Rip a DVD title by title using the given backend
+///
+/// Pass the result of an earlier `backend.scan()` as `scan` to avoid scanning the disc twice.
+/// Titles are picked with `selection`; skipped titles are reported through `log_callback`.
+/// Titles in `completed_titles` were ripped by an interrupted earlier run and are skipped.
+/// With movie metadata the titles come from `movie::plan_movie` instead of the episode window.
+/// Each ripped file is handed to `episode_callback` with the episode its title was matched to by duration.
+/// Returns those matches (empty without TV metadata).
+#[allow(clippy::too_many_arguments)]
 pub async fn rip_dvd<F, L, E>(
+    backend: &dyn RipBackend,
     device: &str,
     output_dir: &Path,
+    scan: Option<DiscScan>,
+    selection: &TitleSelection,
+    completed_titles: &[u32],
     metadata: Option<&DvdMetadata>,
     mut progress_callback: F,
     mut log_callback: L,
     mut episode_callback: E,
-) -> Result<()>
+) -> Result<Vec<Assignment>>
 where
     F: FnMut(RipProgress) + Send,
     L: FnMut(String) + Send,
-    E: for<'a> FnMut(&'a Path, u32) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> + Send,
+    E: for<'a> FnMut(&'a Path, u32, Option<&'a Assignment>) -> EpisodeFuture<'a> + Send,
 {
-    info!("Starting DVD rip from {}", device);
+    info!("Starting DVD rip from {} ({} backend)", device, backend.name());
     log_callback("Starting DVD rip...".to_string());
 
-    // Check if makemkvcon is installed
-    let check = Command::new("which")
-        .arg("makemkvcon")
-        .output()
-        .await?;
-    
-    if !check.status.success() {
-        return Err(anyhow!("makemkvcon not found. Install MakeMKV from https://www.makemkv.com/"));
-    }
+    let scan = prepare_disc(backend, device, output_dir, scan, "DVD", &mut progress_callback, &mut log_callback).await?;
+    let titles = scan.titles;
+    let title_count = titles.len();
 
-    // Setup MakeMKV settings to skip subtitles
-    setup_makemkv_settings().await?;
-
-    // Create output directory
-    if let Err(e) = tokio::fs::create_dir_all(output_dir).await {
-        tracing::error!("Failed to create output directory {}: {}", output_dir.display(), e);
-        return Err(anyhow!("Failed to create output directory: {}", e));
+    if title_count == 0 {
+        return Err(anyhow!("No titles found on DVD"));
     }
 
-    info!("Output directory: {}", output_dir.display());
-    log_callback(format!("Output: {}", output_dir.display()));
+    info!("Found {} titles on DVD", title_count);
+    log_callback(format!("Found {} titles", title_count));
 
-    // First, scan the disc to get info
-    log_callback("Scanning DVD...".to_string());
-    info!("Scanning DVD in {}", device);
-    
-    progress_callback(RipProgress {
-        current_track: 0,
-        total_tracks: 1,
-        track_name: "Scanning...".to_string(),
-        percentage: 0.0,
-        status: crate::ripper::RipStatus::FetchingMetadata,
-        speed_mbps: None,
-        bytes_processed: None,
-    });
+    for t in &titles {
+        info!("Title {}: {}", t.index, t.duration_string());
+    }
 
-    let mut scan_child = Command::new("makemkvcon")
-        .arg("-r")
-        .arg("info")
-        .arg(format!("dev:{}", device))
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| anyhow!("Failed to start makemkvcon: {}", e))?;
-
-    let stdout = scan_child.stdout.take()
-        .ok_or_else(|| anyhow!("Failed to capture stdout"))?;
-    let stderr = scan_child.stderr.take()
-        .ok_or_else(|| anyhow!("Failed to capture stderr"))?;
-    
-    let mut stdout_reader = BufReader::new(stdout).lines();
-    let mut stderr_reader = BufReader::new(stderr).lines();
-    let mut title_count = 0;
-    let mut title_durations: Vec<(usize, String)> = Vec::new(); // (title_index, duration)
-
-    // Parse scan output
-    loop {
-        tokio::select! {
-            result = stdout_reader.next_line() => {
-                match result {
-                    Ok(Some(line)) => {
-                        debug!("makemkvcon scan: {}", line);
-                        
-                        // Count titles
-                        if line.starts_with("TCOUNT:") {
-                            if let Some(count_str) = line.strip_prefix("TCOUNT:") {
-                                title_count = count_str.trim().parse().unwrap_or(0);
-                                info!("Found {} titles on DVD", title_count);
-                                log_callback(format!("Found {} titles", title_count));
-                            }
-                        }
-                        
-                        // Parse title durations: TINFO:0,9,0,"2:59:37"
-                        if line.starts_with("TINFO:") && line.contains(",9,0,") {
-                            let parts: Vec<&str> = line.split(',').collect();
-                            if parts.len() >= 4 {
-                                if let Some(title_idx) = parts[0].strip_prefix("TINFO:").and_then(|s| s.parse::<usize>().ok()) {
-                                    let duration = parts[3].trim_matches('"').to_string();
-                                    title_durations.push((title_idx, duration.clone()));
-                                    info!("Title {}: {}", title_idx, duration);
-                                }
-                            }
-                        }
-                    }
-                    Ok(None) => break,
-                    Err(e) => {
-                        debug!("Error reading scan stdout: {}", e);
-                        break;
-                    }
-                }
-            }
-            result = stderr_reader.next_line() => {
-                match result {
-                    Ok(Some(line)) => {
-                        tracing::warn!("makemkvcon: {}", line);
-                        log_callback(format!("WARN: {}", line));
-                    }
-                    Ok(None) => {}
-                    Err(e) => debug!("Error reading scan stderr: {}", e),
-                }
-            }
+    // Movies rip the main feature, its other cuts and extras instead of episode-length titles
+    let movie_plan = match metadata {
+        Some(meta) if meta.media_type == MediaType::Movie => {
+            let plan = crate::movie::plan_movie(&titles, selection)
+                .ok_or_else(|| anyhow!("No main feature found on DVD"))?;
+            plan.describe(&titles).into_iter().for_each(&mut log_callback);
+            Some(plan)
         }
-    }
+        _ => None,
+    };
 
-    let scan_status = scan_child.wait().await?;
-    if !scan_status.success() {
-        return Err(anyhow!("DVD scan failed with status: {}", scan_status));
+    // Drop play-all and duplicate titles unless the user picked titles explicitly (movie plans already did)
+    let redundant = if selection.titles.is_some() || movie_plan.is_some() {
+        Vec::new()
+    } else {
+        find_redundant_titles(&titles)
+    };
+    for (index, reason) in &redundant {
+        log_callback(format!("⏭️  Skipping title {} - {}", index, reason));
+        info!("Skipping title {} - {}", index, reason);
     }
+    let titles: Vec<TitleInfo> = titles.into_iter()
+        .filter(|t| !redundant.iter().any(|(index, _)| *index == t.index))
+        .collect();
 
-    if title_count == 0 {
-        return Err(anyhow!("No titles found on DVD"));
+    // Pick titles according to the profile/show rules
+    let picked = match movie_plan {
+        Some(ref plan) => plan.select(&titles),
+        None => selection.select(&titles),
+    };
+    for (title, reason) in &picked.skipped {
+        log_callback(format!("⏭️  Skipping title {} ({} min) - {}", title.index, title.duration_minutes(), reason));
+        info!("Skipping title {} ({} min) - {}", title.index, title.duration_minutes(), reason);
     }
+    for title in &picked.selected {
+        info!("Will rip title {} ({} min)", title.index, title.duration_minutes());
+    }
+    let titles_to_rip = picked.selected;
+
+    let title_durations: Vec<(usize, String)> = titles_to_rip.iter()
+        .map(|t| (t.index as usize, t.duration_string()))
+        .collect();
     
     // Match episodes to disc titles by duration if we have metadata
-    let _metadata = if let Some(meta) = metadata {
-        if meta.media_type == MediaType::TVShow && !meta.episodes.is_empty() {
+    let matched_episodes = match metadata {
+        Some(meta) if meta.media_type == MediaType::TVShow && !meta.episodes.is_empty() => {
             log_callback("Matching episodes to disc titles by duration...".to_string());
-            let matched_episodes = crate::dvd_metadata::match_episodes_by_duration(
+            crate::dvd_metadata::match_episodes_by_duration(
                 meta.episodes.clone(),
-                &title_durations
-            );
-            let mut updated_meta = meta.clone();
-            updated_meta.episodes = matched_episodes;
-            Some(updated_meta)
-        } else {
-            Some(meta.clone())
+                &title_durations,
+                selection,
+            )
         }
-    } else {
-        None
+        _ => Vec::new(),
     };
 
-    // Filter titles to rip based on duration (skip "Play All" compilations)
-    let titles_to_rip: Vec<u32> = title_durations.iter()
-        .filter_map(|(idx, duration)| {
-            if let Some(minutes) = parse_duration_to_minutes(duration) {
-                // Only rip titles that look like individual episodes (18-50 min)
-                // Skip compilations/play-all (typically 90-200 min for multi-episode discs)
-                // For movies, we expect them to be clearly movie-length (> 70 min but handled separately)
-                #[allow(clippy::manual_range_contains)]
-                if minutes >= 18 && minutes <= 70 {
-                    info!("Will rip title {} ({} min)", idx, minutes);
-                    Some(*idx as u32)
-                } else {
-                    log_callback(format!("⏭️  Skipping title {} ({} min) - outside episode range", idx, minutes));
-                    info!("Skipping title {} ({} min) - likely compilation or play-all", idx, minutes);
-                    None
-                }
-            } else {
-                None
-            }
-        })
-        .collect();
-
     log_callback(format!("Ripping {} titles (filtered from {})", titles_to_rip.len(), title_count));
-    info!("Starting DVD rip of titles: {:?}", titles_to_rip);
+    info!("Starting DVD rip of titles: {:?}", titles_to_rip.iter().map(|t| t.index).collect::<Vec<_>>());
+    
+    rip_titles(backend, device, output_dir, &titles_to_rip, completed_titles, &matched_episodes, &mut progress_callback, &mut log_callback, &mut episode_callback).await?;
+
+    info!("Successfully ripped and renamed all titles");
+    log_callback("✅ DVD rip complete".to_string());
     
+    Ok(matched_episodes)
+}
+
+/// Create the output directory and scan the disc unless a scan was passed in
+pub(crate) async fn prepare_disc<F, L>(
+    backend: &dyn RipBackend,
+    device: &str,
+    output_dir: &Path,
+    scan: Option<DiscScan>,
+    disc_name: &str,
+    progress_callback: &mut F,
+    log_callback: &mut L,
+) -> Result<DiscScan>
+where
+    F: FnMut(RipProgress) + Send,
+    L: FnMut(String) + Send,
+{
+    // Create output directory
+    if let Err(e) = tokio::fs::create_dir_all(output_dir).await {
+        tracing::error!("Failed to create output directory {}: {}", output_dir.display(), e);
+        return Err(anyhow!("Failed to create output directory: {}", e));
+    }
+
+    info!("Output directory: {}", output_dir.display());
+    log_callback(format!("Output: {}", output_dir.display()));
+
+    // First, scan the disc to get info
+    let scan = match scan {
+        Some(scan) => scan,
+        None => {
+            log_callback(format!("Scanning {}...", disc_name));
+            info!("Scanning {} in {}", disc_name, device);
+            
+            progress_callback(RipProgress {
+                current_track: 0,
+                total_tracks: 1,
+                track_name: "Scanning...".to_string(),
+                percentage: 0.0,
+                status: crate::ripper::RipStatus::FetchingMetadata,
+                speed_mbps: None,
+                bytes_processed: None,
+            });
+
+            backend.scan(device).await?
+        }
+    };
+
+    Ok(scan)
+}
+
+/// Rip the given titles one by one, handing each file to `episode_callback` as soon as it's done
+///
+/// The callback also gets the title's entry in `matches`, if it was matched to an episode.
+/// Titles in `completed_titles` are skipped so an interrupted rip resumes at the next un-ripped title.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn rip_titles<F, L, E>(
+    backend: &dyn RipBackend,
+    device: &str,
+    output_dir: &Path,
+    titles_to_rip: &[&TitleInfo],
+    completed_titles: &[u32],
+    matches: &[Assignment],
+    progress_callback: &mut F,
+    log_callback: &mut L,
+    episode_callback: &mut E,
+) -> Result<()>
+where
+    F: FnMut(RipProgress) + Send,
+    L: FnMut(String) + Send,
+    E: for<'a> FnMut(&'a Path, u32, Option<&'a Assignment>) -> EpisodeFuture<'a> + Send,
+{
     progress_callback(RipProgress {
         current_track: 0,
         total_tracks: titles_to_rip.len() as u32,
@@ -206,163 +202,106 @@ where
     });
 
     // Rip each title individually
-    for (idx, title_num) in titles_to_rip.iter().enumerate() {
-        let title_progress = (idx as f32 / titles_to_rip.len() as f32) * 100.0;
-        log_callback(format!("📀 Starting title {} ({}/{})", title_num, idx + 1, titles_to_rip.len()));
+    let total = titles_to_rip.len();
+    for (idx, title) in titles_to_rip.iter().enumerate() {
+        let title_num = title.index;
+        let title_progress = (idx as f32 / total as f32) * 100.0;
+        if completed_titles.contains(&title_num) {
+            log_callback(format!("⏭️  Skipping title {} - already ripped", title_num));
+            info!("Skipping title {} - ripped before the rip was interrupted", title_num);
+            continue;
+        }
+        // A pause between titles holds the next title back until resumed
+        if backend.control().state() == RipState::Paused {
+            log_callback(format!("⏸️  Paused before title {}", title_num));
+            progress_callback(RipProgress {
+                current_track: idx as u32,
+                total_tracks: total as u32,
+                track_name: format!("Title {} - paused", title_num),
+                percentage: title_progress,
+                speed_mbps: None,
+                bytes_processed: None,
+                status: crate::ripper::RipStatus::Paused,
+            });
+            backend.control().wait_while_paused().await;
+        }
+        if backend.control().is_stopped() {
+            return Err(anyhow!("Rip stopped before title {}", title_num));
+        }
+
+        log_callback(format!("📀 Starting title {} ({}/{})", title_num, idx + 1, total));
         
         progress_callback(RipProgress {
             current_track: idx as u32,
-            total_tracks: titles_to_rip.len() as u32,
+            total_tracks: total as u32,
             track_name: format!("Title {} - 0%", title_num),
             percentage: title_progress,
             speed_mbps: None,
             bytes_processed: None,
             status: crate::ripper::RipStatus::Ripping,
         });
-        
-        let mut rip_child = Command::new("makemkvcon")
-            .arg("-r")
-            .arg("--progress=-same")  // Output progress to stdout
-            .arg("--minlength=300")   // Minimum title length in seconds (5 minutes)
-            .arg("--noscan")          // Don't scan disc again, we already did
-            .arg("mkv")
-            .arg(format!("dev:{}", device))
-            .arg(title_num.to_string())  // Rip specific title by number
-            .arg(output_dir)
-            .env("MAKEMKV_PROFILE", "default")  // Use default profile
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| anyhow!("Failed to start makemkvcon rip: {}", e))?;
-
-        let stdout = rip_child.stdout.take()
-            .ok_or_else(|| anyhow!("Failed to capture stdout"))?;
-        let stderr = rip_child.stderr.take()
-            .ok_or_else(|| anyhow!("Failed to capture stderr"))?;
-        
-        let mut stdout_reader = BufReader::new(stdout).lines();
-        let mut stderr_reader = BufReader::new(stderr).lines();
-        #[allow(unused_assignments)]
-        let mut title_percentage = 0.0;
-
-        // Parse rip output for this title
-        loop {
-            tokio::select! {
-                result = stdout_reader.next_line() => {
-                    match result {
-                        Ok(Some(line)) => {
-                            debug!("makemkvcon: {}", line);
-                            
-                            // Parse progress: "PRGV:current,total,max"
-                            if line.starts_with("PRGV:") {
-                                if let Some(progress_str) = line.strip_prefix("PRGV:") {
-                                    let parts: Vec<&str> = progress_str.split(',').collect();
-                                    if parts.len() >= 3 {
-                                        if let (Ok(current), Ok(max)) = (parts[0].parse::<u32>(), parts[2].parse::<u32>()) {
-                                            title_percentage = if max > 0 {
-                                                (current as f32 / max as f32) * 100.0
-                                            } else {
-                                                0.0
-                                            };
-                                            
-                                            // Calculate overall progress
-                                            let overall = ((idx as f32 + title_percentage / 100.0) / titles_to_rip.len() as f32) * 100.0;
-                                            
-                                            progress_callback(RipProgress {
-                                                current_track: idx as u32,
-                                                total_tracks: titles_to_rip.len() as u32,
-                                                track_name: format!("Title {} - {:.0}%", title_num, title_percentage),
-                                                percentage: overall,
-                                                status: crate::ripper::RipStatus::Ripping,
-                                                speed_mbps: None,
-                                                bytes_processed: None,
-                                            });
-                                        }
-                                    }
-                                }
-                            }
-                            
-                            // Log important messages
-                            if line.starts_with("MSG:") || line.starts_with("TINFO:") {
-                                log_callback(line.clone());
-                            }
-                        }
-                            Ok(None) => break,
-                        Err(e) => {
-                            debug!("Error reading rip stdout: {}", e);
-                            break;
-                        }
-                    }
+
+        let rip_result = {
+            let mut last_percentage = 0.0;
+            let mut on_event = |event: RipEvent| match event {
+                RipEvent::Progress(title_percentage) => {
+                    last_percentage = title_percentage;
+                    // Calculate overall progress
+                    let overall = ((idx as f32 + title_percentage / 100.0) / total as f32) * 100.0;
+                    progress_callback(RipProgress {
+                        current_track: idx as u32,
+                        total_tracks: total as u32,
+                        track_name: format!("Title {} - {:.0}%", title_num, title_percentage),
+                        percentage: overall,
+                        status: crate::ripper::RipStatus::Ripping,
+                        speed_mbps: None,
+                        bytes_processed: None,
+                    });
                 }
-                result = stderr_reader.next_line() => {
-                    match result {
-                        Ok(Some(line)) => {
-                            if line.contains("ERROR") || line.contains("error") || line.contains("failed") {
-                                tracing::error!("makemkvcon: {}", line);
-                                log_callback(format!("❌ ERROR: {}", line));
-                            } else {
-                                debug!("makemkvcon stderr: {}", line);
-                            }
-                        }
-                        Ok(None) => {}
-                        Err(e) => debug!("Error reading rip stderr: {}", e),
-                    }
+                RipEvent::Encoding => {}
+                RipEvent::Paused | RipEvent::Resumed => {
+                    let paused = event == RipEvent::Paused;
+                    log_callback(if paused {
+                        format!("⏸️  Paused title {} at {:.0}%", title_num, last_percentage)
+                    } else {
+                        format!("▶️  Resumed title {}", title_num)
+                    });
+                    progress_callback(RipProgress {
+                        current_track: idx as u32,
+                        total_tracks: total as u32,
+                        track_name: format!("Title {} - {:.0}%{}", title_num, last_percentage, if paused { " (paused)" } else { "" }),
+                        percentage: ((idx as f32 + last_percentage / 100.0) / total as f32) * 100.0,
+                        status: if paused { crate::ripper::RipStatus::Paused } else { crate::ripper::RipStatus::Ripping },
+                        speed_mbps: None,
+                        bytes_processed: None,
+                    });
                 }
-            }
-        }
+                RipEvent::Log(line) => log_callback(line),
+            };
+            backend.rip_title(device, title, output_dir, &mut on_event).await
+        };
 
-        let rip_status = rip_child.wait().await?;
-        
-        if !rip_status.success() {
-            return Err(anyhow!("Failed to rip title {}: status {}", title_num, rip_status));
-        }
+        let file_path = match rip_result {
+            Ok(path) => path,
+            Err(e) => return Err(anyhow!("Failed to rip title {}: {}", title_num, e)),
+        };
         
         log_callback(format!("✅ Title {} ripped", title_num));
         
-        // Find the ripped file for this title
-        let mut ripped_file: Option<std::path::PathBuf> = None;
-        if let Ok(mut entries) = tokio::fs::read_dir(output_dir).await {
-            while let Ok(Some(entry)) = entries.next_entry().await {
-                let path = entry.path();
-                if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("mkv") {
-                    // Check if this file was just created (within last 10 seconds)
-                    if let Ok(file_metadata) = tokio::fs::metadata(&path).await {
-                        if let Ok(modified) = file_metadata.modified() {
-                            let age = std::time::SystemTime::now()
-                                .duration_since(modified)
-                                .unwrap_or_default();
-                            if age.as_secs() < 10 {
-                                ripped_file = Some(path);
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        
-        // Process this episode immediately: OpenAI -> Validate -> Move to completed
-        if let Some(file_path) = ripped_file {
-            // Don't log here - let the callback handle logging to avoid duplicates
-            let file_path_for_callback = file_path.clone();
-            if let Err(e) = episode_callback(&file_path_for_callback, *title_num).await {
-                warn!("Failed to process episode {}: {}", title_num, e);
-                log_callback(format!("⚠️  Could not process title {}: {}", title_num, e));
-            } else {
-                log_callback(format!("✅ Title {} processed and moved to completed", title_num));
-            }
+        // Hand the title to the caller right away, with the episode it matched, so it can be named and moved to completed
+        // Don't log here - let the callback handle logging to avoid duplicates
+        let matched = matches.iter().find(|a| a.title_index == title_num);
+        if let Err(e) = episode_callback(&file_path, title_num, matched).await {
+            warn!("Failed to process episode {}: {}", title_num, e);
+            log_callback(format!("⚠️  Could not process title {}: {}", title_num, e));
         } else {
-            warn!("Could not find ripped file for title {}", title_num);
-            log_callback(format!("⚠️  Could not find file for title {}", title_num));
+            log_callback(format!("✅ Title {} processed and moved to completed", title_num));
         }
     }
     
-    info!("Successfully ripped and renamed all titles");
-    log_callback("✅ DVD rip complete".to_string());
-    
     progress_callback(RipProgress {
-        current_track: titles_to_rip.len() as u32,
-        total_tracks: titles_to_rip.len() as u32,
+        current_track: total as u32,
+        total_tracks: total as u32,
         track_name: "Complete".to_string(),
         percentage: 100.0,
         status: crate::ripper::RipStatus::Complete,
@@ -373,10 +312,120 @@ where
     Ok(())
 }
 
+/// Pin a closure to the episode callback signature so it can be stored before picking a rip function
+pub fn episode_callback<E>(callback: E) -> E
+where
+    E: for<'a> FnMut(&'a Path, u32, Option<&'a Assignment>) -> EpisodeFuture<'a> + Send,
+{
+    callback
+}
+
+/// Find titles that only replay other titles: "play all" concatenations and exact duplicates
+///
+/// Uses MakeMKV segment maps (DVD cells / Blu-ray clips) when available, falling back to
+/// chapter counts and durations. Returns (title index, reason) for every title to drop;
+/// the lowest-numbered copy of a duplicate is kept.
+pub fn find_redundant_titles(titles: &[TitleInfo]) -> Vec<(u32, String)> {
+    let mut redundant: Vec<(u32, String)> = Vec::new();
+    let segments: Vec<Vec<String>> = titles.iter().map(|t| t.segments()).collect();
+    let has_segments = segments.iter().any(|s| !s.is_empty());
+
+    // Exact duplicates: same segments (and angle), or same length/chapters/size without segment info
+    for (i, title) in titles.iter().enumerate() {
+        let original = titles[..i].iter().enumerate().find(|(j, other)| {
+            if redundant.iter().any(|(index, _)| *index == other.index) {
+                return false;
+            }
+            if has_segments {
+                !segments[i].is_empty() && segments[i] == segments[*j] && title.angle == other.angle
+            } else {
+                title.size_bytes.is_some()
+                    && title.duration_seconds == other.duration_seconds
+                    && title.chapter_count == other.chapter_count
+                    && title.size_bytes == other.size_bytes
+            }
+        });
+        if let Some((_, other)) = original {
+            redundant.push((title.index, format!("duplicate of title {}", other.index)));
+        }
+    }
+
+    // Play-all: a title made up of two or more of the remaining titles played back to back
+    let is_kept = |index: u32, redundant: &[(u32, String)]| !redundant.iter().any(|(i, _)| *i == index);
+    for (i, title) in titles.iter().enumerate() {
+        if !is_kept(title.index, &redundant) {
+            continue;
+        }
+        let parts: Vec<u32> = if has_segments {
+            let others: Vec<(u32, &[String])> = titles.iter().enumerate()
+                .filter(|(j, other)| *j != i && is_kept(other.index, &redundant) && !segments[*j].is_empty())
+                .filter(|(j, _)| segments[*j].len() < segments[i].len())
+                .map(|(j, other)| (other.index, segments[j].as_slice()))
+                .collect();
+            concatenated_titles(&segments[i], &others)
+        } else {
+            // Without segment info, a title whose chapters and runtime add up to all shorter titles
+            let shorter: Vec<&TitleInfo> = titles.iter()
+                .filter(|other| other.index != title.index && is_kept(other.index, &redundant))
+                .filter(|other| other.duration_seconds < title.duration_seconds && other.chapter_count > 0)
+                .collect();
+            let chapters: u32 = shorter.iter().map(|t| t.chapter_count).sum();
+            let seconds: u32 = shorter.iter().map(|t| t.duration_seconds).sum();
+            let close = seconds.abs_diff(title.duration_seconds) <= title.duration_seconds / 50;
+            if chapters == title.chapter_count && close {
+                shorter.iter().map(|t| t.index).collect()
+            } else {
+                Vec::new()
+            }
+        };
+
+        if parts.len() >= 2 {
+            let list = parts.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", ");
+            redundant.push((title.index, format!("play-all of titles {}", list)));
+        }
+    }
+
+    redundant.sort_by_key(|(index, _)| *index);
+    redundant
+}
+
+/// Match `segments` as back-to-back runs of other titles' segments
+///
+/// Allows one unmatched segment (an intro or credits cell). Returns the matched titles in order,
+/// or an empty list if the title isn't a concatenation.
+fn concatenated_titles(segments: &[String], others: &[(u32, &[String])]) -> Vec<u32> {
+    let mut parts = Vec::new();
+    let mut unmatched = 0;
+    let mut pos = 0;
+    while pos < segments.len() {
+        let longest = others.iter()
+            .filter(|(_, segs)| segments[pos..].starts_with(segs))
+            .max_by_key(|(_, segs)| segs.len());
+        match longest {
+            Some((index, segs)) => {
+                parts.push(*index);
+                pos += segs.len();
+            }
+            None => {
+                unmatched += 1;
+                pos += 1;
+            }
+        }
+    }
+    if unmatched > 1 {
+        return Vec::new();
+    }
+    parts
+}
+
 
 /// Rename MKV files based on metadata (batch mode, kept for compatibility)
 #[allow(dead_code)]
-async fn rename_dvd_files(output_dir: &Path, metadata: &DvdMetadata) -> Result<()> {
+async fn rename_dvd_files(
+    output_dir: &Path,
+    metadata: &DvdMetadata,
+    journal: &crate::rename_journal::RenameJournal<'_>,
+) -> Result<()> {
     use tokio::fs;
     
     let mut entries = fs::read_dir(output_dir).await?;
@@ -413,7 +462,7 @@ async fn rename_dvd_files(output_dir: &Path, metadata: &DvdMetadata) -> Result<(
         let new_path = output_dir.join(&new_name);
         
         info!("Renaming {} -> {}", file_path.display(), new_name);
-        fs::rename(file_path, &new_path).await?;
+        journal.rename(file_path, &new_path, "disc_label")?;
         
         // Small delay to ensure different timestamps if processing quickly
         if mkv_files.len() > 1 {
@@ -424,43 +473,335 @@ async fn rename_dvd_files(output_dir: &Path, metadata: &DvdMetadata) -> Result<(
     Ok(())
 }
 
-/// Configure MakeMKV to skip subtitles
-async fn setup_makemkv_settings() -> Result<()> {
-    use std::env;
-    use std::path::PathBuf;
-
-    // Get MakeMKV data directory
-    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    let makemkv_dir = PathBuf::from(home).join(".MakeMKV");
-    
-    // Create directory if it doesn't exist
-    tokio::fs::create_dir_all(&makemkv_dir).await?;
-    
-    let settings_file = makemkv_dir.join("settings.conf");
-    
-    // Settings to disable subtitle selection by default
-    // app_DefaultSelectionString controls what tracks are selected
-    // Format: +AUDIOTRACK,+VIDEOTRACK,-SUBTITLETRACK
-    let settings = r#"# Ripley auto-generated MakeMKV settings
-# Skip subtitles by default
-app_DefaultSelectionString = "+sel:all,-sel:subtitle"
-
-# Minimum title length (5 minutes = 300 seconds)
-app_MinLength = "300"
-"#;
-
-    tokio::fs::write(&settings_file, settings).await?;
-    
-    info!("MakeMKV settings configured: skip subtitles, minimum 5 minutes");
-    
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::dvd_metadata::Episode;
+    use crate::rip_backend::{FakeBackend, FakeScript};
+
     #[test]
     fn test_makemkv_check() {
         // Just verify the module compiles and has correct structure
         // Actual functionality requires makemkvcon to be installed
     }
+
+    #[tokio::test]
+    async fn test_rip_dvd_with_fake_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FakeBackend::new(FakeScript::default());
+        let mut ripped = Vec::new();
+        let mut last_progress = None;
+
+        rip_dvd(
+            &backend,
+            "/dev/fake",
+            dir.path(),
+            None,
+            &TitleSelection::default(),
+            &[],
+            None,
+            |progress| last_progress = Some(progress),
+            |_| {},
+            |path: &Path, title_num: u32, _| {
+                assert!(path.exists());
+                ripped.push(title_num);
+                Box::pin(async { Ok(()) })
+            },
+        ).await.unwrap();
+
+        // The 88 minute play-all title is skipped
+        assert_eq!(ripped, vec![1, 2, 3, 4]);
+        let last_progress = last_progress.unwrap();
+        assert_eq!(last_progress.status, crate::ripper::RipStatus::Complete);
+        assert_eq!(last_progress.total_tracks, 4);
+    }
+
+    #[tokio::test]
+    async fn test_rip_dvd_explicit_titles() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FakeBackend::new(FakeScript::default());
+        let selection = TitleSelection::resolve(None, None, Some(&[0, 3]));
+        let mut ripped = Vec::new();
+        let mut logs = Vec::new();
+
+        rip_dvd(
+            &backend,
+            "/dev/fake",
+            dir.path(),
+            None,
+            &selection,
+            &[],
+            None,
+            |_| {},
+            |line| logs.push(line),
+            |_: &Path, title_num: u32, _| {
+                ripped.push(title_num);
+                Box::pin(async { Ok(()) })
+            },
+        ).await.unwrap();
+
+        assert_eq!(ripped, vec![0, 3]);
+        assert!(logs.iter().any(|l| l.contains("Skipping title 1") && l.contains("not in selected titles")));
+    }
+
+    #[tokio::test]
+    async fn test_rip_dvd_returns_matched_episodes() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FakeBackend::new(FakeScript::default());
+        let episode = |season: u32, episode: u32, runtime: u32| Episode {
+            season,
+            episode,
+            title: format!("Episode {}x{}", season, episode),
+            title_index: 0,
+            runtime_minutes: Some(runtime),
+            overview: None,
+        };
+        // Season unknown: hour-long first season, half-hour second season
+        let metadata = DvdMetadata {
+            title: "Show".to_string(),
+            year: None,
+            media_type: MediaType::TVShow,
+            episodes: (1..=4).map(|e| episode(1, e, 45)).chain((1..=6).map(|e| episode(2, e, 22))).collect(),
+        };
+        let mut handed_over = Vec::new();
+
+        let matched = rip_dvd(
+            &backend,
+            "/dev/fake",
+            dir.path(),
+            None,
+            &TitleSelection::default(),
+            &[],
+            Some(&metadata),
+            |_| {},
+            |_| {},
+            |_: &Path, title_num: u32, matched: Option<&Assignment>| {
+                handed_over.push((title_num, matched.map(|a| a.episode_match.episode)));
+                Box::pin(async { Ok(()) })
+            },
+        ).await.unwrap();
+
+        assert_eq!(matched.iter().map(|a| (a.title_index, a.episode_match.season, a.episode_match.episode)).collect::<Vec<_>>(),
+                   vec![(1, 2, 1), (2, 2, 2), (3, 2, 3), (4, 2, 4)]);
+        // Each file reaches the callback with its title's episode
+        assert_eq!(handed_over, vec![(1, Some(1)), (2, Some(2)), (3, Some(3)), (4, Some(4))]);
+    }
+
+    #[tokio::test]
+    async fn test_rip_dvd_movie_rips_cuts_not_episodes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut script = FakeScript::default();
+        script.titles.truncate(3);
+        for (title, (minutes, segments)) in script.titles.iter_mut().zip([(117, "1-9"), (134, "1-12"), (12, "13")]) {
+            title.duration_seconds = minutes * 60;
+            title.segments = Some(segments.to_string());
+        }
+        let backend = FakeBackend::new(script);
+        let metadata = DvdMetadata {
+            title: "Alien".to_string(),
+            year: Some("1979".to_string()),
+            media_type: MediaType::Movie,
+            episodes: Vec::new(),
+        };
+        let mut ripped = Vec::new();
+        let mut logs = Vec::new();
+
+        let matched = rip_dvd(
+            &backend,
+            "/dev/fake",
+            dir.path(),
+            None,
+            &TitleSelection::default(),
+            &[],
+            Some(&metadata),
+            |_| {},
+            |line| logs.push(line),
+            |_: &Path, title_num: u32, _| {
+                ripped.push(title_num);
+                Box::pin(async { Ok(()) })
+            },
+        ).await.unwrap();
+
+        // Both cuts are longer than any episode; the extra isn't wanted by default
+        assert_eq!(ripped, vec![0, 1]);
+        assert!(matched.is_empty());
+        assert!(logs.iter().any(|l| l.starts_with("🎬 Main feature: title 1") && l.ends_with("Director's Cut")));
+        assert!(logs.iter().any(|l| l == "⏭️  Skipping title 2 (12 min) - not the movie or one of its extras"));
+    }
+
+    #[tokio::test]
+    async fn test_pause_resume_reflected_in_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FakeBackend::new(FakeScript::default());
+        let control = backend.control().clone();
+        let selection = TitleSelection::default();
+        let mut statuses: Vec<(String, crate::ripper::RipStatus)> = Vec::new();
+
+        let rip = rip_dvd(
+            &backend,
+            "/dev/fake",
+            dir.path(),
+            None,
+            &selection,
+            &[],
+            None,
+            |progress| {
+                // Pause once; resuming reports the same percentage again
+                if progress.track_name == "Title 2 - 50%" && !statuses.iter().any(|(name, _)| *name == progress.track_name) {
+                    control.pause();
+                }
+                statuses.push((progress.track_name, progress.status));
+            },
+            |_| {},
+            |_: &Path, _, _| Box::pin(async { Ok(()) }),
+        );
+        let resume = async {
+            let mut rx = backend.control().subscribe();
+            rx.wait_for(|state| *state == RipState::Paused).await.unwrap();
+            backend.control().resume();
+        };
+        let (result, _) = tokio::join!(rip, resume);
+        result.unwrap();
+
+        let paused = statuses.iter().position(|(_, s)| *s == crate::ripper::RipStatus::Paused).unwrap();
+        assert_eq!(statuses[paused].0, "Title 2 - 50% (paused)");
+        assert_eq!(statuses[paused + 1], ("Title 2 - 50%".to_string(), crate::ripper::RipStatus::Ripping));
+        assert_eq!(statuses.last().unwrap().1, crate::ripper::RipStatus::Complete);
+    }
+
+    #[tokio::test]
+    async fn test_stop_between_titles() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FakeBackend::new(FakeScript::default());
+        let control = backend.control().clone();
+        let mut ripped = Vec::new();
+
+        let result = rip_dvd(
+            &backend,
+            "/dev/fake",
+            dir.path(),
+            None,
+            &TitleSelection::default(),
+            &[],
+            None,
+            |_| {},
+            |_| {},
+            |_: &Path, title_num: u32, _| {
+                ripped.push(title_num);
+                if title_num == 2 {
+                    control.stop();
+                }
+                Box::pin(async { Ok(()) })
+            },
+        ).await;
+
+        assert_eq!(result.unwrap_err().to_string(), "Rip stopped before title 3");
+        assert_eq!(ripped, vec![1, 2]);
+        assert!(!dir.path().join("title_t03.mkv").exists());
+    }
+
+    fn title(index: u32, minutes: u32, segments: &str) -> TitleInfo {
+        TitleInfo {
+            index,
+            duration_seconds: minutes * 60,
+            chapter_count: 6,
+            segment_map: Some(segments.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_detects_play_all_and_duplicates() {
+        let titles = vec![
+            title(0, 88, "1-8"),
+            title(1, 22, "1,2"),
+            title(2, 22, "3,4"),
+            title(3, 22, "5,6"),
+            title(4, 22, "7,8"),
+            title(5, 22, "3,4"),
+            title(6, 3, "9"),
+        ];
+        let redundant = find_redundant_titles(&titles);
+        assert_eq!(redundant, vec![
+            (0, "play-all of titles 1, 2, 3, 4".to_string()),
+            (5, "duplicate of title 2".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_play_all_with_intro_cell() {
+        let titles = vec![title(0, 45, "1-3"), title(1, 22, "2"), title(2, 22, "3")];
+        assert_eq!(find_redundant_titles(&titles).len(), 1);
+
+        // Two unrelated cells means it's not just the episodes back to back
+        let titles = vec![title(0, 60, "1-4"), title(1, 22, "2"), title(2, 22, "3")];
+        assert!(find_redundant_titles(&titles).is_empty());
+    }
+
+    #[test]
+    fn test_play_all_without_segment_maps() {
+        let mut titles = vec![title(0, 66, ""), title(1, 22, ""), title(2, 22, ""), title(3, 22, "")];
+        for t in &mut titles {
+            t.segment_map = None;
+        }
+        titles[0].chapter_count = 18;
+        let redundant = find_redundant_titles(&titles);
+        assert_eq!(redundant.len(), 1);
+        assert_eq!(redundant[0].0, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rip_dvd_logs_play_all_skip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut script = FakeScript::default();
+        // A long play-all that the duration window alone would let through
+        script.titles[0].duration_seconds = 50 * 60;
+        let backend = FakeBackend::new(script);
+        let mut ripped = Vec::new();
+        let mut logs = Vec::new();
+
+        rip_dvd(
+            &backend,
+            "/dev/fake",
+            dir.path(),
+            None,
+            &TitleSelection::default(),
+            &[],
+            None,
+            |_| {},
+            |line| logs.push(line),
+            |_: &Path, title_num: u32, _| {
+                ripped.push(title_num);
+                Box::pin(async { Ok(()) })
+            },
+        ).await.unwrap();
+
+        assert_eq!(ripped, vec![1, 2, 3, 4]);
+        assert!(logs.iter().any(|l| l == "⏭️  Skipping title 0 - play-all of titles 1, 2, 3, 4"));
+    }
+
+    #[tokio::test]
+    async fn test_rip_dvd_resumes_after_completed_titles() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FakeBackend::new(FakeScript::default());
+        let mut ripped = Vec::new();
+
+        rip_dvd(
+            &backend,
+            "/dev/fake",
+            dir.path(),
+            None,
+            &TitleSelection::default(),
+            &[1, 2],
+            None,
+            |_| {},
+            |_| {},
+            |_: &Path, title_num: u32, _| {
+                ripped.push(title_num);
+                Box::pin(async { Ok(()) })
+            },
+        ).await.unwrap();
+
+        assert_eq!(ripped, vec![3, 4]);
+    }
 }
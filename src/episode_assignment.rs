@@ -0,0 +1,384 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::debug;
+
+use crate::dvd_metadata::Episode;
+use crate::speech_match::EpisodeMatch;
+
+// Assigns a disc's titles to episodes all at once instead of title by title: every title × episode pair gets
+// a cost from the runtime difference, the title's position on the disc and what transcript matching said, and
+// the Hungarian algorithm finds the cheapest one-to-one assignment. A title's confidence is how much more the
+// best assignment that doesn't pair it that way would cost, so a pick that another title could just as well
+// have taken comes out low and is flagged for review.
+
+/// Cost of a title/episode pair per tolerance's worth of runtime difference
+const DURATION_WEIGHT: f64 = 1.0;
+/// Cost per position a title sits away from its expected episode, capped at `MAX_ORDER_COST`
+const ORDER_WEIGHT: f64 = 0.25;
+const MAX_ORDER_COST: f64 = 1.0;
+/// Tie-breaker favouring the start of the episode list when the disc could begin at several episodes
+const OFFSET_WEIGHT: f64 = 0.01;
+/// Cost of going against a transcript match of 100% confidence
+const TRANSCRIPT_WEIGHT: f64 = 2.0;
+/// Cost when a runtime is unknown (neither evidence for nor against)
+const UNKNOWN_DURATION_COST: f64 = 0.5;
+/// Leaving a title unmatched; cheaper than any pair outside the runtime tolerance
+const UNMATCHED_COST: f64 = 5.0;
+const FORBIDDEN_COST: f64 = 1e6;
+/// Extra cost (of the alternative) at which confidence reaches ~63%
+const CONFIDENCE_SCALE: f64 = 0.5;
+/// Assignments below this confidence are flagged for manual review instead of being renamed
+pub const REVIEW_CONFIDENCE: f32 = 85.0;
+/// Candidate episodes kept for a review
+const REVIEW_CANDIDATES: usize = 5;
+
+/// What is known about one disc title (or ripped file)
+#[derive(Debug, Clone)]
+pub struct TitleEvidence {
+    pub title_index: u32, // Disc order; titles are ranked by it for the order prior
+    pub minutes: Option<u32>,
+    pub transcript_match: Option<EpisodeMatch>,
+}
+
+/// A title's episode in the global assignment
+#[derive(Debug, Clone)]
+pub struct Assignment {
+    pub title_index: u32,
+    pub episode_match: EpisodeMatch, // match_method is the transcript's when it agrees, else "duration"
+    pub needs_review: bool,
+    pub candidates: Vec<EpisodeCandidate>, // Best episodes for the title, only filled in when it needs review
+}
+
+/// An episode a title could be, scored 0-100 relative to the best assignment (which scores 100)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpisodeCandidate {
+    pub season: u32,
+    pub episode: u32,
+    pub title: String,
+    pub score: f32,
+}
+
+/// Restriction on one (title, episode) pair when solving
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Constraint {
+    None,
+    Forbid(usize, usize),
+    Require(usize, usize),
+}
+
+/// Assign titles to episodes (keyed by season and episode) minimising the combined cost
+///
+/// Titles that can't be matched within `tolerance_minutes` are left out of the result.
+pub fn assign_episodes(titles: &[TitleEvidence], episodes: &[Episode], tolerance_minutes: u32) -> Vec<Assignment> {
+    if titles.is_empty() || episodes.is_empty() {
+        return Vec::new();
+    }
+
+    // Titles in disc order, episodes in broadcast order across seasons
+    let mut title_order: Vec<usize> = (0..titles.len()).collect();
+    title_order.sort_by_key(|&i| titles[i].title_index);
+    let mut episode_order: Vec<usize> = (0..episodes.len()).collect();
+    episode_order.sort_by_key(|&j| (episodes[j].season, episodes[j].episode));
+    let ordered_titles: Vec<&TitleEvidence> = title_order.iter().map(|&i| &titles[i]).collect();
+    let ordered_episodes: Vec<&Episode> = episode_order.iter().map(|&j| &episodes[j]).collect();
+
+    let base = base_costs(&ordered_titles, &ordered_episodes, tolerance_minutes.max(1));
+    let Some((best_cost, best_offset, columns)) = solve(&base, Constraint::None) else {
+        return Vec::new();
+    };
+    debug!("Assigned {} titles starting at episode position {} (cost {:.2})", titles.len(), best_offset, best_cost);
+
+    let mut assignments = Vec::new();
+    for (row, &column) in columns.iter().enumerate() {
+        if column >= ordered_episodes.len() {
+            debug!("Title {} left unmatched", ordered_titles[row].title_index);
+            continue;
+        }
+
+        // The cheapest assignment that doesn't pair this title with this episode
+        let alternative = solve(&base, Constraint::Forbid(row, column)).map(|(cost, _, _)| cost).unwrap_or(FORBIDDEN_COST);
+        let extra = (alternative - best_cost).max(0.0);
+        let confidence = ((1.0 - (-extra / CONFIDENCE_SCALE).exp()) * 100.0) as f32;
+        let needs_review = confidence < REVIEW_CONFIDENCE;
+
+        let title = ordered_titles[row];
+        let episode = ordered_episodes[column];
+        let agrees = title.transcript_match.as_ref()
+            .filter(|m| (m.season, m.episode) == (episode.season, episode.episode));
+        assignments.push(Assignment {
+            title_index: title.title_index,
+            episode_match: EpisodeMatch {
+                season: episode.season,
+                episode: episode.episode,
+                title: episode.title.clone(),
+                confidence,
+                match_method: agrees.map(|m| m.match_method.clone()).unwrap_or_else(|| "duration".to_string()),
+            },
+            needs_review,
+            candidates: if needs_review { candidates(&base, best_cost, row, &ordered_episodes) } else { Vec::new() },
+        });
+    }
+    assignments
+}
+
+/// The episodes a title could take, by how close the best assignment giving it each one comes to the overall best
+fn candidates(base: &[Vec<f64>], best_cost: f64, row: usize, episodes: &[&Episode]) -> Vec<EpisodeCandidate> {
+    let mut candidates: Vec<EpisodeCandidate> = (0..episodes.len())
+        .filter(|&column| base[row][column] < FORBIDDEN_COST)
+        .filter_map(|column| {
+            let (cost, _, _) = solve(base, Constraint::Require(row, column))?;
+            if cost >= FORBIDDEN_COST {
+                return None;
+            }
+            let extra = (cost - best_cost).max(0.0);
+            Some(EpisodeCandidate {
+                season: episodes[column].season,
+                episode: episodes[column].episode,
+                title: episodes[column].title.clone(),
+                score: ((-extra / CONFIDENCE_SCALE).exp() * 100.0) as f32,
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score).then((a.season, a.episode).cmp(&(b.season, b.episode))));
+    candidates.truncate(REVIEW_CANDIDATES);
+    candidates
+}
+
+/// Duration and transcript cost for each (title, episode) pair, in disc/broadcast order
+fn base_costs(titles: &[&TitleEvidence], episodes: &[&Episode], tolerance_minutes: u32) -> Vec<Vec<f64>> {
+    let episode_positions: HashMap<(u32, u32), usize> = episodes.iter().enumerate()
+        .map(|(j, ep)| ((ep.season, ep.episode), j))
+        .collect();
+
+    titles.iter()
+        .map(|title| {
+            let matched = title.transcript_match.as_ref()
+                .and_then(|m| episode_positions.get(&(m.season, m.episode)).map(|&j| (j, m.confidence)));
+            episodes.iter().enumerate()
+                .map(|(j, ep)| {
+                    let duration = match (title.minutes, ep.runtime_minutes) {
+                        (Some(title_min), Some(runtime)) => {
+                            let diff = (title_min as i64 - runtime as i64).unsigned_abs() as f64;
+                            if diff > tolerance_minutes as f64 {
+                                return FORBIDDEN_COST;
+                            }
+                            diff / tolerance_minutes as f64
+                        }
+                        _ => UNKNOWN_DURATION_COST,
+                    };
+                    let transcript = match matched {
+                        Some((matched_j, confidence)) if matched_j == j => 1.0 - confidence.clamp(0.0, 100.0) as f64 / 100.0,
+                        Some(_) => 1.0,
+                        None => 0.0,
+                    };
+                    DURATION_WEIGHT * duration + TRANSCRIPT_WEIGHT * transcript
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Cheapest assignment over every starting offset for the order prior, optionally forbidding or requiring one pair
+///
+/// Returns (cost, offset, episode column per title); columns past the episodes mean "unmatched".
+fn solve(base: &[Vec<f64>], constraint: Constraint) -> Option<(f64, usize, Vec<usize>)> {
+    let rows = base.len();
+    let episodes = base.first()?.len();
+    let mut best: Option<(f64, usize, Vec<usize>)> = None;
+
+    for offset in 0..=episodes.saturating_sub(rows) {
+        // One "unmatched" column per title so every title can always be placed
+        let cost: Vec<Vec<f64>> = base.iter().enumerate()
+            .map(|(row, costs)| {
+                let allowed = |column: usize| match constraint {
+                    Constraint::Forbid(r, c) => (r, c) != (row, column),
+                    Constraint::Require(r, c) => r != row || c == column,
+                    Constraint::None => true,
+                };
+                let mut line: Vec<f64> = costs.iter().enumerate()
+                    .map(|(column, &c)| {
+                        if !allowed(column) || c >= FORBIDDEN_COST {
+                            return FORBIDDEN_COST;
+                        }
+                        let distance = (column as f64 - offset as f64 - row as f64).abs();
+                        c + (distance * ORDER_WEIGHT).min(MAX_ORDER_COST)
+                    })
+                    .collect();
+                line.extend((0..rows).map(|i| if allowed(costs.len() + i) { UNMATCHED_COST } else { FORBIDDEN_COST }));
+                line
+            })
+            .collect();
+
+        let columns = hungarian(&cost);
+        let total = columns.iter().enumerate().map(|(row, &column)| cost[row][column]).sum::<f64>()
+            + offset as f64 * OFFSET_WEIGHT;
+        if best.as_ref().is_none_or(|(best_total, _, _)| total < *best_total) {
+            best = Some((total, offset, columns));
+        }
+    }
+    best
+}
+
+/// Minimum-cost assignment of every row to a distinct column (rows <= columns), as the column per row
+fn hungarian(cost: &[Vec<f64>]) -> Vec<usize> {
+    let n = cost.len();
+    let m = cost.first().map_or(0, |r| r.len());
+    // Potentials and matching are 1-based with 0 as the virtual start column
+    let mut u = vec![0.0; n + 1];
+    let mut v = vec![0.0; m + 1];
+    let mut row_of = vec![0usize; m + 1];
+    let mut way = vec![0usize; m + 1];
+
+    for i in 1..=n {
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut min_to = vec![f64::INFINITY; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let mut delta = f64::INFINITY;
+            let mut j1 = 0;
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let reduced = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                if reduced < min_to[j] {
+                    min_to[j] = reduced;
+                    way[j] = j0;
+                }
+                if min_to[j] < delta {
+                    delta = min_to[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_to[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if row_of[j0] == 0 {
+                break;
+            }
+        }
+        loop {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut columns = vec![0; n];
+    for j in 1..=m {
+        if row_of[j] != 0 {
+            columns[row_of[j] - 1] = j - 1;
+        }
+    }
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episode(season: u32, episode: u32, runtime: u32) -> Episode {
+        Episode {
+            season,
+            episode,
+            title: format!("Episode {}x{}", season, episode),
+            title_index: 0,
+            runtime_minutes: Some(runtime),
+            overview: None,
+        }
+    }
+
+    fn title(title_index: u32, minutes: u32) -> TitleEvidence {
+        TitleEvidence { title_index, minutes: Some(minutes), transcript_match: None }
+    }
+
+    fn transcript(season: u32, episode: u32, confidence: f32) -> Option<EpisodeMatch> {
+        Some(EpisodeMatch {
+            season,
+            episode,
+            title: String::new(),
+            confidence,
+            match_method: "transcript".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_hungarian_finds_global_minimum() {
+        // Greedy row by row would take (0,0) then be forced into (1,1) for a total of 1 + 100
+        let cost = vec![vec![1.0, 2.0], vec![3.0, 100.0]];
+        assert_eq!(hungarian(&cost), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_avoids_greedy_cascade() {
+        // Title 1 is closest to episode 1, but only title 1 can fit episode 2's long runtime
+        let episodes = vec![episode(1, 1, 22), episode(1, 2, 26)];
+        let titles = vec![title(1, 23), title(2, 21)];
+        let assignments = assign_episodes(&titles, &episodes, 3);
+        let pairs: Vec<(u32, u32)> = assignments.iter().map(|a| (a.title_index, a.episode_match.episode)).collect();
+        assert_eq!(pairs, vec![(1, 2), (2, 1)]);
+        assert!(assignments.iter().all(|a| a.episode_match.match_method == "duration"));
+    }
+
+    #[test]
+    fn test_keys_episodes_by_season() {
+        // Same episode numbers in two seasons; the disc continues from the end of season 1
+        let episodes = vec![episode(1, 12, 22), episode(1, 13, 22), episode(2, 1, 44), episode(2, 2, 22)];
+        let titles = vec![title(0, 22), title(1, 44), title(2, 22)];
+        let assignments = assign_episodes(&titles, &episodes, 5);
+        let codes: Vec<(u32, u32)> = assignments.iter()
+            .map(|a| (a.episode_match.season, a.episode_match.episode))
+            .collect();
+        assert_eq!(codes, vec![(1, 13), (2, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_transcripts_override_order_and_set_confidence() {
+        let episodes = vec![episode(1, 1, 22), episode(1, 2, 22), episode(1, 3, 22)];
+        // The disc has episodes 2 and 1 swapped; transcripts say so
+        let titles = vec![
+            TitleEvidence { title_index: 0, minutes: Some(22), transcript_match: transcript(1, 2, 95.0) },
+            TitleEvidence { title_index: 1, minutes: Some(22), transcript_match: transcript(1, 1, 90.0) },
+            TitleEvidence { title_index: 2, minutes: Some(22), transcript_match: None },
+        ];
+        let assignments = assign_episodes(&titles, &episodes, 5);
+        assert_eq!(assignments[0].episode_match.episode, 2);
+        assert_eq!(assignments[1].episode_match.episode, 1);
+        assert_eq!(assignments[2].episode_match.episode, 3);
+        assert_eq!(assignments[0].episode_match.match_method, "transcript");
+        assert_eq!(assignments[2].episode_match.match_method, "duration");
+        assert!(assignments[0].episode_match.confidence >= 85.0);
+        assert!(!assignments[0].needs_review);
+    }
+
+    #[test]
+    fn test_ambiguous_and_unmatched_titles() {
+        // Identical runtimes and a disc that could start anywhere: nothing tells the episodes apart
+        let episodes: Vec<Episode> = (1..=6).map(|e| episode(1, e, 22)).collect();
+        let assignments = assign_episodes(&[title(0, 22), title(1, 22)], &episodes, 5);
+        assert_eq!(assignments.len(), 2);
+        assert!(assignments.iter().all(|a| a.needs_review));
+        let candidates = &assignments[0].candidates;
+        assert_eq!(candidates.len(), 5);
+        assert_eq!((candidates[0].episode, candidates[0].score), (1, 100.0));
+        assert!(candidates.windows(2).all(|w| w[0].score >= w[1].score));
+
+        // A 90 minute title fits no episode
+        let assignments = assign_episodes(&[title(0, 22), title(1, 90)], &episodes[..1], 5);
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].title_index, 0);
+        assert!(assign_episodes(&[], &episodes, 5).is_empty());
+    }
+}
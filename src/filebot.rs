@@ -1,15 +1,37 @@
 use anyhow::Result;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
+use crate::config::Config;
+use crate::dvd_metadata::EpisodeOrder;
+use crate::naming::{self, MediaKind};
+use crate::rename_journal::RenameJournal;
+
 /// Rename video files using Filebot to match TheTVDB/TMDB database order
+///
+/// Files are named with the show's episode template, in the show's episode order (else `filebot.order`). Each
+/// move Filebot reports is recorded in `journal`; in dry-run mode Filebot only prints what it would do.
 pub async fn rename_with_filebot(
     output_dir: &Path,
     show_title: &str,
+    order: EpisodeOrder,
+    config: &Config,
+    journal: &RenameJournal<'_>,
     log_callback: impl Fn(String) + Send + 'static,
 ) -> Result<()> {
     info!("Running Filebot to rename files in {} for '{}'", output_dir.display(), show_title);
     
+    // Orders Filebot doesn't have (story arcs...) are already numbered by Ripley from TMDB
+    let Some(filebot_order) = order.filebot_order() else {
+        log_callback(format!("ℹ️  Filebot has no {:?} order, keeping Ripley's numbering", order));
+        return Ok(());
+    };
+    let filebot_order = match order {
+        EpisodeOrder::Aired if !config.filebot.order.is_empty() => config.filebot.order.as_str(),
+        _ => filebot_order,
+    };
+    let database = if config.filebot.database.is_empty() { "TheTVDB" } else { config.filebot.database.as_str() };
+    
     log_callback(format!("🤖 Running Filebot to fix episode ordering for '{}'...", show_title));
     
     // Check if filebot is installed
@@ -26,15 +48,20 @@ pub async fn rename_with_filebot(
     
     log_callback("📝 Filebot analyzing episodes and renaming to broadcast order...".to_string());
     
+    let format = naming::filebot_format(&config.naming_template(MediaKind::Episode, Some(show_title)), MediaKind::Episode)?;
+    
     // Build filebot command to rename files
     let mut cmd = tokio::process::Command::new("filebot");
     cmd.arg("-rename")
         .arg(output_dir)
-        .arg("--db").arg("TheTVDB")  // Use TheTVDB (best for TV series)
+        .arg("--db").arg(database)  // TheTVDB unless configured (best for TV series)
         .arg("--q").arg(show_title)
-        .arg("--order").arg("Airdate")  // Use broadcast order
-        .arg("--format").arg("{n.space('.')}.S{s.pad(2)}E{e.pad(2)}.{t.space('.')}")  // Format: Show.Name.S01E01.Episode.Title
+        .arg("--order").arg(filebot_order)
+        .arg("--format").arg(&format)
         .arg("-non-strict");  // Allow fuzzy matching
+    if journal.is_dry_run() {
+        cmd.arg("--action").arg("test");  // Report matches without moving anything
+    }
     
     debug!("Filebot command: {:?}", cmd);
     
@@ -54,20 +81,13 @@ pub async fn rename_with_filebot(
         return Err(anyhow::anyhow!("Filebot exited with error: {}", output.status));
     }
     
-    // Parse output to count renames - look for [MOVE] lines
-    let mut rename_count = 0;
-    for line in stdout.lines() {
-        if line.starts_with("[MOVE]") && line.contains("from [") && line.contains("] to [") {
-            rename_count += 1;
-            // Extract filenames for logging
-            if let Some(from_start) = line.find("from [") {
-                if let Some(from_end) = line[from_start..].find("] to [") {
-                    let old_path = &line[from_start + 6..from_start + from_end];
-                    if let Some(old_name) = std::path::Path::new(old_path).file_name() {
-                        log_callback(format!("  ✓ {}", old_name.to_string_lossy()));
-                    }
-                }
-            }
+    // Parse output to count renames - look for [MOVE] (or [TEST] in dry-run mode) lines
+    let moves = parse_filebot_moves(&stdout);
+    let rename_count = moves.len();
+    for (old_path, new_path) in &moves {
+        journal.record(old_path, new_path, "filebot");
+        if let (Some(old_name), Some(new_name)) = (old_path.file_name(), new_path.file_name()) {
+            log_callback(format!("  ✓ {} → {}", old_name.to_string_lossy(), new_name.to_string_lossy()));
         }
     }
     
@@ -82,9 +102,24 @@ pub async fn rename_with_filebot(
     }
 }
 
+/// (old, new) paths from Filebot's `[MOVE] from [old] to [new]` (or `[TEST]`) lines
+fn parse_filebot_moves(stdout: &str) -> Vec<(PathBuf, PathBuf)> {
+    stdout
+        .lines()
+        .filter(|line| line.starts_with("[MOVE]") || line.starts_with("[TEST]"))
+        .filter_map(|line| {
+            let rest = &line[line.find("from [")? + 6..];
+            let (old_path, rest) = rest.split_once("] to [")?;
+            let new_path = &rest[..rest.rfind(']')?];
+            Some((PathBuf::from(old_path), PathBuf::from(new_path)))
+        })
+        .collect()
+}
+
 /// Rename audio files using Filebot with MusicBrainz database
 pub async fn rename_music_with_filebot(
     album_dir: &Path,
+    config: &Config,
     log_callback: impl Fn(String) + Send + 'static,
 ) -> Result<()> {
     info!("Running Filebot to standardize music filenames in {}", album_dir.display());
@@ -106,12 +141,13 @@ pub async fn rename_music_with_filebot(
     log_callback("📝 Filebot analyzing album metadata from MusicBrainz...".to_string());
     
     // Build filebot command for music mode
-    // Format: Artist/Album/01 - Track Title.flac
+    // Format: the music template, Artist/Album/01 - Track Title.flac by default
+    let format = naming::filebot_format(&config.naming_template(MediaKind::Music, None), MediaKind::Music)?;
     let mut cmd = tokio::process::Command::new("filebot");
     cmd.arg("-rename")
         .arg(album_dir)
         .arg("--db").arg("AcoustID")  // Use AcoustID/MusicBrainz for music
-        .arg("--format").arg("{artist}/{album}/{pi.pad(2)} - {t}")
+        .arg("--format").arg(&format)
         .arg("-non-strict");
     
     debug!("Filebot music command: {:?}", cmd);
@@ -145,3 +181,20 @@ pub async fn rename_music_with_filebot(
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filebot_moves() {
+        let stdout = "Rename episodes using [TheTVDB] with [Airdate]\n\
+            [MOVE] from [/rips/Show/title_t00.mkv] to [/rips/Show/Show.S01E01.Pilot.mkv]\n\
+            [TEST] from [/rips/Show/title_t01.mkv] to [/rips/Show/Show.S01E02.[Part 2].mkv]\n\
+            Processed 2 files\n";
+        assert_eq!(parse_filebot_moves(stdout), vec![
+            (PathBuf::from("/rips/Show/title_t00.mkv"), PathBuf::from("/rips/Show/Show.S01E01.Pilot.mkv")),
+            (PathBuf::from("/rips/Show/title_t01.mkv"), PathBuf::from("/rips/Show/Show.S01E02.[Part 2].mkv")),
+        ]);
+    }
+}
@@ -0,0 +1,216 @@
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::database::Database;
+use crate::disc_scan::DiscScan;
+
+// Disc fingerprints recognise discs that have been ripped before. Mounted video discs are stored under the
+// hash of their IFO (DVD) or BDMV (Blu-ray) navigation files plus volume label ("dvd:", "bluray:"), which
+// needs no scan. A disc that isn't mounted falls back to the hash of its scanned titles ("scan:"); rips
+// record that as an alias of the structure hash so the disc is recognised either way. Audio CDs use the
+// MusicBrainz disc ID ("musicbrainz:").
+
+/// Fingerprint an audio CD from its MusicBrainz disc ID
+pub fn cd_fingerprint(disc_id: &str) -> String {
+    format!("musicbrainz:{}", disc_id)
+}
+
+/// Fingerprint a video disc from its scan, for discs whose structure can't be read
+pub fn video_fingerprint(scan: &DiscScan) -> Option<String> {
+    Some(scan).filter(|s| !s.titles.is_empty()).map(scan_fingerprint)
+}
+
+/// The fingerprint a video disc's rips are stored under: its structure hash when mounted, else its scan hash
+///
+/// Either is resolved through the aliases in `db`, so a disc first ripped under the other hash keeps its
+/// history; the scan hash is recorded as an alias so the disc is still recognised when it isn't mounted.
+pub fn record_video_fingerprint(db: &Database, mounted: Option<&str>, scan: &DiscScan) -> Option<String> {
+    let scanned = video_fingerprint(scan);
+    let fingerprint = mounted.map(String::from).or_else(|| scanned.clone())?;
+    let fingerprint = db.canonical_fingerprint(&fingerprint).unwrap_or(fingerprint);
+    if let Some(scanned) = scanned.filter(|s| *s != fingerprint) {
+        if let Err(e) = db.add_fingerprint_alias(&scanned, &fingerprint) {
+            warn!("Failed to record disc fingerprint alias: {}", e);
+        }
+    }
+    Some(fingerprint)
+}
+
+/// Fingerprint a mounted video disc from its file structure, with the disc's own volume label
+///
+/// Returns `None` when the disc isn't mounted.
+pub fn mounted_fingerprint(device: &str, volume_label: Option<&str>) -> Option<String> {
+    let fingerprint = crate::drive::find_mount_point(device)
+        .and_then(|root| structure_fingerprint(&root, volume_label));
+    if fingerprint.is_none() {
+        debug!("No readable disc structure for {}", device);
+    }
+    fingerprint
+}
+
+/// Hash the navigation files under a disc root plus the volume label (not the mount folder's name, which
+/// differs between hosts, e.g. "LABEL 1" on macOS)
+pub fn structure_fingerprint(root: &Path, volume_label: Option<&str>) -> Option<String> {
+    let (kind, mut files) = structure_files(root)?;
+    if files.is_empty() {
+        return None;
+    }
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(volume_label.unwrap_or_default().as_bytes());
+    for file in &files {
+        let contents = std::fs::read(file).ok()?;
+        let relative = file.strip_prefix(root).unwrap_or(file);
+        hasher.update(relative.to_string_lossy().to_uppercase().as_bytes());
+        hasher.update((contents.len() as u64).to_be_bytes());
+        hasher.update(&contents);
+    }
+    Some(format!("{}:{}", kind, &hex::encode(hasher.finalize())[..32]))
+}
+
+/// Hash the title layout from a scan (durations, chapters, segments) plus the volume label
+pub fn scan_fingerprint(scan: &DiscScan) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(scan.volume_label.as_deref().unwrap_or_default().as_bytes());
+    for title in &scan.titles {
+        hasher.update(format!(
+            "|{}:{}:{}:{}:{}",
+            title.index,
+            title.duration_seconds,
+            title.chapter_count,
+            title.segment_map.as_deref().unwrap_or_default(),
+            title.source_file.as_deref().unwrap_or_default(),
+        ).as_bytes());
+    }
+    format!("scan:{}", &hex::encode(hasher.finalize())[..32])
+}
+
+/// The navigation files that describe a disc's structure, and the kind of disc they belong to
+fn structure_files(root: &Path) -> Option<(&'static str, Vec<PathBuf>)> {
+    let video_ts = find_dir(root, "VIDEO_TS");
+    if let Some(dir) = video_ts {
+        return Some(("dvd", files_with_extension(&dir, "ifo")));
+    }
+
+    let bdmv = find_dir(root, "BDMV")?;
+    let mut files: Vec<PathBuf> = ["index.bdmv", "MovieObject.bdmv"].iter()
+        .map(|name| bdmv.join(name))
+        .filter(|path| path.is_file())
+        .collect();
+    if let Some(dir) = find_dir(&bdmv, "PLAYLIST") {
+        files.extend(files_with_extension(&dir, "mpls"));
+    }
+    if let Some(dir) = find_dir(&bdmv, "CLIPINF") {
+        files.extend(files_with_extension(&dir, "clpi"));
+    }
+    Some(("bluray", files))
+}
+
+/// Find a subdirectory by name, ignoring case (some filesystems report lowercase names)
+fn find_dir(parent: &Path, name: &str) -> Option<PathBuf> {
+    std::fs::read_dir(parent).ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| path.is_dir() && path.file_name().is_some_and(|n| n.to_string_lossy().eq_ignore_ascii_case(name)))
+}
+
+fn files_with_extension(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|e| e.to_string_lossy().eq_ignore_ascii_case(extension)))
+            .collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disc_scan::TitleInfo;
+
+    fn write_dvd(root: &Path, vts_contents: &[u8]) {
+        let video_ts = root.join("VIDEO_TS");
+        std::fs::create_dir_all(&video_ts).unwrap();
+        std::fs::write(video_ts.join("VIDEO_TS.IFO"), b"DVDVIDEO-VMG").unwrap();
+        std::fs::write(video_ts.join("VTS_01_0.IFO"), vts_contents).unwrap();
+        std::fs::write(video_ts.join("VTS_01_1.VOB"), b"ignored video data").unwrap();
+    }
+
+    #[test]
+    fn test_dvd_structure_fingerprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let disc = dir.path().join("SHOW_S1_D1");
+        write_dvd(&disc, b"DVDVIDEO-VTS one");
+
+        let fingerprint = structure_fingerprint(&disc, Some("SHOW_S1_D1")).unwrap();
+        assert!(fingerprint.starts_with("dvd:"));
+        assert_eq!(structure_fingerprint(&disc, Some("SHOW_S1_D1")), Some(fingerprint.clone()));
+
+        // Video data and where the disc is mounted don't matter, navigation data and the label do
+        std::fs::write(disc.join("VIDEO_TS/VTS_01_1.VOB"), b"different").unwrap();
+        assert_eq!(structure_fingerprint(&disc, Some("SHOW_S1_D1")), Some(fingerprint.clone()));
+        let remounted = dir.path().join("SHOW_S1_D1 1");
+        std::fs::rename(&disc, &remounted).unwrap();
+        assert_eq!(structure_fingerprint(&remounted, Some("SHOW_S1_D1")), Some(fingerprint.clone()));
+        assert_ne!(structure_fingerprint(&remounted, Some("SHOW_S1_D2")), Some(fingerprint.clone()));
+        std::fs::write(remounted.join("VIDEO_TS/VTS_01_0.IFO"), b"DVDVIDEO-VTS two").unwrap();
+        assert_ne!(structure_fingerprint(&remounted, Some("SHOW_S1_D1")), Some(fingerprint));
+    }
+
+    #[test]
+    fn test_bluray_structure_fingerprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let playlist = dir.path().join("BDMV").join("PLAYLIST");
+        std::fs::create_dir_all(&playlist).unwrap();
+        std::fs::write(dir.path().join("BDMV/index.bdmv"), b"INDX0200").unwrap();
+        std::fs::write(playlist.join("00800.mpls"), b"MPLS0200").unwrap();
+
+        assert!(structure_fingerprint(dir.path(), None).unwrap().starts_with("bluray:"));
+        assert_eq!(structure_fingerprint(&dir.path().join("BDMV/PLAYLIST"), None), None);
+    }
+
+    #[test]
+    fn test_scan_fingerprint() {
+        let scan = DiscScan {
+            volume_label: Some("SHOW_S1_D1".to_string()),
+            titles: vec![TitleInfo { index: 0, duration_seconds: 1320, chapter_count: 6, ..Default::default() }],
+            ..Default::default()
+        };
+        let fingerprint = scan_fingerprint(&scan);
+        assert!(fingerprint.starts_with("scan:"));
+        assert_eq!(video_fingerprint(&scan), Some(fingerprint.clone()));
+        assert_eq!(video_fingerprint(&DiscScan::default()), None);
+
+        // Rescanning the same disc later gives the same fingerprint
+        let rescan = DiscScan { device: "/dev/sr1".to_string(), scanned_at: Some(chrono::Utc::now()), ..scan.clone() };
+        assert_eq!(scan_fingerprint(&rescan), fingerprint);
+        let other = DiscScan { volume_label: Some("SHOW_S1_D2".to_string()), ..scan };
+        assert_ne!(scan_fingerprint(&other), fingerprint);
+
+        assert_eq!(cd_fingerprint("abc-"), "musicbrainz:abc-");
+    }
+
+    #[test]
+    fn test_record_video_fingerprint() {
+        std::env::set_var("RIPLEY_TEST_DB", ":memory:");
+        let db = Database::new().unwrap();
+        let scan = DiscScan {
+            titles: vec![TitleInfo { index: 0, duration_seconds: 1320, ..Default::default() }],
+            ..Default::default()
+        };
+        let scanned = scan_fingerprint(&scan);
+
+        // Mounted: stored under the structure hash, and the scan hash finds it when the disc isn't mounted
+        assert_eq!(record_video_fingerprint(&db, Some("dvd:0123abcd"), &scan).as_deref(), Some("dvd:0123abcd"));
+        assert_eq!(record_video_fingerprint(&db, None, &scan).as_deref(), Some("dvd:0123abcd"));
+        assert_eq!(db.canonical_fingerprint(&scanned).unwrap(), "dvd:0123abcd");
+
+        // A disc never mounted is stored under its scan hash
+        let other = DiscScan { volume_label: Some("OTHER".to_string()), ..scan };
+        assert_eq!(record_video_fingerprint(&db, None, &other), Some(scan_fingerprint(&other)));
+        assert_eq!(record_video_fingerprint(&db, None, &DiscScan::default()), None);
+    }
+}
@@ -1,6 +1,7 @@
 // Library exports for testing
 pub mod api;
 pub mod app;
+pub mod bluray;
 pub mod cli;
 pub mod config;
 pub mod database;
@@ -16,4 +17,16 @@ pub mod rsync;
 pub mod speech_match;
 pub mod tui;
 pub mod checksum;
+pub mod disc_scan;
 pub mod rename;
+pub mod rip_backend;
+pub mod title_selection;
+pub mod fingerprint;
+pub mod transcript_match;
+pub mod subtitle_ocr;
+pub mod reference_subtitles;
+pub mod episode_assignment;
+pub mod rename_journal;
+pub mod naming;
+pub mod metadata_provider;
+pub mod movie;
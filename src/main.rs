@@ -6,6 +6,7 @@ mod drive;
 mod web_ui;
 mod dvd_metadata;
 mod dvd_ripper;
+mod bluray;
 mod metadata;
 mod ripper;
 mod tui;
@@ -16,6 +17,18 @@ mod rsync;
 mod speech_match;
 mod rename;
 mod checksum;
+mod disc_scan;
+mod rip_backend;
+mod title_selection;
+mod fingerprint;
+mod transcript_match;
+mod subtitle_ocr;
+mod reference_subtitles;
+mod episode_assignment;
+mod rename_journal;
+mod naming;
+mod metadata_provider;
+mod movie;
 
 use anyhow::Result;
 use cli::{Args, Command, RipArgs};
@@ -33,6 +46,9 @@ async fn main() -> Result<()> {
             title,
             skip_metadata,
             skip_filebot,
+            profile,
+            session,
+            titles,
         }) => {
             // Initialize file logging to ~/ripley.log
             let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
@@ -58,6 +74,9 @@ async fn main() -> Result<()> {
                 skip_metadata: *skip_metadata,
                 title: title.clone(),
                 skip_filebot: *skip_filebot,
+                profile: profile.clone(),
+                session_id: *session,
+                titles: Some(titles.clone()).filter(|t| !t.is_empty()),
                 quality: 5,  // Default FLAC quality for audio CDs
                 eject_when_done: true,  // Default eject behavior
             };
@@ -70,16 +89,32 @@ async fn main() -> Result<()> {
             title,
             skip_speech,
             skip_filebot,
+            dry_run,
+            order,
+            undo,
         }) => {
             eprintln!("\x1b[35m📝 Ripley Rename Tool\x1b[0m");
             eprintln!("\x1b[36m━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\x1b[0m\n");
             
+            if let Some(batch_id) = undo {
+                rename::run_undo(batch_id)?;
+                return Ok(());
+            }
+            
+            let order = match order {
+                Some(name) => Some(dvd_metadata::EpisodeOrder::parse(name)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown episode order '{}'", name))?),
+                None => None,
+            };
+            
             // Run rename command
             rename::run_rename(
                 directory.clone(),
                 title.clone(),
                 *skip_speech,
                 *skip_filebot,
+                *dry_run,
+                order,
             )
             .await?;
         }
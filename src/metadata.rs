@@ -1,10 +1,9 @@
 use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
-use tracing::{debug, info, warn};
+use tracing::{debug, warn};
 
-const MUSICBRAINZ_API: &str = "https://musicbrainz.org/ws/2";
-const USER_AGENT: &str = "Ripley/0.1.0 (https://github.com/johnny/ripley)";
+use crate::metadata_provider::MetadataProvider;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscMetadata {
@@ -24,18 +23,18 @@ pub struct Track {
 }
 
 /// Fetch metadata for a CD using its disc ID
-pub async fn fetch_metadata(disc_id: &str, retry_count: u32) -> Result<DiscMetadata> {
+pub async fn fetch_metadata(provider: &dyn MetadataProvider, disc_id: &str, retry_count: u32) -> Result<DiscMetadata> {
     let mut attempts = 0;
     let max_attempts = retry_count;
 
     while attempts < max_attempts {
         attempts += 1;
         
-        // Try MusicBrainz first
-        match fetch_from_musicbrainz(disc_id).await {
+        // Try the configured provider (MusicBrainz) first
+        match provider.lookup_disc(disc_id).await {
             Ok(metadata) => return Ok(metadata),
             Err(e) => {
-                warn!("MusicBrainz attempt {}/{} failed: {}", attempts, max_attempts, e);
+                warn!("{} attempt {}/{} failed: {}", provider.name(), attempts, max_attempts, e);
                 
                 if attempts < max_attempts {
                     // Try CDDB/freedb as fallback
@@ -52,114 +51,6 @@ pub async fn fetch_metadata(disc_id: &str, retry_count: u32) -> Result<DiscMetad
     Err(anyhow!("Failed to fetch metadata after {} attempts", max_attempts))
 }
 
-/// Fetch metadata from MusicBrainz API
-async fn fetch_from_musicbrainz(disc_id: &str) -> Result<DiscMetadata> {
-    let url = format!("{}/discid/{}", MUSICBRAINZ_API, disc_id);
-    debug!("Fetching metadata from MusicBrainz: {}", url);
-    
-    let client = reqwest::Client::builder()
-        .user_agent(USER_AGENT)
-        .build()?;
-    
-    let response = client
-        .get(&url)
-        .query(&[("inc", "artists+recordings+release-groups")])
-        .send()
-        .await
-        .context("Failed to fetch from MusicBrainz")?;
-    
-    if !response.status().is_success() {
-        return Err(anyhow!("MusicBrainz API returned status: {}", response.status()));
-    }
-    
-    let json: serde_json::Value = response.json().await?;
-    
-    // Parse MusicBrainz response
-    let release = json["releases"]
-        .as_array()
-        .and_then(|releases| releases.first())
-        .ok_or_else(|| anyhow!("No releases found"))?;
-    
-    let release_group = release["release-group"]
-        .as_object()
-        .ok_or_else(|| anyhow!("No release group found"))?;
-    
-    let artist_credits = release["artist-credit"]
-        .as_array()
-        .ok_or_else(|| anyhow!("No artist credits found"))?;
-    
-    let artist = artist_credits
-        .iter()
-        .filter_map(|credit| credit["artist"]["name"].as_str())
-        .collect::<Vec<_>>()
-        .join(", ");
-    
-    let album = release_group["title"]
-        .as_str()
-        .unwrap_or("Unknown Album")
-        .to_string();
-    
-    let year = release_group["first-release-date"]
-        .as_str()
-        .and_then(|date| date.split('-').next())
-        .map(String::from);
-    
-    let genre = release_group["primary-type"]
-        .as_str()
-        .map(String::from);
-    
-    let medium = release["media"]
-        .as_array()
-        .and_then(|media| media.first())
-        .ok_or_else(|| anyhow!("No medium found"))?;
-    
-    let tracks_data = medium["tracks"]
-        .as_array()
-        .ok_or_else(|| anyhow!("No tracks found"))?;
-    
-    let mut tracks = Vec::new();
-    for track_data in tracks_data {
-        let recording = track_data["recording"]
-            .as_object()
-            .ok_or_else(|| anyhow!("No recording data"))?;
-        
-        let number = track_data["number"]
-            .as_str()
-            .and_then(|n| n.parse::<u32>().ok())
-            .unwrap_or(0);
-        
-        let title = recording["title"]
-            .as_str()
-            .unwrap_or("Unknown Track")
-            .to_string();
-        
-        let duration = recording["length"]
-            .as_u64()
-            .map(|ms| (ms / 1000) as u32);
-        
-        tracks.push(Track {
-            number,
-            title,
-            artist: None, // MusicBrainz doesn't provide per-track artists in this structure
-            duration,
-        });
-    }
-    
-    if tracks.is_empty() {
-        return Err(anyhow!("No tracks found in release"));
-    }
-    
-    info!("Found metadata: {} - {} ({} tracks)", artist, album, tracks.len());
-    
-    Ok(DiscMetadata {
-        artist: if artist.is_empty() { "Unknown Artist".to_string() } else { artist },
-        album,
-        year,
-        genre,
-        tracks,
-    })
-}
-
 /// Fetch metadata from CDDB/freedb (fallback)
 async fn fetch_from_cddb(_disc_id: &str) -> Result<DiscMetadata> {
     // CDDB lookup would go here
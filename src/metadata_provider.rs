@@ -0,0 +1,988 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::database::Database;
+use crate::dvd_metadata::{Episode, EpisodeOrder};
+use crate::metadata::{DiscMetadata, Track};
+
+/// Boxed future returned by metadata lookups
+pub type MetadataFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+const TMDB_API_BASE: &str = "https://api.themoviedb.org/3";
+const TMDB_IMAGE_BASE: &str = "https://image.tmdb.org/t/p/w342";
+const TVDB_API_BASE: &str = "https://api4.thetvdb.com/v4";
+const MUSICBRAINZ_API: &str = "https://musicbrainz.org/ws/2";
+const USER_AGENT: &str = "Ripley/0.1.0 (https://github.com/johnny/ripley)";
+const DEFAULT_CACHE_TTL_HOURS: u64 = 168;
+
+/// A show or movie returned by a search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub id: String, // Provider's own ID, passed back to `list_episodes`
+    pub title: String,
+    pub year: Option<String>,
+    pub overview: Option<String>,
+    pub poster_url: Option<String>,
+    pub popularity: Option<f64>,
+}
+
+/// A metadata service that can find shows and movies, list a show's episodes or identify an audio CD
+///
+/// Each service implements the lookups it offers; the others fail with an error naming the service.
+pub trait MetadataProvider: Send + Sync {
+    /// Short identifier used in config files and logs
+    fn name(&self) -> &'static str;
+
+    /// Base URL the service is reached at, so lookups cached from one server aren't served for another
+    fn endpoint(&self) -> &str {
+        ""
+    }
+
+    /// Search for TV shows by name, in the service's relevance order
+    fn search_show<'a>(&'a self, _query: &'a str) -> MetadataFuture<'a, Vec<SearchResult>> {
+        unsupported(self.name(), "show search")
+    }
+
+    /// Search for movies by name, in the service's relevance order
+    fn search_movie<'a>(&'a self, _query: &'a str) -> MetadataFuture<'a, Vec<SearchResult>> {
+        unsupported(self.name(), "movie search")
+    }
+
+    /// Regular season numbers of a show, in order (specials left out)
+    fn list_seasons<'a>(&'a self, _show_id: &'a str) -> MetadataFuture<'a, Vec<u32>> {
+        unsupported(self.name(), "season lists")
+    }
+
+    /// Episodes of one season of a show, in aired order
+    fn list_episodes<'a>(&'a self, _show_id: &'a str, _season: u32) -> MetadataFuture<'a, Vec<Episode>> {
+        unsupported(self.name(), "episode lists")
+    }
+
+    /// Every episode of a show numbered in `order`; empty when the service has no such order for the show
+    fn list_episodes_in_order<'a>(&'a self, _show_id: &'a str, _order: EpisodeOrder) -> MetadataFuture<'a, Vec<Episode>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    /// Look up an audio CD by its MusicBrainz disc ID
+    fn lookup_disc<'a>(&'a self, _disc_id: &'a str) -> MetadataFuture<'a, DiscMetadata> {
+        unsupported(self.name(), "disc lookup")
+    }
+}
+
+fn unsupported<'a, T: Send + 'a>(provider: &'static str, lookup: &'static str) -> MetadataFuture<'a, T> {
+    Box::pin(async move { Err(anyhow!("{} does not support {}", provider, lookup)) })
+}
+
+/// Provider for show and movie lookups, picked by `metadata.provider` ("tmdb" when unset, or "tvdb")
+pub fn video_provider_from_config(config: &Config) -> Result<Box<dyn MetadataProvider>> {
+    match config.metadata.provider.as_deref().unwrap_or("tmdb").to_lowercase().as_str() {
+        "tmdb" => Ok(Box::new(TmdbProvider::from_config(config)?)),
+        "tvdb" | "thetvdb" => Ok(Box::new(TvdbProvider::from_config(config)?)),
+        other => Err(anyhow!("Unknown metadata provider '{}' (expected tmdb or tvdb)", other)),
+    }
+}
+
+/// Provider for audio CD lookups
+pub fn music_provider_from_config(config: &Config) -> Result<Box<dyn MetadataProvider>> {
+    Ok(Box::new(MusicBrainzProvider::from_config(config)?))
+}
+
+/// Put `provider` behind the metadata cache in `db`, keeping lookups for `metadata.cache_ttl_hours`
+///
+/// Without a database lookups go straight to the provider.
+pub fn with_cache<'a>(provider: Box<dyn MetadataProvider>, db: Option<&'a Database>, config: &Config) -> Box<dyn MetadataProvider + 'a> {
+    let Some(db) = db else {
+        return provider;
+    };
+    let ttl_hours = config.metadata.cache_ttl_hours.unwrap_or(DEFAULT_CACHE_TTL_HOURS);
+    Box::new(CachedProvider {
+        inner: provider,
+        db,
+        ttl: chrono::Duration::hours(ttl_hours as i64),
+        offline: AtomicBool::new(false),
+    })
+}
+
+/// Serves lookups from the database while they're fresh, and stale ones when the service can't be reached
+///
+/// The first network failure switches to cache-only, so a rip without network doesn't sit through a timeout
+/// per lookup; anything not cached then fails and the rip carries on as with `--skip-metadata`.
+pub struct CachedProvider<'a> {
+    inner: Box<dyn MetadataProvider>,
+    db: &'a Database,
+    ttl: chrono::Duration,
+    offline: AtomicBool,
+}
+
+impl CachedProvider<'_> {
+    /// Look up `lookup` in the cache or fetch it; results `keep` rejects (e.g. empty lists) aren't stored
+    async fn cached<T>(&self, lookup: String, fetch: MetadataFuture<'_, T>, keep: fn(&T) -> bool) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let provider = self.inner.name();
+        let cache_key = format!("{}:{}:{}", provider, self.inner.endpoint(), lookup);
+        let entry = self.db.get_cached_metadata(provider, &cache_key).unwrap_or_else(|e| {
+            warn!("Metadata cache unavailable: {}", e);
+            None
+        });
+        let stale = match entry {
+            Some(entry) => {
+                let fresh = Utc::now() - entry.fetched_at < self.ttl;
+                match serde_json::from_str::<T>(&entry.value_json) {
+                    Ok(value) if fresh => {
+                        debug!("Metadata cache hit: {} {}", provider, cache_key);
+                        return Ok(value);
+                    }
+                    Ok(value) => Some(value),
+                    Err(_) => None,
+                }
+            }
+            None => None,
+        };
+
+        if self.offline.load(Ordering::Relaxed) {
+            return stale.ok_or_else(|| anyhow!("{} is offline and {} isn't cached", provider, cache_key));
+        }
+
+        match fetch.await {
+            Ok(value) if !keep(&value) => Ok(value),
+            Ok(value) => {
+                let stored = serde_json::to_string(&value).map_err(anyhow::Error::from)
+                    .and_then(|json| self.db.put_cached_metadata(provider, &cache_key, &json));
+                if let Err(e) = stored {
+                    warn!("Failed to cache {} {}: {}", provider, cache_key, e);
+                }
+                Ok(value)
+            }
+            Err(e) if is_network_error(&e) => {
+                warn!("⚠️  {} unreachable, using cached metadata only: {}", provider, e);
+                self.offline.store(true, Ordering::Relaxed);
+                stale.ok_or(e)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl MetadataProvider for CachedProvider<'_> {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn endpoint(&self) -> &str {
+        self.inner.endpoint()
+    }
+
+    // Empty lists aren't cached, so a show or season that isn't listed yet is looked up again next time
+    fn search_show<'a>(&'a self, query: &'a str) -> MetadataFuture<'a, Vec<SearchResult>> {
+        Box::pin(self.cached(format!("search_show:{}", query.to_lowercase()), self.inner.search_show(query), |r| !r.is_empty()))
+    }
+
+    fn search_movie<'a>(&'a self, query: &'a str) -> MetadataFuture<'a, Vec<SearchResult>> {
+        Box::pin(self.cached(format!("search_movie:{}", query.to_lowercase()), self.inner.search_movie(query), |r| !r.is_empty()))
+    }
+
+    fn list_seasons<'a>(&'a self, show_id: &'a str) -> MetadataFuture<'a, Vec<u32>> {
+        Box::pin(self.cached(format!("seasons:{}", show_id), self.inner.list_seasons(show_id), |s| !s.is_empty()))
+    }
+
+    fn list_episodes<'a>(&'a self, show_id: &'a str, season: u32) -> MetadataFuture<'a, Vec<Episode>> {
+        Box::pin(self.cached(format!("episodes:{}:{}", show_id, season), self.inner.list_episodes(show_id, season), |e| !e.is_empty()))
+    }
+
+    fn list_episodes_in_order<'a>(&'a self, show_id: &'a str, order: EpisodeOrder) -> MetadataFuture<'a, Vec<Episode>> {
+        Box::pin(self.cached(format!("order:{}:{:?}", show_id, order), self.inner.list_episodes_in_order(show_id, order), |e| !e.is_empty()))
+    }
+
+    fn lookup_disc<'a>(&'a self, disc_id: &'a str) -> MetadataFuture<'a, DiscMetadata> {
+        Box::pin(self.cached(format!("disc:{}", disc_id), self.inner.lookup_disc(disc_id), |_| true))
+    }
+}
+
+/// Whether a lookup failed because the service couldn't be reached at all
+fn is_network_error(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| {
+        cause.downcast_ref::<reqwest::Error>().is_some_and(|e| e.is_connect() || e.is_timeout())
+    })
+}
+
+fn base_url(configured: &Option<String>, default: &str) -> String {
+    configured.as_deref().unwrap_or(default).trim_end_matches('/').to_string()
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(10))
+        .build()?)
+}
+
+/// Year from a "YYYY-MM-DD" date
+fn year_of(date: &serde_json::Value) -> Option<String> {
+    date.as_str()
+        .and_then(|d| d.split('-').next())
+        .filter(|y| !y.is_empty())
+        .map(String::from)
+}
+
+fn non_empty(value: &serde_json::Value) -> Option<String> {
+    value.as_str().map(|s| s.to_string()).filter(|s| !s.is_empty())
+}
+
+/// The Movie Database (themoviedb.org)
+pub struct TmdbProvider {
+    base_url: String,
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl TmdbProvider {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(TmdbProvider {
+            base_url: base_url(&config.metadata.tmdb.base_url, TMDB_API_BASE),
+            api_key: config.get_tmdb_api_key()
+                .ok_or_else(|| anyhow!("TMDB needs an API key: set metadata.tmdb.api_key (or tmdb_api_key) in config.yaml"))?,
+            client: http_client()?,
+        })
+    }
+
+    async fn get(&self, path: &str, query: &[(&str, &str)]) -> Result<serde_json::Value> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("TMDB request: {}", url);
+        let response = self.client.get(&url)
+            .query(&[("api_key", self.api_key.as_str())])
+            .query(query)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach TMDB at {}", self.base_url))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("TMDB returned status {} for {}", response.status(), path));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Search results; shows and movies name their title and date fields differently
+    fn search_results(data: &serde_json::Value, title_field: &str, date_field: &str) -> Vec<SearchResult> {
+        data["results"].as_array()
+            .map(|results| results.iter()
+                .filter_map(|r| Some(SearchResult {
+                    id: r["id"].as_i64()?.to_string(),
+                    title: r[title_field].as_str()?.to_string(),
+                    year: year_of(&r[date_field]),
+                    overview: non_empty(&r["overview"]),
+                    poster_url: r["poster_path"].as_str().map(|p| format!("{}{}", TMDB_IMAGE_BASE, p)),
+                    popularity: r["popularity"].as_f64(),
+                }))
+                .collect())
+            .unwrap_or_default()
+    }
+}
+
+impl MetadataProvider for TmdbProvider {
+    fn name(&self) -> &'static str {
+        "tmdb"
+    }
+
+    fn endpoint(&self) -> &str {
+        &self.base_url
+    }
+
+    fn search_show<'a>(&'a self, query: &'a str) -> MetadataFuture<'a, Vec<SearchResult>> {
+        Box::pin(async move {
+            let data = self.get("/search/tv", &[("query", query)]).await?;
+            Ok(Self::search_results(&data, "name", "first_air_date"))
+        })
+    }
+
+    fn search_movie<'a>(&'a self, query: &'a str) -> MetadataFuture<'a, Vec<SearchResult>> {
+        Box::pin(async move {
+            let data = self.get("/search/movie", &[("query", query)]).await?;
+            Ok(Self::search_results(&data, "title", "release_date"))
+        })
+    }
+
+    fn list_seasons<'a>(&'a self, show_id: &'a str) -> MetadataFuture<'a, Vec<u32>> {
+        Box::pin(async move {
+            let data = self.get(&format!("/tv/{}", show_id), &[]).await?;
+            Ok(regular_seasons(data["seasons"].as_array().into_iter().flatten(), "season_number"))
+        })
+    }
+
+    fn list_episodes<'a>(&'a self, show_id: &'a str, season: u32) -> MetadataFuture<'a, Vec<Episode>> {
+        Box::pin(async move {
+            let data = self.get(&format!("/tv/{}/season/{}", show_id, season), &[]).await?;
+            let episode_list = data["episodes"].as_array()
+                .ok_or_else(|| anyhow!("No episodes"))?;
+
+            let episodes: Vec<Episode> = episode_list.iter().enumerate()
+                .map(|(idx, ep)| Episode {
+                    season,
+                    episode: ep["episode_number"].as_u64().unwrap_or((idx + 1) as u64) as u32,
+                    title: ep["name"].as_str().unwrap_or("Unknown Episode").to_string(),
+                    title_index: idx as u32, // Will be updated by duration matching
+                    runtime_minutes: ep["runtime"].as_u64().map(|r| r as u32),
+                    overview: non_empty(&ep["overview"]),
+                })
+                .collect();
+            info!("Found {} episodes for season {}", episodes.len(), season);
+            Ok(episodes)
+        })
+    }
+
+    /// Renumbered by the show's TMDB episode group for `order`
+    fn list_episodes_in_order<'a>(&'a self, show_id: &'a str, order: EpisodeOrder) -> MetadataFuture<'a, Vec<Episode>> {
+        Box::pin(async move {
+            if order.tmdb_group_type().is_none() {
+                return Ok(Vec::new());
+            }
+            let groups = self.get(&format!("/tv/{}/episode_groups", show_id), &[]).await?;
+            let Some(group_id) = pick_episode_group(&groups, order) else {
+                return Ok(Vec::new());
+            };
+            let group = self.get(&format!("/tv/episode_group/{}", group_id), &[]).await?;
+            Ok(episodes_from_group(&group))
+        })
+    }
+}
+
+/// Sorted, de-duplicated season numbers from a list of season objects, without season 0 (specials)
+fn regular_seasons<'a>(seasons: impl Iterator<Item = &'a serde_json::Value>, number_field: &str) -> Vec<u32> {
+    let mut numbers: Vec<u32> = seasons
+        .filter_map(|season| season[number_field].as_u64())
+        .filter(|&number| number > 0)
+        .map(|number| number as u32)
+        .collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+}
+
+/// ID of the episode group for `order` (the most complete one when a show has several)
+fn pick_episode_group(groups: &serde_json::Value, order: EpisodeOrder) -> Option<String> {
+    let group_type = order.tmdb_group_type()?;
+    groups["results"].as_array()?
+        .iter()
+        .filter(|g| g["type"].as_u64() == Some(group_type))
+        .max_by_key(|g| g["episode_count"].as_u64().unwrap_or(0))
+        .and_then(|g| g["id"].as_str().map(String::from))
+}
+
+/// Episodes of an episode group: each group is a season, episodes numbered by position
+///
+/// A group's `order` is often 0-based, so seasons come from the group names ("Season 3"), falling back to
+/// their 1-based position; groups named as specials are season 0.
+fn episodes_from_group(group: &serde_json::Value) -> Vec<Episode> {
+    let mut seasons: Vec<&serde_json::Value> = group["groups"].as_array()
+        .map(|g| g.iter().collect())
+        .unwrap_or_default();
+    seasons.sort_by_key(|g| g["order"].as_u64().unwrap_or(0));
+
+    let mut episodes = Vec::new();
+    let mut position = 0;
+    for season in seasons {
+        let name = season["name"].as_str().unwrap_or_default();
+        let season_number = if is_specials_group(name) {
+            0
+        } else {
+            position += 1;
+            season_number_from_name(name).unwrap_or(position)
+        };
+        let mut group_episodes: Vec<&serde_json::Value> = season["episodes"].as_array()
+            .map(|e| e.iter().collect())
+            .unwrap_or_default();
+        group_episodes.sort_by_key(|ep| ep["order"].as_u64().unwrap_or(0));
+
+        for (idx, ep) in group_episodes.iter().enumerate() {
+            episodes.push(Episode {
+                season: season_number,
+                episode: idx as u32 + 1,
+                title: ep["name"].as_str().unwrap_or("Unknown Episode").to_string(),
+                title_index: idx as u32, // Will be updated by duration matching
+                runtime_minutes: ep["runtime"].as_u64().map(|r| r as u32),
+                overview: non_empty(&ep["overview"]),
+            });
+        }
+    }
+    episodes
+}
+
+fn is_specials_group(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.contains("special") || name.contains("extra")
+}
+
+/// The season number in a group name such as "Season 3" or "Series 2"
+fn season_number_from_name(name: &str) -> Option<u32> {
+    let re = regex::Regex::new(r"(?i)\b(?:season|series|staffel|saison|temporada)\s*(\d+)").unwrap();
+    re.captures(name)?[1].parse().ok()
+}
+
+/// TheTVDB (v4 API); needs a project API key, and a subscriber PIN for user-supported keys
+pub struct TvdbProvider {
+    base_url: String,
+    api_key: String,
+    pin: Option<String>,
+    client: reqwest::Client,
+    token: OnceCell<String>, // Bearer token from /login, valid for a month
+}
+
+impl TvdbProvider {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let tvdb = &config.metadata.tvdb;
+        Ok(TvdbProvider {
+            base_url: base_url(&tvdb.base_url, TVDB_API_BASE),
+            api_key: tvdb.api_key.clone()
+                .ok_or_else(|| anyhow!("TheTVDB needs metadata.tvdb.api_key in config.yaml"))?,
+            pin: tvdb.pin.clone(),
+            client: http_client()?,
+            token: OnceCell::new(),
+        })
+    }
+
+    async fn token(&self) -> Result<&str> {
+        let token = self.token.get_or_try_init(|| async {
+            let mut body = serde_json::json!({ "apikey": self.api_key });
+            if let Some(ref pin) = self.pin {
+                body["pin"] = serde_json::json!(pin);
+            }
+            let response = self.client.post(format!("{}/login", self.base_url))
+                .json(&body)
+                .send()
+                .await
+                .with_context(|| format!("Failed to reach TheTVDB at {}", self.base_url))?;
+            if !response.status().is_success() {
+                return Err(anyhow!("TheTVDB login failed: {}", response.status()));
+            }
+            let json: serde_json::Value = response.json().await?;
+            json["data"]["token"].as_str()
+                .map(String::from)
+                .ok_or_else(|| anyhow!("TheTVDB login returned no token"))
+        }).await?;
+        Ok(token)
+    }
+
+    async fn get(&self, path: &str, query: &[(&str, &str)]) -> Result<serde_json::Value> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("TheTVDB request: {}", url);
+        let token = self.token().await?;
+        let response = self.client.get(&url)
+            .bearer_auth(token)
+            .query(query)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach TheTVDB at {}", self.base_url))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("TheTVDB returned status {} for {}", response.status(), path));
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn search(&self, query: &str, kind: &str) -> Result<Vec<SearchResult>> {
+        let data = self.get("/search", &[("query", query), ("type", kind)]).await?;
+        Ok(data["data"].as_array()
+            .map(|results| results.iter()
+                .filter_map(|r| Some(SearchResult {
+                    id: r["tvdb_id"].as_str()?.to_string(),
+                    title: r["name"].as_str()?.to_string(),
+                    year: non_empty(&r["year"]),
+                    overview: non_empty(&r["overview"]),
+                    poster_url: non_empty(&r["image_url"]),
+                    popularity: None,
+                }))
+                .collect())
+            .unwrap_or_default())
+    }
+
+    /// Episodes from a `/series/{id}/episodes/{season-type}` response
+    fn episodes(data: &serde_json::Value) -> Vec<Episode> {
+        data["data"]["episodes"].as_array()
+            .map(|episodes| episodes.iter().enumerate()
+                .filter_map(|(idx, ep)| Some(Episode {
+                    season: ep["seasonNumber"].as_u64()? as u32,
+                    episode: ep["number"].as_u64()? as u32,
+                    title: ep["name"].as_str().unwrap_or("Unknown Episode").to_string(),
+                    title_index: idx as u32, // Will be updated by duration matching
+                    runtime_minutes: ep["runtime"].as_u64().map(|r| r as u32),
+                    overview: non_empty(&ep["overview"]),
+                }))
+                .collect())
+            .unwrap_or_default()
+    }
+}
+
+impl MetadataProvider for TvdbProvider {
+    fn name(&self) -> &'static str {
+        "tvdb"
+    }
+
+    fn endpoint(&self) -> &str {
+        &self.base_url
+    }
+
+    fn search_show<'a>(&'a self, query: &'a str) -> MetadataFuture<'a, Vec<SearchResult>> {
+        Box::pin(self.search(query, "series"))
+    }
+
+    fn search_movie<'a>(&'a self, query: &'a str) -> MetadataFuture<'a, Vec<SearchResult>> {
+        Box::pin(self.search(query, "movie"))
+    }
+
+    /// Seasons of the show's aired ("official") season type
+    fn list_seasons<'a>(&'a self, show_id: &'a str) -> MetadataFuture<'a, Vec<u32>> {
+        Box::pin(async move {
+            let data = self.get(&format!("/series/{}/extended", show_id), &[("short", "true")]).await?;
+            let official = data["data"]["seasons"].as_array().into_iter().flatten()
+                .filter(|season| season["type"]["type"].as_str().is_none_or(|t| t == "official"));
+            Ok(regular_seasons(official, "number"))
+        })
+    }
+
+    fn list_episodes<'a>(&'a self, show_id: &'a str, season: u32) -> MetadataFuture<'a, Vec<Episode>> {
+        Box::pin(async move {
+            let season_param = season.to_string();
+            let path = format!("/series/{}/episodes/default", show_id);
+            let data = self.get(&path, &[("season", season_param.as_str()), ("page", "0")]).await?;
+            let episodes: Vec<Episode> = Self::episodes(&data).into_iter()
+                .filter(|ep| ep.season == season)
+                .collect();
+            info!("Found {} episodes for season {}", episodes.len(), season);
+            Ok(episodes)
+        })
+    }
+
+    /// TheTVDB keeps DVD and absolute numbering as season types of their own
+    fn list_episodes_in_order<'a>(&'a self, show_id: &'a str, order: EpisodeOrder) -> MetadataFuture<'a, Vec<Episode>> {
+        Box::pin(async move {
+            let season_type = match order {
+                EpisodeOrder::Dvd => "dvd",
+                EpisodeOrder::Absolute => "absolute",
+                _ => return Ok(Vec::new()),
+            };
+            let data = self.get(&format!("/series/{}/episodes/{}", show_id, season_type), &[("page", "0")]).await?;
+            Ok(Self::episodes(&data))
+        })
+    }
+}
+
+/// MusicBrainz, for audio CDs
+pub struct MusicBrainzProvider {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl MusicBrainzProvider {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(MusicBrainzProvider {
+            base_url: base_url(&config.metadata.musicbrainz.base_url, MUSICBRAINZ_API),
+            client: reqwest::Client::builder()
+                .user_agent(USER_AGENT)
+                .build()?,
+        })
+    }
+}
+
+impl MetadataProvider for MusicBrainzProvider {
+    fn name(&self) -> &'static str {
+        "musicbrainz"
+    }
+
+    fn endpoint(&self) -> &str {
+        &self.base_url
+    }
+
+    fn lookup_disc<'a>(&'a self, disc_id: &'a str) -> MetadataFuture<'a, DiscMetadata> {
+        Box::pin(async move {
+            let url = format!("{}/discid/{}", self.base_url, disc_id);
+            debug!("Fetching metadata from MusicBrainz: {}", url);
+
+            let response = self.client
+                .get(&url)
+                .query(&[("inc", "artists+recordings+release-groups")])
+                .send()
+                .await
+                .context("Failed to fetch from MusicBrainz")?;
+
+            if !response.status().is_success() {
+                return Err(anyhow!("MusicBrainz API returned status: {}", response.status()));
+            }
+
+            let json: serde_json::Value = response.json().await?;
+            disc_from_release(&json)
+        })
+    }
+}
+
+/// Album and tracks from the first release in a MusicBrainz discid response
+fn disc_from_release(json: &serde_json::Value) -> Result<DiscMetadata> {
+    let release = json["releases"]
+        .as_array()
+        .and_then(|releases| releases.first())
+        .ok_or_else(|| anyhow!("No releases found"))?;
+
+    let release_group = release["release-group"]
+        .as_object()
+        .ok_or_else(|| anyhow!("No release group found"))?;
+
+    let artist_credits = release["artist-credit"]
+        .as_array()
+        .ok_or_else(|| anyhow!("No artist credits found"))?;
+
+    let artist = artist_credits
+        .iter()
+        .filter_map(|credit| credit["artist"]["name"].as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let album = release_group["title"]
+        .as_str()
+        .unwrap_or("Unknown Album")
+        .to_string();
+
+    let year = release_group["first-release-date"]
+        .as_str()
+        .and_then(|date| date.split('-').next())
+        .map(String::from);
+
+    let genre = release_group["primary-type"]
+        .as_str()
+        .map(String::from);
+
+    let medium = release["media"]
+        .as_array()
+        .and_then(|media| media.first())
+        .ok_or_else(|| anyhow!("No medium found"))?;
+
+    let tracks_data = medium["tracks"]
+        .as_array()
+        .ok_or_else(|| anyhow!("No tracks found"))?;
+
+    let mut tracks = Vec::new();
+    for track_data in tracks_data {
+        let recording = track_data["recording"]
+            .as_object()
+            .ok_or_else(|| anyhow!("No recording data"))?;
+
+        let number = track_data["number"]
+            .as_str()
+            .and_then(|n| n.parse::<u32>().ok())
+            .unwrap_or(0);
+
+        let title = recording["title"]
+            .as_str()
+            .unwrap_or("Unknown Track")
+            .to_string();
+
+        let duration = recording["length"]
+            .as_u64()
+            .map(|ms| (ms / 1000) as u32);
+
+        tracks.push(Track {
+            number,
+            title,
+            artist: None, // MusicBrainz doesn't provide per-track artists in this structure
+            duration,
+        });
+    }
+
+    if tracks.is_empty() {
+        return Err(anyhow!("No tracks found in release"));
+    }
+
+    info!("Found metadata: {} - {} ({} tracks)", artist, album, tracks.len());
+
+    Ok(DiscMetadata {
+        artist: if artist.is_empty() { "Unknown Artist".to_string() } else { artist },
+        album,
+        year,
+        genre,
+        tracks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::Query, routing::{get, post}, Json, Router};
+    use std::collections::HashMap;
+
+    /// Serve `app` on a local port and return its address
+    async fn serve(app: Router) -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        addr
+    }
+
+    #[test]
+    fn test_provider_from_config() {
+        let mut config = Config::default();
+        let err = video_provider_from_config(&config).err().unwrap();
+        assert!(err.to_string().contains("metadata.tmdb.api_key"), "{}", err); // No built-in key
+        config.tmdb_api_key = Some("key".to_string());
+        assert_eq!(video_provider_from_config(&config).unwrap().name(), "tmdb");
+        assert_eq!(music_provider_from_config(&config).unwrap().name(), "musicbrainz");
+
+        config.metadata.provider = Some("tvdb".to_string());
+        assert!(video_provider_from_config(&config).is_err()); // No API key
+        config.metadata.tvdb.api_key = Some("key".to_string());
+        assert_eq!(video_provider_from_config(&config).unwrap().name(), "tvdb");
+
+        config.metadata.provider = Some("imdb".to_string());
+        assert!(video_provider_from_config(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_lookups() {
+        let provider = MusicBrainzProvider::from_config(&Config::default()).unwrap();
+        let err = provider.search_show("Firefly").await.unwrap_err();
+        assert_eq!(err.to_string(), "musicbrainz does not support show search");
+        assert!(provider.list_episodes_in_order("1", EpisodeOrder::Dvd).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tmdb_against_stub_server() {
+        let app = Router::new()
+            .route("/3/search/tv", get(|axum::extract::Query(q): axum::extract::Query<std::collections::HashMap<String, String>>| async move {
+                assert_eq!(q.get("api_key").map(String::as_str), Some("local_key"));
+                Json(serde_json::json!({ "results": [{
+                    "id": 1437, "name": q["query"], "first_air_date": "2002-09-20",
+                    "overview": "Space western.", "poster_path": "/firefly.jpg", "popularity": 52.1
+                }]}))
+            }))
+            .route("/3/tv/1437", get(|| async {
+                Json(serde_json::json!({ "seasons": [
+                    { "season_number": 0, "name": "Specials" },
+                    { "season_number": 1, "name": "Season 1" },
+                ]}))
+            }))
+            .route("/3/tv/1437/season/1", get(|| async {
+                Json(serde_json::json!({ "episodes": [
+                    { "episode_number": 1, "name": "Serenity", "runtime": 86, "overview": "" },
+                    { "episode_number": 2, "name": "The Train Job", "runtime": 43 },
+                ]}))
+            }));
+        let addr = serve(app).await;
+
+        let mut config = Config::default();
+        config.metadata.tmdb.base_url = Some(format!("http://{}/3/", addr));
+        config.metadata.tmdb.api_key = Some("local_key".to_string());
+        let provider = TmdbProvider::from_config(&config).unwrap();
+
+        let results = provider.search_show("Firefly").await.unwrap();
+        assert_eq!(results, vec![SearchResult {
+            id: "1437".to_string(),
+            title: "Firefly".to_string(),
+            year: Some("2002".to_string()),
+            overview: Some("Space western.".to_string()),
+            poster_url: Some(format!("{}/firefly.jpg", TMDB_IMAGE_BASE)),
+            popularity: Some(52.1),
+        }]);
+
+        assert_eq!(provider.list_seasons("1437").await.unwrap(), vec![1]);
+        let episodes = provider.list_episodes("1437", 1).await.unwrap();
+        assert_eq!(episodes.len(), 2);
+        assert_eq!((episodes[1].episode, episodes[1].title.as_str()), (2, "The Train Job"));
+        assert_eq!(episodes[0].overview, None);
+        assert!(provider.list_episodes("1437", 2).await.is_err());
+        assert!(provider.lookup_disc("abc").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tvdb_logs_in_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let logins = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&logins);
+        let app = Router::new()
+            .route("/v4/login", post(move |Json(body): Json<serde_json::Value>| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    assert_eq!((body["apikey"].as_str(), body["pin"].as_str()), (Some("key"), Some("1234")));
+                    Json(serde_json::json!({ "status": "success", "data": { "token": "tok" } }))
+                }
+            }))
+            .route("/v4/search", get(|headers: axum::http::HeaderMap| async move {
+                assert_eq!(headers["authorization"], "Bearer tok");
+                Json(serde_json::json!({ "data": [
+                    { "tvdb_id": "78874", "name": "Firefly", "year": "2002", "image_url": "http://img/firefly.jpg" },
+                ]}))
+            }))
+            .route("/v4/series/78874/extended", get(|| async {
+                Json(serde_json::json!({ "data": { "seasons": [
+                    { "number": 1, "type": { "type": "official" } },
+                    { "number": 1, "type": { "type": "dvd" } },
+                    { "number": 0, "type": { "type": "official" } },
+                ]}}))
+            }))
+            .route("/v4/series/78874/episodes/dvd", get(|| async {
+                Json(serde_json::json!({ "data": { "episodes": [
+                    { "seasonNumber": 1, "number": 1, "name": "Serenity", "runtime": 86 },
+                    { "seasonNumber": 1, "number": 2, "name": "The Train Job", "runtime": 43 },
+                ]}}))
+            }));
+        let addr = serve(app).await;
+
+        let mut config = Config::default();
+        config.metadata.tvdb.base_url = Some(format!("http://{}/v4", addr));
+        config.metadata.tvdb.api_key = Some("key".to_string());
+        config.metadata.tvdb.pin = Some("1234".to_string());
+        let provider = TvdbProvider::from_config(&config).unwrap();
+
+        let results = provider.search_show("Firefly").await.unwrap();
+        assert_eq!((results[0].id.as_str(), results[0].year.as_deref()), ("78874", Some("2002")));
+        assert_eq!(results[0].poster_url.as_deref(), Some("http://img/firefly.jpg"));
+
+        assert_eq!(provider.list_seasons("78874").await.unwrap(), vec![1]);
+        let dvd = provider.list_episodes_in_order("78874", EpisodeOrder::Dvd).await.unwrap();
+        assert_eq!(dvd.iter().map(|e| e.title.as_str()).collect::<Vec<_>>(), vec!["Serenity", "The Train Job"]);
+        assert!(provider.list_episodes_in_order("78874", EpisodeOrder::StoryArc).await.unwrap().is_empty());
+        assert_eq!(logins.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_musicbrainz_against_stub_server() {
+        let app = Router::new().route("/ws/2/discid/abc-", get(|| async {
+            Json(serde_json::json!({ "releases": [{
+                "artist-credit": [{ "artist": { "name": "Daft Punk" } }],
+                "release-group": { "title": "Discovery", "first-release-date": "2001-03-12", "primary-type": "Album" },
+                "media": [{ "tracks": [
+                    { "number": "1", "recording": { "title": "One More Time", "length": 320000 } },
+                ]}],
+            }]}))
+        }));
+        let addr = serve(app).await;
+
+        let mut config = Config::default();
+        config.metadata.musicbrainz.base_url = Some(format!("http://{}/ws/2", addr));
+        let provider = music_provider_from_config(&config).unwrap();
+        let disc = provider.lookup_disc("abc-").await.unwrap();
+        assert_eq!((disc.artist.as_str(), disc.album.as_str(), disc.year.as_deref()), ("Daft Punk", "Discovery", Some("2001")));
+        assert_eq!(disc.tracks[0].duration, Some(320));
+        assert!(provider.lookup_disc("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cache_serves_repeat_and_offline_lookups() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        std::env::set_var("RIPLEY_TEST_DB", ":memory:");
+        let db = Database::new().unwrap();
+        let searches = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&searches);
+        let app = Router::new().route("/3/search/tv", get(move |Query(params): Query<HashMap<String, String>>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let results = if params["query"] == "Firefly" { serde_json::json!([{ "id": 1437, "name": "Firefly" }]) } else { serde_json::json!([]) };
+            async move { Json(serde_json::json!({ "results": results })) }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut config = Config::default();
+        config.metadata.tmdb.base_url = Some(format!("http://{}/3", addr));
+        config.metadata.tmdb.api_key = Some("local_key".to_string());
+        let provider = with_cache(video_provider_from_config(&config).unwrap(), Some(&db), &config);
+        assert_eq!(provider.name(), "tmdb");
+        assert_eq!(provider.search_show("Firefly").await.unwrap()[0].id, "1437");
+        assert_eq!(provider.search_show("firefly").await.unwrap()[0].id, "1437");
+        assert_eq!(searches.load(Ordering::SeqCst), 1);
+
+        // Expired entries are fetched again
+        config.metadata.cache_ttl_hours = Some(0);
+        let provider = with_cache(video_provider_from_config(&config).unwrap(), Some(&db), &config);
+        provider.search_show("Firefly").await.unwrap();
+        assert_eq!(searches.load(Ordering::SeqCst), 2);
+
+        // Empty results aren't cached
+        provider.search_show("Fireflies").await.unwrap();
+        provider.search_show("Fireflies").await.unwrap();
+        assert_eq!(searches.load(Ordering::SeqCst), 4);
+
+        // Another server for the same provider has its own cache entries
+        config.metadata.cache_ttl_hours = None;
+        let other = serve(Router::new().route("/3/search/tv", get(|| async {
+            Json(serde_json::json!({ "results": [{ "id": 1, "name": "Other Firefly" }] }))
+        }))).await;
+        let mut other_config = config.clone();
+        other_config.metadata.tmdb.base_url = Some(format!("http://{}/3", other));
+        let provider = with_cache(video_provider_from_config(&other_config).unwrap(), Some(&db), &other_config);
+        assert_eq!(provider.search_show("Firefly").await.unwrap()[0].title, "Other Firefly");
+
+        // Server gone: the stale entry is served, and uncached lookups fail without trying again
+        server.abort();
+        let _ = server.await;
+        config.metadata.cache_ttl_hours = Some(0);
+        let provider = with_cache(video_provider_from_config(&config).unwrap(), Some(&db), &config);
+        assert_eq!(provider.search_show("Firefly").await.unwrap()[0].title, "Firefly");
+        let err = provider.search_movie("Serenity").await.unwrap_err();
+        assert!(err.to_string().contains("offline"), "{}", err);
+    }
+
+    #[test]
+    fn test_episode_groups() {
+        let groups = serde_json::json!({
+            "results": [
+                { "id": "aaa", "type": 3, "episode_count": 20, "name": "DVD Order (partial)" },
+                { "id": "bbb", "type": 3, "episode_count": 72, "name": "DVD Order" },
+                { "id": "ccc", "type": 2, "episode_count": 72, "name": "Absolute" },
+            ]
+        });
+        assert_eq!(pick_episode_group(&groups, EpisodeOrder::Dvd).as_deref(), Some("bbb"));
+        assert_eq!(pick_episode_group(&groups, EpisodeOrder::Absolute).as_deref(), Some("ccc"));
+        assert_eq!(pick_episode_group(&groups, EpisodeOrder::StoryArc), None);
+        assert_eq!(pick_episode_group(&groups, EpisodeOrder::Aired), None);
+
+        // Firefly on DVD: the pilot "Serenity" comes first, unlike its air order
+        let group = serde_json::json!({
+            "groups": [
+                { "name": "Season 1", "order": 1, "episodes": [
+                    { "name": "The Train Job", "order": 1, "season_number": 1, "episode_number": 1, "runtime": 43 },
+                    { "name": "Serenity", "order": 0, "season_number": 1, "episode_number": 11, "runtime": 86, "overview": "The pilot." },
+                ]},
+                { "name": "Specials", "order": 0, "episodes": [
+                    { "name": "Here's How It Was", "order": 0, "season_number": 0, "episode_number": 1 },
+                ]},
+            ]
+        });
+        let episodes = episodes_from_group(&group);
+        let numbered: Vec<(u32, u32, &str)> = episodes.iter().map(|e| (e.season, e.episode, e.title.as_str())).collect();
+        assert_eq!(numbered, vec![(0, 1, "Here's How It Was"), (1, 1, "Serenity"), (1, 2, "The Train Job")]);
+        assert_eq!(episodes[1].runtime_minutes, Some(86));
+        assert_eq!(episodes[1].overview.as_deref(), Some("The pilot."));
+
+        // 0-based orders: unnamed groups are numbered by position, specials by name
+        let group = serde_json::json!({
+            "groups": [
+                { "name": "Volume One", "order": 0, "episodes": [
+                    { "name": "Serenity", "order": 0 },
+                ]},
+                { "name": "Volume Two", "order": 1, "episodes": [
+                    { "name": "Objects in Space", "order": 0 },
+                ]},
+                { "name": "Specials", "order": 2, "episodes": [
+                    { "name": "Here's How It Was", "order": 0 },
+                ]},
+            ]
+        });
+        let numbered: Vec<(u32, u32)> = episodes_from_group(&group).iter().map(|e| (e.season, e.episode)).collect();
+        assert_eq!(numbered, vec![(1, 1), (2, 1), (0, 1)]);
+
+        let group = serde_json::json!({
+            "groups": [
+                { "name": "Season 2 (DVD)", "order": 0, "episodes": [{ "name": "Bushwhacked", "order": 0 }]},
+            ]
+        });
+        assert_eq!(episodes_from_group(&group)[0].season, 2);
+    }
+}
@@ -0,0 +1,368 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::bluray::{find_main_feature, MAIN_FEATURE_SLACK};
+use crate::config::Config;
+use crate::disc_scan::TitleInfo;
+use crate::dvd_metadata::DvdMetadata;
+use crate::dvd_ripper::find_redundant_titles;
+use crate::naming::{self, MediaKind, NameFields};
+use crate::title_selection::{SelectedTitles, TitleSelection};
+
+// Movie discs rip the main feature instead of episode-length titles, plus any other cuts of it and, when the
+// selection asks for them, the extras. Cut candidates are found by length: each feature-length title clearly
+// shorter than the last one found (picked like the main feature, so obfuscation decoys of a cut are skipped
+// too). Length alone can't tell a cut from a long extra like a documentary, so a candidate is only a cut when
+// it also shares the main feature's segments, is another angle of it or has its audio tracks; the rest are
+// ripped for review. With several cuts the longest is the director's cut and the shortest the theatrical one.
+// Extras go in the Plex/Jellyfin extras folder their title name suggests, featurettes when it suggests nothing.
+
+/// Shortest cut, as a share (percent) of the main feature's length
+const EDITION_MIN_PERCENT: u32 = 80;
+
+/// Extras folder feature-length titles that may not be cuts are filed in until someone sorts them
+const REVIEW_FOLDER: &str = "Other";
+
+/// Plex/Jellyfin extras folder an extra is filed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtraKind {
+    BehindTheScenes,
+    DeletedScenes,
+    Featurettes,
+    Interviews,
+    Trailers,
+}
+
+impl ExtraKind {
+    /// Guess from a title's name
+    pub fn from_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        if name.contains("behind") || name.contains("making") {
+            ExtraKind::BehindTheScenes
+        } else if name.contains("deleted") {
+            ExtraKind::DeletedScenes
+        } else if name.contains("interview") {
+            ExtraKind::Interviews
+        } else if name.contains("trailer") || name.contains("teaser") {
+            ExtraKind::Trailers
+        } else {
+            ExtraKind::Featurettes
+        }
+    }
+
+    pub fn folder(self) -> &'static str {
+        match self {
+            ExtraKind::BehindTheScenes => "Behind The Scenes",
+            ExtraKind::DeletedScenes => "Deleted Scenes",
+            ExtraKind::Featurettes => "Featurettes",
+            ExtraKind::Interviews => "Interviews",
+            ExtraKind::Trailers => "Trailers",
+        }
+    }
+}
+
+/// What a title ripped from a movie disc is
+#[derive(Debug, Clone, PartialEq)]
+pub enum MovieTitle {
+    Feature { edition: Option<String> }, // Edition only when the disc has several cuts
+    Extra { kind: ExtraKind, name: String },
+    Review { name: String }, // Feature-length, but only its length says it's a cut
+}
+
+/// The titles to rip from a movie disc
+#[derive(Debug, Clone)]
+pub struct MoviePlan {
+    pub main: u32,     // Title index of the main feature
+    pub decoys: usize, // Playlists of (nearly) the main feature's length that were passed over
+    pub titles: Vec<(u32, MovieTitle)>, // Every title to rip, in disc order
+}
+
+impl MoviePlan {
+    pub fn get(&self, title_index: u32) -> Option<&MovieTitle> {
+        self.titles.iter().find(|(index, _)| *index == title_index).map(|(_, title)| title)
+    }
+
+    /// Split a disc's titles into the planned ones and the rest
+    pub fn select<'a>(&self, titles: &'a [TitleInfo]) -> SelectedTitles<'a> {
+        let mut result = SelectedTitles::default();
+        for title in titles {
+            if self.get(title.index).is_some() {
+                result.selected.push(title);
+            } else {
+                result.skipped.push((title, "not the movie or one of its extras".to_string()));
+            }
+        }
+        result
+    }
+
+    /// One line per planned title, for the rip log
+    pub fn describe(&self, titles: &[TitleInfo]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (index, role) in &self.titles {
+            let Some(title) = titles.iter().find(|t| t.index == *index) else {
+                continue;
+            };
+            let source = title.source_file.as_deref().map(|f| format!("{}, ", f)).unwrap_or_default();
+            lines.push(match role {
+                MovieTitle::Feature { edition } if *index == self.main => format!(
+                    "🎬 Main feature: title {} ({}{}){}",
+                    index, source, title.duration_string(), edition.as_ref().map(|e| format!(" - {}", e)).unwrap_or_default()
+                ),
+                MovieTitle::Feature { edition } => format!(
+                    "🎬 Other cut: title {} ({}{}) - {}",
+                    index, source, title.duration_string(), edition.as_deref().unwrap_or("unnamed")
+                ),
+                MovieTitle::Extra { kind, name } => format!(
+                    "🎞️  Extra: title {} ({} min) '{}' → {}", index, title.duration_minutes(), name, kind.folder()
+                ),
+                MovieTitle::Review { name } => format!(
+                    "❓ Cut or extra? Title {} ({}{}) '{}' shares nothing but its length with the main feature → {} for review",
+                    index, source, title.duration_string(), name, REVIEW_FOLDER
+                ),
+            });
+        }
+        if self.decoys > 0 {
+            lines.push(format!("🔀 {} other playlists share the main feature's length - skipping them", self.decoys));
+        }
+        lines
+    }
+}
+
+/// Plan a movie rip: the main feature, its other cuts and (with `include_extras`) the extras
+///
+/// Explicitly picked titles are all ripped: the longest is the main feature and whatever isn't a cut is an
+/// extra. Returns None when there are no titles to choose from.
+pub fn plan_movie(titles: &[TitleInfo], selection: &TitleSelection) -> Option<MoviePlan> {
+    let redundant = find_redundant_titles(titles);
+    let titles: Vec<TitleInfo> = titles.iter()
+        .filter(|t| match selection.titles {
+            Some(ref wanted) => wanted.contains(&t.index),
+            None => !redundant.iter().any(|(index, _)| *index == t.index),
+        })
+        .cloned()
+        .collect();
+
+    let main = find_main_feature(&titles)?;
+    let (main_index, decoys) = (main.title.index, main.decoys);
+    let mut cuts = vec![main.title.clone()];
+    if !selection.longest_only {
+        let shortest = main.title.duration_seconds / 100 * EDITION_MIN_PERCENT;
+        let mut rest: Vec<TitleInfo> = titles.iter().filter(|t| t.duration_seconds >= shortest).cloned().collect();
+        loop {
+            let last = cuts.last().map_or(0, |cut| cut.duration_seconds);
+            rest.retain(|t| t.duration_seconds < last - last / MAIN_FEATURE_SLACK);
+            let Some(cut) = find_main_feature(&rest) else {
+                break;
+            };
+            cuts.push(cut.title.clone());
+        }
+    }
+
+    // A long extra is as long as a cut, so a cut also needs something besides its length in common with the main feature
+    let (cuts, unsure): (Vec<TitleInfo>, Vec<TitleInfo>) = cuts.into_iter()
+        .partition(|t| t.index == main_index || is_same_movie(main.title, t));
+
+    let mut planned: Vec<(u32, MovieTitle)> = cuts.iter().enumerate()
+        .map(|(i, cut)| (cut.index, MovieTitle::Feature { edition: edition_name(i, cuts.len()) }))
+        .collect();
+    for title in &unsure {
+        let name = distinct_name(title, &titles).unwrap_or_else(|| format!("Title {:02}", title.index));
+        planned.push((title.index, MovieTitle::Review { name }));
+    }
+
+    let is_cut = |t: &TitleInfo| cuts.iter().chain(&unsure).any(|cut| cut.index == t.index);
+    let extras: Vec<&TitleInfo> = match selection.titles {
+        Some(_) => titles.iter().filter(|t| !is_cut(t)).collect(),
+        None if selection.include_extras => selection.select(&titles).selected.into_iter()
+            .filter(|t| !is_cut(t) && t.duration_minutes() < selection.min_minutes())
+            .collect(),
+        None => Vec::new(),
+    };
+    for extra in extras {
+        let name = distinct_name(extra, &titles).unwrap_or_else(|| format!("Extra {:02}", extra.index));
+        planned.push((extra.index, MovieTitle::Extra { kind: ExtraKind::from_name(&name), name }));
+    }
+
+    planned.sort_by_key(|(index, _)| *index);
+    Some(MoviePlan { main: main_index, decoys, titles: planned })
+}
+
+/// A title's name, unless other titles share it
+///
+/// MakeMKV often names every title after the disc, which says nothing about the title.
+fn distinct_name(title: &TitleInfo, titles: &[TitleInfo]) -> Option<String> {
+    title.name.clone()
+        .filter(|name| !name.trim().is_empty() && titles.iter().filter(|t| t.name.as_ref() == Some(name)).count() == 1)
+}
+
+/// Whether a feature-length title is another cut of the main feature: it reuses most of the feature's segments,
+/// is another angle of it, or has the same audio tracks (languages and channels)
+fn is_same_movie(main: &TitleInfo, title: &TitleInfo) -> bool {
+    let main_segments = main.segments();
+    let segments = title.segments();
+    let shared = segments.iter().filter(|s| main_segments.contains(s)).count();
+    let other_angle = main.angle.is_some() && title.angle.is_some() && main.angle != title.angle;
+    let audio_layout = |t: &TitleInfo| t.audio_streams.iter().map(|a| (a.language_code.clone(), a.channels)).collect::<Vec<_>>();
+    let same_audio = !title.audio_streams.is_empty() && audio_layout(main) == audio_layout(title);
+    shared * 2 > segments.len() || other_angle || same_audio
+}
+
+/// Edition of the `position`th longest of `count` cuts
+fn edition_name(position: usize, count: usize) -> Option<String> {
+    match (position, count) {
+        (_, 1) => None,
+        (0, _) => Some("Director's Cut".to_string()),
+        (p, c) if p + 1 == c => Some("Theatrical Cut".to_string()),
+        (1, _) => Some("Extended Cut".to_string()),
+        (p, _) => Some(format!("Alternate Cut {}", p)),
+    }
+}
+
+/// Where a title of a movie rip goes under `folder`, from the movie naming template
+///
+/// Cuts are named as the movie with their edition; extras go in their kind's folder next to the movie file
+/// (`Alien (1979)/Featurettes/Making Of.mkv`), and titles left for review in the `Other` folder.
+pub fn movie_path(config: &Config, folder: &Path, file: &Path, metadata: &DvdMetadata, title: &MovieTitle) -> Result<PathBuf> {
+    let template = config.naming_template(MediaKind::Movie, None);
+    let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("mkv");
+    let fields = NameFields::movie(&metadata.title, metadata.year.as_deref()).with_ext(ext);
+    let (extras_folder, name) = match title {
+        MovieTitle::Feature { edition } => return Ok(folder.join(naming::render(&template, &fields.with_edition(edition.as_deref()))?)),
+        MovieTitle::Extra { kind, name } => (kind.folder(), name),
+        MovieTitle::Review { name } => (REVIEW_FOLDER, name),
+    };
+    let movie_file = naming::render(&template, &fields)?;
+    let movie_folder = movie_file.parent().unwrap_or_else(|| Path::new(""));
+    Ok(folder.join(movie_folder).join(extras_folder).join(format!("{}.{}", naming::sanitize(name), ext)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dvd_metadata::MediaType;
+
+    fn title(index: u32, minutes: u32, name: &str) -> TitleInfo {
+        TitleInfo {
+            index,
+            name: Some(name.to_string()),
+            duration_seconds: minutes * 60,
+            chapter_count: 12,
+            segment_map: Some(format!("{}", index + 1)),
+            ..Default::default()
+        }
+    }
+
+    fn roles(plan: &MoviePlan) -> Vec<(u32, MovieTitle)> {
+        plan.titles.clone()
+    }
+
+    fn with_segments(title: TitleInfo, segments: &str) -> TitleInfo {
+        TitleInfo { segment_map: Some(segments.to_string()), ..title }
+    }
+
+    fn with_audio(title: TitleInfo, channels: &[u32]) -> TitleInfo {
+        let audio_streams = channels.iter().enumerate()
+            .map(|(i, c)| crate::disc_scan::StreamInfo { index: i as u32, language_code: Some("eng".to_string()), channels: Some(*c), ..Default::default() })
+            .collect();
+        TitleInfo { audio_streams, ..title }
+    }
+
+    #[test]
+    fn test_plan_detects_cuts_and_extras() {
+        let titles = vec![
+            with_segments(title(0, 117, "ALIEN"), "1-9"),
+            with_segments(title(1, 116, "ALIEN"), "1-8,10"), // Same cut, a minute shorter
+            with_segments(title(2, 134, "ALIEN"), "1-12"),
+            title(3, 12, "Making of Alien"),
+            title(4, 3, "ALIEN"),
+            title(5, 0, "ALIEN"),
+        ];
+        let selection = TitleSelection { include_extras: true, ..Default::default() };
+        let plan = plan_movie(&titles, &selection).unwrap();
+        assert_eq!(plan.main, 2);
+        assert_eq!(roles(&plan), vec![
+            (0, MovieTitle::Feature { edition: Some("Theatrical Cut".to_string()) }),
+            (2, MovieTitle::Feature { edition: Some("Director's Cut".to_string()) }),
+            (3, MovieTitle::Extra { kind: ExtraKind::BehindTheScenes, name: "Making of Alien".to_string() }),
+            (4, MovieTitle::Extra { kind: ExtraKind::Featurettes, name: "Extra 04".to_string() }),
+        ]);
+        let picked = plan.select(&titles);
+        assert_eq!(picked.selected.iter().map(|t| t.index).collect::<Vec<_>>(), vec![0, 2, 3, 4]);
+        assert_eq!(picked.skipped.len(), 2);
+
+        // Without extras (or with longest_only) just the cuts, or just the main feature
+        assert_eq!(plan_movie(&titles, &TitleSelection::default()).unwrap().titles.len(), 2);
+        let longest = TitleSelection { longest_only: true, ..Default::default() };
+        assert_eq!(roles(&plan_movie(&titles, &longest).unwrap()), vec![(2, MovieTitle::Feature { edition: None })]);
+        assert!(plan_movie(&[], &selection).is_none());
+    }
+
+    #[test]
+    fn test_long_extra_is_not_a_cut() {
+        let titles = vec![
+            TitleInfo { angle: Some("1".to_string()), ..with_audio(with_segments(title(0, 134, "ALIEN"), "1-12"), &[6, 2]) },
+            with_audio(with_segments(title(1, 112, "The Beast Within"), "20-31"), &[2]), // Feature-length documentary
+            with_audio(with_segments(title(2, 117, "ALIEN"), "40-48"), &[6, 2]), // Re-authored cut with its own segments
+            TitleInfo { angle: Some("2".to_string()), ..with_segments(title(3, 108, "ALIEN"), "50-57") }, // Branches off as another angle
+        ];
+        let plan = plan_movie(&titles, &TitleSelection::default()).unwrap();
+        assert_eq!(plan.main, 0);
+        assert_eq!(roles(&plan), vec![
+            (0, MovieTitle::Feature { edition: Some("Director's Cut".to_string()) }),
+            (1, MovieTitle::Review { name: "The Beast Within".to_string() }),
+            (2, MovieTitle::Feature { edition: Some("Extended Cut".to_string()) }),
+            (3, MovieTitle::Feature { edition: Some("Theatrical Cut".to_string()) }),
+        ]);
+        assert!(plan.describe(&titles).iter().any(|l| l.starts_with("❓ Cut or extra? Title 1")));
+
+        // Nothing but the length in common: ripped for review rather than named as an edition
+        let plan = plan_movie(&titles[..2], &TitleSelection::default()).unwrap();
+        assert_eq!(roles(&plan), vec![
+            (0, MovieTitle::Feature { edition: None }),
+            (1, MovieTitle::Review { name: "The Beast Within".to_string() }),
+        ]);
+    }
+
+    #[test]
+    fn test_explicit_titles_are_all_ripped() {
+        let titles = vec![title(0, 117, "ALIEN"), title(1, 134, "ALIEN"), title(2, 12, "Trailer")];
+        let selection = TitleSelection::resolve(None, None, Some(&[0, 2]));
+        let plan = plan_movie(&titles, &selection).unwrap();
+        assert_eq!(roles(&plan), vec![
+            (0, MovieTitle::Feature { edition: None }),
+            (2, MovieTitle::Extra { kind: ExtraKind::Trailers, name: "Trailer".to_string() }),
+        ]);
+    }
+
+    #[test]
+    fn test_movie_paths() {
+        let metadata = DvdMetadata {
+            title: "Alien".to_string(),
+            year: Some("1979".to_string()),
+            media_type: MediaType::Movie,
+            episodes: Vec::new(),
+        };
+        let folder = Path::new("/rips/Alien/completed");
+        let file = Path::new("/rips/Alien/title_t02.mkv");
+        let mut config = Config::default();
+
+        let feature = MovieTitle::Feature { edition: None };
+        assert_eq!(movie_path(&config, folder, file, &metadata, &feature).unwrap(), folder.join("Alien (1979)/Alien (1979).mkv"));
+        let extra = MovieTitle::Extra { kind: ExtraKind::BehindTheScenes, name: "Making of: Alien".to_string() };
+        assert_eq!(
+            movie_path(&config, folder, file, &metadata, &extra).unwrap(),
+            folder.join("Alien (1979)/Behind The Scenes/Making of_ Alien.mkv")
+        );
+
+        let review = MovieTitle::Review { name: "Title 03".to_string() };
+        assert_eq!(movie_path(&config, folder, file, &metadata, &review).unwrap(), folder.join("Alien (1979)/Other/Title 03.mkv"));
+
+        config.naming.layout = Some("plex".to_string());
+        let cut = MovieTitle::Feature { edition: Some("Director's Cut".to_string()) };
+        assert_eq!(
+            movie_path(&config, folder, file, &metadata, &cut).unwrap(),
+            folder.join("Alien (1979)/Alien (1979) {edition-Director's Cut}.mkv")
+        );
+    }
+}
@@ -0,0 +1,366 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+
+// File name templates: `{name}` placeholders are replaced with sanitized values, `{name:02}` zero-pads numbers
+// and `{name:dots}` swaps spaces for dots. A `/` in a template makes folders, which is how the Plex and
+// Jellyfin layouts put episodes under `Show (Year)/Season 01/`. Missing values render empty and the
+// separators around them are tidied away, so `{show} ({year})` is just `Show` when the year is unknown.
+// `{edition:plex}` renders a movie's edition in Plex's `{edition-Director's Cut}` form.
+
+/// The historical `Show.Name.S01E01.Episode.Title.mkv` naming
+pub const DEFAULT_EPISODE_TEMPLATE: &str = "{show:dots}.S{season:02}E{episode:02}.{title:dots}.{ext}";
+pub const DEFAULT_MOVIE_TEMPLATE: &str = "{title} ({year})/{title} ({year}) - {edition}.{ext}";
+pub const DEFAULT_MUSIC_TEMPLATE: &str = "{artist}/{album}/{track:02} - {title}.{ext}";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    Episode,
+    Movie,
+    Music,
+}
+
+/// Templates for a named library layout ("plex" or "jellyfin")
+pub fn layout_template(layout: &str, kind: MediaKind) -> Option<&'static str> {
+    match (layout.to_lowercase().as_str(), kind) {
+        ("plex", MediaKind::Episode) => Some("{show} ({year})/Season {season:02}/{show} ({year}) - s{season:02}e{episode:02} - {title}.{ext}"),
+        ("jellyfin", MediaKind::Episode) => Some("{show} ({year})/Season {season:02}/{show} S{season:02}E{episode:02} {title}.{ext}"),
+        ("plex", MediaKind::Movie) => Some("{title} ({year})/{title} ({year}) {edition:plex}.{ext}"),
+        ("jellyfin", MediaKind::Movie) => Some("{title} ({year})/{title} ({year}) - {edition}.{ext}"),
+        ("plex" | "jellyfin", MediaKind::Music) => Some("{artist}/{album}/{track:02} - {title}.{ext}"),
+        _ => None,
+    }
+}
+
+/// Values available to a template; unset ones render empty
+#[derive(Debug, Clone, Default)]
+pub struct NameFields {
+    pub show: Option<String>,
+    pub season: Option<u32>,
+    pub episode: Option<u32>,
+    pub title: Option<String>, // Episode, movie or track title
+    pub year: Option<String>,
+    pub edition: Option<String>, // Movie cut, e.g. "Director's Cut"
+    pub ext: Option<String>,   // Without the dot
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track: Option<u32>,
+}
+
+impl NameFields {
+    pub fn episode(show: &str, season: u32, episode: u32, title: Option<&str>) -> Self {
+        NameFields {
+            show: Some(show.to_string()),
+            season: Some(season),
+            episode: Some(episode),
+            title: title.map(str::to_string),
+            ext: Some("mkv".to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn movie(title: &str, year: Option<&str>) -> Self {
+        NameFields {
+            title: Some(title.to_string()),
+            year: year.map(str::to_string),
+            ext: Some("mkv".to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_year(mut self, year: Option<&str>) -> Self {
+        self.year = year.map(str::to_string);
+        self
+    }
+
+    pub fn with_edition(mut self, edition: Option<&str>) -> Self {
+        self.edition = edition.map(str::to_string);
+        self
+    }
+
+    pub fn with_ext(mut self, ext: &str) -> Self {
+        self.ext = Some(ext.trim_start_matches('.').to_string());
+        self
+    }
+
+    fn value(&self, name: &str) -> Result<Value> {
+        let text = |v: &Option<String>| v.clone().map(Value::Text).unwrap_or(Value::Missing);
+        let number = |v: Option<u32>| v.map(Value::Number).unwrap_or(Value::Missing);
+        Ok(match name {
+            "show" => text(&self.show),
+            "season" => number(self.season),
+            "episode" => number(self.episode),
+            "title" => text(&self.title),
+            "year" => text(&self.year),
+            "edition" => text(&self.edition),
+            "ext" => text(&self.ext),
+            "artist" => text(&self.artist),
+            "album" => text(&self.album),
+            "track" => number(self.track),
+            _ => anyhow::bail!("Unknown placeholder {{{}}}", name),
+        })
+    }
+}
+
+enum Value {
+    Text(String),
+    Number(u32),
+    Missing,
+}
+
+impl Config {
+    /// Template for a kind of media: the show's own `naming`, then `naming.<kind>`, then `naming.layout`
+    pub fn naming_template(&self, kind: MediaKind, show: Option<&str>) -> String {
+        let show_template = show.and_then(|show| {
+            self.seed.shows.iter().find_map(|seed| match seed {
+                crate::config::ShowSeed::WithConfig { name, naming: Some(template), .. }
+                    if name.eq_ignore_ascii_case(show) => Some(template.clone()),
+                _ => None,
+            })
+        });
+        let kind_template = match kind {
+            MediaKind::Episode => self.naming.episode.clone(),
+            MediaKind::Movie => self.naming.movie.clone(),
+            MediaKind::Music => self.naming.music.clone(),
+        };
+        let layout_template = self.naming.layout.as_deref()
+            .and_then(|layout| layout_template(layout, kind))
+            .map(str::to_string);
+
+        show_template
+            .or(kind_template)
+            .or(layout_template)
+            .unwrap_or_else(|| match kind {
+                MediaKind::Episode => DEFAULT_EPISODE_TEMPLATE,
+                MediaKind::Movie => DEFAULT_MOVIE_TEMPLATE,
+                MediaKind::Music => DEFAULT_MUSIC_TEMPLATE,
+            }.to_string())
+    }
+}
+
+/// Where `file` goes when renamed with `template`: relative to its folder, keeping its extension
+pub fn renamed_path(template: &str, file: &Path, mut fields: NameFields) -> Result<PathBuf> {
+    if let Some(ext) = file.extension().and_then(|s| s.to_str()) {
+        fields.ext = Some(ext.to_string());
+    }
+    let folder = file.parent().unwrap_or_else(|| Path::new(""));
+    Ok(folder.join(render(template, &fields)?))
+}
+
+/// Render a template to a relative path
+pub fn render(template: &str, fields: &NameFields) -> Result<PathBuf> {
+    let mut path = PathBuf::new();
+    for component in template.split('/').filter(|c| !c.is_empty()) {
+        let rendered = tidy(&render_component(component, fields)?);
+        if rendered.is_empty() || rendered == "." || rendered == ".." {
+            anyhow::bail!("Template {:?} renders an empty folder or file name", template);
+        }
+        path.push(rendered);
+    }
+    if path.as_os_str().is_empty() {
+        anyhow::bail!("Template is empty");
+    }
+    Ok(path)
+}
+
+fn render_component(component: &str, fields: &NameFields) -> Result<String> {
+    let mut out = String::new();
+    let mut rest = component;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let end = rest[start..].find('}')
+            .ok_or_else(|| anyhow::anyhow!("Unclosed {{ in template {:?}", component))?;
+        let placeholder = &rest[start + 1..start + end];
+        let (name, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+
+        match (fields.value(name.trim())?, spec) {
+            (Value::Missing, _) => {}
+            (Value::Number(n), "") => out.push_str(&n.to_string()),
+            (Value::Number(n), spec) => {
+                let width: usize = spec.parse()
+                    .map_err(|_| anyhow::anyhow!("Bad format {:?} for {{{}}}", spec, name))?;
+                out.push_str(&format!("{:0width$}", n, width = width));
+            }
+            (Value::Text(text), "") => out.push_str(&sanitize(&text)),
+            (Value::Text(text), "dots") => out.push_str(&sanitize(&text).replace(' ', ".")),
+            (Value::Text(text), "plex") => out.push_str(&format!("{{edition-{}}}", sanitize(&text))),
+            (Value::Text(_), spec) => anyhow::bail!("Bad format {:?} for {{{}}}", spec, name),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Drop what's left around missing values: empty `()`/`[]`, dangling ` - ` and doubled separators
+fn tidy(component: &str) -> String {
+    let empty_groups = regex::Regex::new(r"\s*(\(\s*\)|\[\s*\])").unwrap();
+    let dangling_dash = regex::Regex::new(r"\s+-\s*(\.[^.\s]+)?$").unwrap();
+    let leading_dash = regex::Regex::new(r"^\s*-\s+").unwrap();
+    let repeated = regex::Regex::new(r"(\s)\s+|(\.)\.+|\s+(\.)").unwrap();
+
+    let mut text = empty_groups.replace_all(component, "").to_string();
+    // " - - " where a middle value is missing
+    while text.contains(" -  - ") || text.contains(" - - ") {
+        text = text.replace(" -  - ", " - ").replace(" - - ", " - ");
+    }
+    text = dangling_dash.replace(&text, "$1").to_string();
+    text = leading_dash.replace(&text, "").to_string();
+    text = repeated.replace_all(&text, "$1$2$3").to_string();
+    text.trim_matches(|c: char| c.is_whitespace() || c == '-').trim_start_matches('.').to_string()
+}
+
+/// Replace characters that aren't allowed in file names
+pub fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            _ => c,
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Translate a template to Filebot's `--format` syntax (Filebot adds the extension itself)
+pub fn filebot_format(template: &str, kind: MediaKind) -> Result<String> {
+    let template = template.trim_end_matches(".{ext}").replace("{ext}", "");
+    let mut out = String::new();
+    let mut rest = template.as_str();
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let end = rest[start..].find('}')
+            .ok_or_else(|| anyhow::anyhow!("Unclosed {{ in template {:?}", template))?;
+        let placeholder = &rest[start + 1..start + end];
+        let (name, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+
+        let binding = match (name.trim(), kind) {
+            ("show", _) => "n",
+            ("title", MediaKind::Episode) => "t",
+            ("title", MediaKind::Movie) => "n",
+            ("title", MediaKind::Music) => "t",
+            ("season", _) => "s",
+            ("episode", _) => "e",
+            ("year", _) => "y",
+            ("artist", _) => "artist",
+            ("album", _) => "album",
+            ("track", _) => "pi",
+            (other, _) => anyhow::bail!("Unknown placeholder {{{}}}", other),
+        };
+        let expression = match spec {
+            "" => binding.to_string(),
+            "dots" => format!("{}.space('.')", binding),
+            width => {
+                let width: usize = width.parse()
+                    .map_err(|_| anyhow::anyhow!("Bad format {:?} for {{{}}}", width, name))?;
+                format!("{}.pad({})", binding, width)
+            }
+        };
+        out.push('{');
+        out.push_str(&expression);
+        out.push('}');
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_episode_template() {
+        let fields = NameFields::episode("Futurama", 1, 2, Some("The Series Has Landed"));
+        assert_eq!(render(DEFAULT_EPISODE_TEMPLATE, &fields).unwrap(), PathBuf::from("Futurama.S01E02.The.Series.Has.Landed.mkv"));
+
+        let untitled = NameFields::episode("Foster's Home: Imaginary", 3, 10, None);
+        assert_eq!(render(DEFAULT_EPISODE_TEMPLATE, &untitled).unwrap(), PathBuf::from("Foster's.Home_.Imaginary.S03E10.mkv"));
+    }
+
+    #[test]
+    fn test_layouts() {
+        let fields = NameFields::episode("Futurama", 1, 2, Some("The Series Has Landed")).with_year(Some("1999"));
+        let plex = layout_template("Plex", MediaKind::Episode).unwrap();
+        assert_eq!(
+            render(plex, &fields).unwrap(),
+            PathBuf::from("Futurama (1999)/Season 01/Futurama (1999) - s01e02 - The Series Has Landed.mkv")
+        );
+        let jellyfin = layout_template("jellyfin", MediaKind::Episode).unwrap();
+        assert_eq!(
+            render(jellyfin, &fields.clone().with_year(None).with_ext(".mp4")).unwrap(),
+            PathBuf::from("Futurama/Season 01/Futurama S01E02 The Series Has Landed.mp4")
+        );
+
+        // Missing year and title leave no stray separators
+        let bare = NameFields::episode("Futurama", 1, 2, None);
+        assert_eq!(render(plex, &bare).unwrap(), PathBuf::from("Futurama/Season 01/Futurama - s01e02.mkv"));
+
+        let movie = NameFields::movie("Alien", Some("1979"));
+        assert_eq!(render(DEFAULT_MOVIE_TEMPLATE, &movie).unwrap(), PathBuf::from("Alien (1979)/Alien (1979).mkv"));
+        let cut = movie.with_edition(Some("Director's Cut"));
+        assert_eq!(render(DEFAULT_MOVIE_TEMPLATE, &cut).unwrap(), PathBuf::from("Alien (1979)/Alien (1979) - Director's Cut.mkv"));
+        assert_eq!(
+            render(layout_template("plex", MediaKind::Movie).unwrap(), &cut).unwrap(),
+            PathBuf::from("Alien (1979)/Alien (1979) {edition-Director's Cut}.mkv")
+        );
+    }
+
+    #[test]
+    fn test_renamed_path_keeps_folder_and_extension() {
+        let fields = NameFields::episode("Futurama", 1, 2, None);
+        assert_eq!(
+            renamed_path(layout_template("plex", MediaKind::Episode).unwrap(), Path::new("/rips/Futurama/title_t01.mp4"), fields).unwrap(),
+            PathBuf::from("/rips/Futurama/Futurama/Season 01/Futurama - s01e02.mp4")
+        );
+    }
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(sanitize("Foster's Home: The Movie?"), "Foster's Home_ The Movie_");
+        assert_eq!(sanitize("Normal Title"), "Normal Title");
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let fields = NameFields::episode("Futurama", 1, 2, None);
+        assert!(render("{show}.{bogus}", &fields).is_err());
+        assert!(render("{show", &fields).is_err());
+        assert!(render("{show:wide}", &fields).is_err());
+        assert!(render("{year}/{show}", &fields).is_err());
+    }
+
+    #[test]
+    fn test_naming_template_precedence() {
+        let mut config = Config::default();
+        assert_eq!(config.naming_template(MediaKind::Episode, Some("Futurama")), DEFAULT_EPISODE_TEMPLATE);
+
+        config.naming.layout = Some("plex".to_string());
+        assert_eq!(config.naming_template(MediaKind::Movie, None), layout_template("plex", MediaKind::Movie).unwrap());
+        config.naming.episode = Some("{show} {season}x{episode:02}.{ext}".to_string());
+        assert_eq!(config.naming_template(MediaKind::Episode, Some("Futurama")), "{show} {season}x{episode:02}.{ext}");
+
+        config.seed.shows.push(crate::config::ShowSeed::WithConfig {
+            name: "Futurama".to_string(),
+            topaz_profiles: Vec::new(),
+            preview: None,
+            naming: Some("{show}/{episode}.{ext}".to_string()),
+        });
+        assert_eq!(config.naming_template(MediaKind::Episode, Some("futurama")), "{show}/{episode}.{ext}");
+        assert_eq!(config.naming_template(MediaKind::Episode, Some("Seinfeld")), "{show} {season}x{episode:02}.{ext}");
+    }
+
+    #[test]
+    fn test_filebot_format() {
+        assert_eq!(
+            filebot_format(DEFAULT_EPISODE_TEMPLATE, MediaKind::Episode).unwrap(),
+            "{n.space('.')}.S{s.pad(2)}E{e.pad(2)}.{t.space('.')}"
+        );
+        assert_eq!(
+            filebot_format(DEFAULT_MUSIC_TEMPLATE, MediaKind::Music).unwrap(),
+            "{artist}/{album}/{pi.pad(2)} - {t}"
+        );
+        assert!(filebot_format("{nope}", MediaKind::Episode).is_err());
+    }
+}
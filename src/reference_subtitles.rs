@@ -0,0 +1,189 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::config::SpeechMatchConfig;
+use crate::dvd_metadata::Episode;
+use crate::speech_match::{parse_srt_dialogue, EpisodeMatch};
+
+// Matching against known subtitle files: the reference `.srt` for each episode is reduced to word trigrams
+// and the extracted dialogue is scored by how many of its trigrams appear in each reference. Unlike overview
+// matching this compares what is said with what is said, so a handful of lines is usually enough.
+
+/// Containment at which a match counts as strong evidence on its own (see `confidence`)
+const STRONG_CONTAINMENT: f32 = 0.25;
+
+/// Directory of reference subtitles: `speech_match.reference_subtitles`, or the provider cache when it exists
+///
+/// Files are found by the `S01E02` code in their name, under a folder named after the show or with the show
+/// in the file name, e.g. `Futurama/Season 1/Futurama.S01E02.srt`.
+pub fn reference_dir(config: &SpeechMatchConfig) -> Option<PathBuf> {
+    if let Some(ref dir) = config.reference_subtitles {
+        return Some(PathBuf::from(dir));
+    }
+    dirs::cache_dir()
+        .map(|cache| cache.join("ripley").join("subtitles"))
+        .filter(|dir| dir.is_dir())
+}
+
+/// Reference subtitle files for a show as ((season, episode), path)
+pub fn find_reference_subtitles(dir: &Path, show_name: &str) -> Vec<((u32, u32), PathBuf)> {
+    let episode_re = regex::Regex::new(r"[Ss](\d{1,2})[Ee](\d{1,3})").unwrap();
+    let show = normalize_name(show_name);
+
+    let mut found: Vec<((u32, u32), PathBuf)> = WalkDir::new(dir)
+        .max_depth(4)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.path().extension().and_then(|s| s.to_str()).is_some_and(|s| s.eq_ignore_ascii_case("srt"))
+        })
+        .filter_map(|e| {
+            let relative = e.path().strip_prefix(dir).ok()?;
+            let belongs_to_show = relative.iter()
+                .any(|part| normalize_name(&part.to_string_lossy()).starts_with(&show));
+            if !belongs_to_show {
+                return None;
+            }
+            let file_name = e.file_name().to_string_lossy();
+            let caps = episode_re.captures(&file_name)?;
+            Some(((caps[1].parse().ok()?, caps[2].parse().ok()?), e.path().to_path_buf()))
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+/// Read a reference `.srt` (often Latin-1) into dialogue
+pub fn load_reference(path: &Path) -> Result<String> {
+    let bytes = std::fs::read(path)?;
+    Ok(parse_srt_dialogue(&String::from_utf8_lossy(&bytes)))
+}
+
+/// Share of the dialogue's word trigrams that appear in the reference, 0.0-1.0
+pub fn similarity(dialogue: &str, reference: &str) -> f32 {
+    let dialogue = trigrams(dialogue);
+    if dialogue.is_empty() {
+        return 0.0;
+    }
+    let reference = trigrams(reference);
+    dialogue.intersection(&reference).count() as f32 / dialogue.len() as f32
+}
+
+/// Pick the episode whose reference subtitles best contain `dialogue`
+///
+/// `references` pairs each candidate episode with its reference dialogue; `None` when nothing overlaps.
+pub fn match_reference(dialogue: &str, references: &[(&Episode, String)]) -> Option<EpisodeMatch> {
+    let scores: Vec<f32> = references.iter().map(|(_, reference)| similarity(dialogue, reference)).collect();
+    let (best_index, best) = scores.iter().copied().enumerate()
+        .fold((0, 0.0), |best, (i, score)| if score > best.1 { (i, score) } else { best });
+    if best <= 0.0 {
+        return None;
+    }
+    let runner_up = scores.iter().copied().enumerate()
+        .filter(|&(i, _)| i != best_index)
+        .map(|(_, score)| score)
+        .fold(0.0, f32::max);
+
+    let episode = references[best_index].0;
+    Some(EpisodeMatch {
+        season: episode.season,
+        episode: episode.episode,
+        title: episode.title.clone(),
+        confidence: confidence(best, runner_up),
+        match_method: "subtitle_reference".to_string(),
+    })
+}
+
+/// 0-100 confidence from the best containment and the runner-up's, shaped like `transcript_match::confidence`
+fn confidence(best: f32, runner_up: f32) -> f32 {
+    if best <= 0.0 {
+        return 0.0;
+    }
+    let margin = ((best - runner_up.max(0.0)) / best).clamp(0.0, 1.0);
+    let strength = (best / STRONG_CONTAINMENT).min(1.0);
+    ((margin * strength).sqrt() * 100.0).clamp(0.0, 100.0)
+}
+
+fn trigrams(text: &str) -> HashSet<String> {
+    let words: Vec<String> = text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.windows(3).map(|w| w.join(" ")).collect()
+}
+
+/// Lowercase letters and digits only, so "Futurama (1999)" and "futurama.S01E02" compare
+fn normalize_name(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).flat_map(|c| c.to_lowercase()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episode(episode: u32, title: &str) -> Episode {
+        Episode {
+            season: 1,
+            episode,
+            title: title.to_string(),
+            title_index: episode - 1,
+            runtime_minutes: Some(22),
+            overview: None,
+        }
+    }
+
+    const MOON_REFERENCE: &str = "1\n00:01:12,000 --> 00:01:15,000\nWelcome to the Moon, everybody!\n\n\
+        2\n00:01:16,500 --> 00:01:19,000\n<i>Leela, look, a lunar theme park!</i>\n\n\
+        3\n00:02:40,000 --> 00:02:44,000\nGet off my land! No gophers, no crew, no Fry!\n";
+
+    const PILOT_REFERENCE: &str = "1\n00:00:40,000 --> 00:00:43,000\nPizza delivery for I.C. Wiener.\n\n\
+        2\n00:00:44,000 --> 00:00:47,000\nWelcome to the world of tomorrow!\n";
+
+    #[test]
+    fn test_match_reference() {
+        let (pilot, moon) = (episode(1, "Space Pilot 3000"), episode(2, "The Series Has Landed"));
+        let references = vec![
+            (&pilot, parse_srt_dialogue(PILOT_REFERENCE)),
+            (&moon, parse_srt_dialogue(MOON_REFERENCE)),
+        ];
+
+        // Whisper-style transcript of part of the second episode
+        let transcript = "[OPENING]: welcome to the moon everybody. Leela look a lunar theme park";
+        let result = match_reference(transcript, &references).unwrap();
+        assert_eq!((result.season, result.episode), (1, 2));
+        assert_eq!(result.match_method, "subtitle_reference");
+        assert!(result.confidence >= 85.0, "confidence {}", result.confidence);
+
+        assert!(match_reference("Nothing in common here at all", &references).is_none());
+        assert!(similarity("welcome to the", "Welcome to the world") > 0.99);
+        assert_eq!(similarity("", MOON_REFERENCE), 0.0);
+    }
+
+    #[test]
+    fn test_confidence_needs_margin() {
+        assert!(confidence(0.5, 0.45) < 50.0);
+        assert!(confidence(0.05, 0.0) < confidence(0.5, 0.0));
+        assert_eq!(confidence(0.5, 0.0), 100.0);
+    }
+
+    #[test]
+    fn test_find_reference_subtitles() {
+        let dir = tempfile::tempdir().unwrap();
+        let season = dir.path().join("Futurama").join("Season 1");
+        std::fs::create_dir_all(&season).unwrap();
+        std::fs::write(season.join("Futurama.S01E02.srt"), MOON_REFERENCE).unwrap();
+        std::fs::write(season.join("Futurama.S01E01.en.srt"), PILOT_REFERENCE).unwrap();
+        std::fs::write(season.join("notes.txt"), "not a subtitle").unwrap();
+        std::fs::write(dir.path().join("Futurama.S02E01.srt"), "").unwrap();
+        std::fs::write(dir.path().join("Other.Show.S01E01.srt"), "").unwrap();
+
+        let found = find_reference_subtitles(dir.path(), "Futurama");
+        let codes: Vec<(u32, u32)> = found.iter().map(|(code, _)| *code).collect();
+        assert_eq!(codes, vec![(1, 1), (1, 2), (2, 1)]);
+        assert!(load_reference(&found[1].1).unwrap().starts_with("Welcome to the Moon"));
+        assert!(find_reference_subtitles(dir.path(), "Seinfeld").is_empty());
+    }
+}
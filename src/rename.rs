@@ -1,10 +1,15 @@
 use anyhow::{Context, Result};
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 use crate::config::Config;
-use crate::speech_match;
+use crate::database::{Database, EpisodeMatchReview, ReviewStatus};
+use crate::dvd_metadata::EpisodeOrder;
+use crate::episode_assignment::Assignment;
+use crate::naming::{self, MediaKind, NameFields};
+use crate::rename_journal::{self, RenameJournal};
+use crate::speech_match::{self, EpisodeMatch};
 
 #[derive(Debug, Default)]
 pub struct CostTracker {
@@ -44,13 +49,23 @@ impl CostTracker {
 }
 
 /// Run the rename command on a directory of video files
+///
+/// With `dry_run` files are matched as usual but only the planned renames are printed. Episodes are numbered
+/// in `order`, or the show's saved order when it's `None`.
 pub async fn run_rename(
     directory: Option<PathBuf>,
     title: Option<String>,
     skip_speech: bool,
     skip_filebot: bool,
+    dry_run: bool,
+    order: Option<EpisodeOrder>,
 ) -> Result<()> {
     let config = Config::load()?;
+    let db = Database::new().ok();
+    let journal = RenameJournal::new(db.as_ref(), dry_run);
+    if dry_run {
+        println!("🧪 Dry run: no files will be renamed");
+    }
     
     // Get working directory
     let work_dir = directory.unwrap_or_else(|| std::env::current_dir().unwrap());
@@ -75,8 +90,13 @@ pub async fn run_rename(
     println!("📺 Show: {}", show_title);
     
     // Fetch episode metadata from TMDB
-    println!("🔍 Fetching episode list from TMDB...");
-    let metadata = crate::dvd_metadata::fetch_dvd_metadata("", Some(&show_title))
+    let order = order
+        .or_else(|| db.as_ref().map(|db| db.episode_order_for_show(&show_title)))
+        .unwrap_or_default();
+    println!("🔍 Fetching episode list from TMDB ({:?} order)...", order);
+    let provider = crate::metadata_provider::video_provider_from_config(&config)?;
+    let provider = crate::metadata_provider::with_cache(provider, db.as_ref(), &config);
+    let metadata = crate::dvd_metadata::fetch_dvd_metadata_with_episode(provider.as_ref(), Some(&show_title), None, None, order)
         .await
         .context("Failed to fetch TMDB metadata")?;
     
@@ -119,6 +139,15 @@ pub async fn run_rename(
         println!("\n🎙️  Phase 1: Speech Matching");
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
         
+        let transcriber = speech_match::transcriber_from_config(&config.speech_match)?;
+        println!("🎧 Transcribing locally with {}", transcriber.name());
+        let matcher = speech_match::matcher_from_config(&config.speech_match)?;
+        println!("🧩 Matching episodes with the {} matcher", matcher.name());
+        let ocr = crate::subtitle_ocr::SubtitleOcr::from_config(&config.speech_match);
+        
+        // Transcribe and match every file first, then assign episodes across the whole directory
+        let mut pending: Vec<&PathBuf> = Vec::new();
+        let mut evidence = Vec::new();
         for (i, file_path) in mkv_files.iter().enumerate() {
             let file_name = file_path.file_name().unwrap().to_string_lossy();
             println!("\n[{}/{}] {}", i + 1, mkv_files.len(), file_name);
@@ -133,9 +162,9 @@ pub async fn run_rename(
             print!("  ⏳ Extracting audio...");
             io::stdout().flush()?;
             
-            let result = speech_match::extract_and_transcribe_audio(file_path).await;
+            let result = speech_match::extract_and_transcribe_audio_with(file_path, transcriber.as_ref(), &ocr).await;
             
-            match result {
+            let transcript_match = match result {
                 Ok(transcript) => {
                     println!(" ✓ Transcribed {} characters", transcript.len());
                     
@@ -151,12 +180,9 @@ pub async fn run_rename(
                     print!("  🔍 Matching episode...");
                     io::stdout().flush()?;
                     
-                    let match_result = speech_match::match_episode_by_transcript(
-                        &show_title,
-                        &transcript,
-                        &metadata.episodes,
-                    )
-                    .await;
+                    let match_result = matcher
+                        .match_episode(&show_title, &transcript, &metadata.episodes, None)
+                        .await;
                     
                     cost_tracker.add_gpt_request();
                     
@@ -169,39 +195,80 @@ pub async fn run_rename(
                                 episode_match.title,
                                 episode_match.confidence
                             );
-                            
-                            // Rename file
-                            if episode_match.confidence >= 85.0 {
-                                let new_name = format!(
-                                    "{}.S{:02}E{:02}.{}.mkv",
-                                    sanitize_filename(&show_title).replace(' ', "."),
-                                    episode_match.season,
-                                    episode_match.episode,
-                                    sanitize_filename(&episode_match.title).replace(' ', ".")
-                                );
-                                
-                                let new_path = file_path.with_file_name(&new_name);
-                                
-                                match std::fs::rename(file_path, &new_path) {
-                                    Ok(_) => {
-                                        println!("  ✓ Renamed to: {}", new_name);
-                                        renamed_count += 1;
-                                    }
-                                    Err(e) => {
-                                        println!("  ✗ Rename failed: {}", e);
-                                    }
-                                }
-                            } else {
-                                println!("  ⚠️  Confidence too low, skipping rename");
-                            }
+                            Some(episode_match)
                         }
                         Err(e) => {
                             println!(" ✗ Match failed: {}", e);
+                            None
                         }
                     }
                 }
                 Err(e) => {
                     println!(" ✗ Transcription failed: {}", e);
+                    None
+                }
+            };
+            
+            evidence.push(crate::episode_assignment::TitleEvidence {
+                title_index: pending.len() as u32,
+                minutes: speech_match::video_duration_minutes(file_path).await.ok(),
+                transcript_match,
+            });
+            pending.push(file_path);
+        }
+        
+        // Resolve conflicts (two files matching one episode) with durations and file order
+        let tolerance = crate::title_selection::TitleSelection::default().tolerance_minutes();
+        let assignments = crate::episode_assignment::assign_episodes(&evidence, &metadata.episodes, tolerance);
+        if !assignments.is_empty() {
+            println!("\n🧮 Assigned {} file(s) across the disc", assignments.len());
+        }
+        for assignment in &assignments {
+            let file_path = pending[assignment.title_index as usize];
+            let episode_match = &assignment.episode_match;
+            let file_name = file_path.file_name().unwrap().to_string_lossy();
+            
+            // Rename file
+            if !assignment.needs_review {
+                let new_path = match episode_path(&config, file_path, &show_title, metadata.year.as_deref(), episode_match) {
+                    Ok(path) => path,
+                    Err(e) => {
+                        println!("  ✗ Can't name {}: {}", file_name, e);
+                        continue;
+                    }
+                };
+                let new_name = new_path.strip_prefix(&work_dir).unwrap_or(&new_path).display().to_string();
+                
+                if dry_run {
+                    println!("  ⇢ Would rename {} → {} ({:.0}%)", file_name, new_name, episode_match.confidence);
+                    renamed_count += 1;
+                    continue;
+                }
+                
+                match journal.rename(file_path, &new_path, "speech") {
+                    Ok(_) => {
+                        println!("  ✓ {} → {} ({:.0}%)", file_name, new_name, episode_match.confidence);
+                        renamed_count += 1;
+                        if let Some(ref db) = db {
+                            if let Err(e) = speech_match::record_match(db, &show_title, episode_match, None) {
+                                println!("  ⚠️  Failed to record match: {}", e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        println!("  ✗ Rename of {} failed: {}", file_name, e);
+                    }
+                }
+            } else {
+                println!(
+                    "  ⚠️  {}: S{:02}E{:02} confidence too low ({:.0}%), skipping rename",
+                    file_name, episode_match.season, episode_match.episode, episode_match.confidence
+                );
+                if let Some(db) = db.as_ref().filter(|_| !dry_run) {
+                    match queue_for_review(db, &show_title, file_path, assignment) {
+                        Ok(id) => println!("  📝 Queued for review (#{})", id),
+                        Err(e) => println!("  ⚠️  Failed to queue review: {}", e),
+                    }
                 }
             }
         }
@@ -218,6 +285,9 @@ pub async fn run_rename(
         let filebot_result = crate::filebot::rename_with_filebot(
             &work_dir,
             &show_title,
+            order,
+            &config,
+            &journal,
             |msg| {
                 println!("{}", msg);
             },
@@ -240,7 +310,12 @@ pub async fn run_rename(
     println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("📊 Summary");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("✓ Renamed {} file(s)", renamed_count);
+    if dry_run {
+        println!("⇢ Would rename {} file(s)", renamed_count);
+    } else {
+        println!("✓ Renamed {} file(s)", renamed_count);
+        println!("↩️  Undo with: ripley rename --undo {}", journal.batch_id());
+    }
     println!("\n💰 Cost Estimate:");
     println!("   Whisper: {:.1} min @ $0.006/min = ${:.4}", 
         cost_tracker.whisper_minutes,
@@ -256,6 +331,96 @@ pub async fn run_rename(
     Ok(())
 }
 
+/// Put back the files renamed in a batch (`ripley rename --undo <batch>`)
+pub fn run_undo(batch_id: &str) -> Result<()> {
+    let db = Database::new()?;
+    let restored = rename_journal::undo_batch(&db, batch_id)?;
+    for entry in &restored {
+        println!("  ↩ {} → {}", entry.new_path, entry.old_path);
+    }
+    println!("✓ Restored {} file(s) from batch {}", restored.len(), batch_id);
+    Ok(())
+}
+
+/// Where an episode file goes, from the show's naming template (`Show.Name.S01E02.Episode.Title.mkv` by default)
+pub fn episode_path(config: &Config, file: &Path, show: &str, year: Option<&str>, episode_match: &EpisodeMatch) -> Result<PathBuf> {
+    let title = Some(episode_match.title.as_str()).filter(|t| !t.is_empty());
+    let fields = NameFields::episode(show, episode_match.season, episode_match.episode, title).with_year(year);
+    naming::renamed_path(&config.naming_template(MediaKind::Episode, Some(show)), file, fields)
+}
+
+/// Store a low-confidence assignment as a pending review, with its candidate episodes
+pub fn queue_for_review(db: &Database, show: &str, file_path: &Path, assignment: &Assignment) -> Result<i64> {
+    let mut candidates = assignment.candidates.clone();
+    if candidates.is_empty() {
+        let suggestion = &assignment.episode_match;
+        candidates.push(crate::episode_assignment::EpisodeCandidate {
+            season: suggestion.season,
+            episode: suggestion.episode,
+            title: suggestion.title.clone(),
+            score: 100.0,
+        });
+    }
+    db.add_episode_match_review(&EpisodeMatchReview {
+        id: None,
+        created_at: chrono::Utc::now(),
+        show_name: show.to_string(),
+        file_path: file_path.to_string_lossy().to_string(),
+        candidates,
+        confidence: assignment.episode_match.confidence,
+        status: ReviewStatus::Pending,
+        resolved_at: None,
+        resolved_season: None,
+        resolved_episode: None,
+    })
+}
+
+/// Resolve a pending review by renaming its file and recording the episode as a manual match
+///
+/// `choice` is the (season, episode) picked by the user; `None` accepts the suggestion. The title falls back
+/// to the matching candidate's. Returns the renamed file.
+pub fn apply_review(
+    db: &Database,
+    config: &Config,
+    id: i64,
+    choice: Option<(u32, u32)>,
+    title: Option<&str>,
+) -> Result<PathBuf> {
+    let review = db.get_episode_match_review(id)?
+        .ok_or_else(|| anyhow::anyhow!("Review {} not found", id))?;
+    if review.status != ReviewStatus::Pending {
+        anyhow::bail!("Review {} was already resolved", id);
+    }
+    let suggestion = review.candidates.first()
+        .map(|c| (c.season, c.episode));
+    let (season, episode) = choice.or(suggestion)
+        .ok_or_else(|| anyhow::anyhow!("Review {} has no suggested episode", id))?;
+    let title = title.map(str::to_string).or_else(|| {
+        review.candidates.iter()
+            .find(|c| (c.season, c.episode) == (season, episode))
+            .map(|c| c.title.clone())
+    });
+
+    let file_path = PathBuf::from(&review.file_path);
+    if !file_path.exists() {
+        anyhow::bail!("File no longer exists: {}", file_path.display());
+    }
+    let episode_match = EpisodeMatch {
+        season,
+        episode,
+        title: title.unwrap_or_default(),
+        confidence: 100.0,
+        match_method: "manual".to_string(),
+    };
+    let new_path = episode_path(config, &file_path, &review.show_name, None, &episode_match)?;
+    RenameJournal::new(Some(db), false).rename(&file_path, &new_path, "review")?;
+
+    speech_match::record_match(db, &review.show_name, &episode_match, None)?;
+    let status = if Some((season, episode)) == suggestion { ReviewStatus::Accepted } else { ReviewStatus::Overridden };
+    db.resolve_episode_match_review(id, status, season, episode)?;
+    Ok(new_path)
+}
+
 /// Check if filename is already in the correct format (contains S##E##)
 fn is_already_renamed(filename: &str) -> bool {
     // Look for pattern like S01E02 or S1E2
@@ -263,18 +428,6 @@ fn is_already_renamed(filename: &str) -> bool {
     re.is_match(filename)
 }
 
-/// Sanitize filename by removing invalid characters
-fn sanitize_filename(name: &str) -> String {
-    name.chars()
-        .map(|c| match c {
-            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
-            _ => c,
-        })
-        .collect::<String>()
-        .trim()
-        .to_string()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,14 +458,73 @@ mod tests {
     }
     
     #[test]
-    fn test_sanitize_filename() {
+    fn test_episode_path() {
+        let episode_match = |episode: u32, title: &str| EpisodeMatch {
+            season: 1,
+            episode,
+            title: title.to_string(),
+            confidence: 90.0,
+            match_method: "transcript".to_string(),
+        };
+        let file = Path::new("/rips/Futurama/title_t01.mkv");
+        let mut config = Config::default();
+        assert_eq!(
+            episode_path(&config, file, "Futurama", Some("1999"), &episode_match(2, "The Series Has Landed")).unwrap(),
+            PathBuf::from("/rips/Futurama/Futurama.S01E02.The.Series.Has.Landed.mkv")
+        );
         assert_eq!(
-            sanitize_filename("Foster's Home: The Movie?"),
-            "Foster's Home_ The Movie_"
+            episode_path(&config, file, "Foster's Home", None, &episode_match(10, "")).unwrap(),
+            PathBuf::from("/rips/Futurama/Foster's.Home.S01E10.mkv")
         );
+
+        config.naming.layout = Some("jellyfin".to_string());
         assert_eq!(
-            sanitize_filename("Normal Title"),
-            "Normal Title"
+            episode_path(&config, file, "Futurama", Some("1999"), &episode_match(2, "The Series Has Landed")).unwrap(),
+            PathBuf::from("/rips/Futurama/Futurama (1999)/Season 01/Futurama S01E02 The Series Has Landed.mkv")
         );
     }
+    
+    #[test]
+    fn test_apply_review() {
+        std::env::set_var("RIPLEY_TEST_DB", ":memory:");
+        let db = Database::new().unwrap();
+        let config = Config::default();
+        let dir = tempfile::tempdir().unwrap();
+        let candidate = |episode: u32, title: &str, score: f32| crate::episode_assignment::EpisodeCandidate {
+            season: 1,
+            episode,
+            title: title.to_string(),
+            score,
+        };
+        let assignment = |episode: u32| Assignment {
+            title_index: 0,
+            episode_match: EpisodeMatch {
+                season: 1,
+                episode,
+                title: "Space Pilot 3000".to_string(),
+                confidence: 40.0,
+                match_method: "duration".to_string(),
+            },
+            needs_review: true,
+            candidates: vec![candidate(1, "Space Pilot 3000", 100.0), candidate(2, "The Series Has Landed", 90.0)],
+        };
+
+        let first = dir.path().join("title_t00.mkv");
+        let second = dir.path().join("title_t01.mkv");
+        std::fs::write(&first, "").unwrap();
+        std::fs::write(&second, "").unwrap();
+        let accept = queue_for_review(&db, "Futurama", &first, &assignment(1)).unwrap();
+        let overrule = queue_for_review(&db, "Futurama", &second, &assignment(1)).unwrap();
+
+        let accepted = apply_review(&db, &config, accept, None, None).unwrap();
+        assert_eq!(accepted, dir.path().join("Futurama.S01E01.Space.Pilot.3000.mkv"));
+        let overridden = apply_review(&db, &config, overrule, Some((1, 2)), None).unwrap();
+        assert_eq!(overridden, dir.path().join("Futurama.S01E02.The.Series.Has.Landed.mkv"));
+        assert!(overridden.exists() && !second.exists());
+        assert!(apply_review(&db, &config, accept, None, None).is_err());
+
+        let stats = db.get_episode_match_statistics().unwrap();
+        assert_eq!(stats["by_method"]["manual"]["count"], 2);
+        assert_eq!(stats["reviews"]["accuracy"], 50.0);
+    }
 }
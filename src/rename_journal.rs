@@ -0,0 +1,185 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::database::{Database, RenameJournalEntry};
+
+// Every rename ripley makes goes through a journal: the old and new path are stored under a batch ID (one per
+// run) so the whole run can be put back with `ripley rename --undo <batch>`. In dry-run mode nothing is moved
+// or recorded; callers print the planned renames instead.
+
+/// Records renames made during one run under a shared batch ID
+pub struct RenameJournal<'a> {
+    db: Option<&'a Database>,
+    batch_id: String,
+    dry_run: bool,
+}
+
+impl<'a> RenameJournal<'a> {
+    /// Start a new batch; without a database renames still happen but can't be undone
+    pub fn new(db: Option<&'a Database>, dry_run: bool) -> Self {
+        RenameJournal {
+            db,
+            batch_id: Utc::now().format("%Y%m%d-%H%M%S%.3f").to_string().replace('.', "-"),
+            dry_run,
+        }
+    }
+
+    /// Add to a batch started earlier, e.g. by another file of the same rip, so it's undone together
+    pub fn for_batch(db: Option<&'a Database>, batch_id: &str) -> Self {
+        RenameJournal {
+            db,
+            batch_id: batch_id.to_string(),
+            dry_run: false,
+        }
+    }
+
+    pub fn batch_id(&self) -> &str {
+        &self.batch_id
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Rename `old_path` to `new_path` and record it (a no-op in dry-run mode)
+    pub fn rename(&self, old_path: &Path, new_path: &Path, method: &str) -> Result<()> {
+        if self.dry_run {
+            info!("Dry run: would rename {} -> {}", old_path.display(), new_path.display());
+            return Ok(());
+        }
+        if new_path.exists() {
+            anyhow::bail!("{} already exists", new_path.display());
+        }
+        // Folder layouts (Show/Season 01/...) may need new folders
+        if let Some(parent) = new_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(old_path, new_path)
+            .with_context(|| format!("Failed to rename {}", old_path.display()))?;
+        self.record(old_path, new_path, method);
+        Ok(())
+    }
+
+    /// Record a rename made by another tool (e.g. Filebot)
+    pub fn record(&self, old_path: &Path, new_path: &Path, method: &str) {
+        if self.dry_run {
+            return;
+        }
+        let Some(db) = self.db else {
+            return;
+        };
+        let entry = RenameJournalEntry {
+            id: None,
+            batch_id: self.batch_id.clone(),
+            timestamp: Utc::now(),
+            old_path: old_path.to_string_lossy().to_string(),
+            new_path: new_path.to_string_lossy().to_string(),
+            method: method.to_string(),
+            undone_at: None,
+        };
+        if let Err(e) = db.add_rename_journal_entry(&entry) {
+            warn!("Failed to journal rename of {}: {}", old_path.display(), e);
+        }
+    }
+}
+
+/// Put every file renamed in a batch back where it was, newest rename first
+///
+/// Stops at the first file that can't be restored (moved again, or its old name taken); files already
+/// restored are marked undone, so running it again carries on from there. Returns the restored entries.
+pub fn undo_batch(db: &Database, batch_id: &str) -> Result<Vec<RenameJournalEntry>> {
+    let entries = db.get_rename_batch(batch_id)?;
+    if entries.is_empty() {
+        anyhow::bail!("No renames found for batch {}", batch_id);
+    }
+
+    let mut restored = Vec::new();
+    for entry in entries.into_iter().rev().filter(|e| e.undone_at.is_none()) {
+        let (old_path, new_path) = (PathBuf::from(&entry.old_path), PathBuf::from(&entry.new_path));
+        let blocked = if !new_path.exists() {
+            Some(format!("{} no longer exists", new_path.display()))
+        } else if old_path.exists() {
+            Some(format!("{} already exists", old_path.display()))
+        } else {
+            None
+        };
+        if let Some(reason) = blocked {
+            anyhow::bail!("Stopped after restoring {} file(s): {}", restored.len(), reason);
+        }
+
+        std::fs::rename(&new_path, &old_path)
+            .with_context(|| format!("Failed to restore {}", old_path.display()))?;
+        if let Some(id) = entry.id {
+            db.mark_rename_undone(id)?;
+        }
+        info!("Restored {} -> {}", new_path.display(), old_path.display());
+        restored.push(entry);
+    }
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (Database, tempfile::TempDir) {
+        std::env::set_var("RIPLEY_TEST_DB", ":memory:");
+        (Database::new().unwrap(), tempfile::tempdir().unwrap())
+    }
+
+    #[test]
+    fn test_dry_run_touches_nothing() {
+        let (db, dir) = setup();
+        let old_path = dir.path().join("title_t00.mkv");
+        std::fs::write(&old_path, "").unwrap();
+
+        let journal = RenameJournal::new(Some(&db), true);
+        journal.rename(&old_path, &dir.path().join("Show.S01E01.mkv"), "speech").unwrap();
+        journal.record(&old_path, &dir.path().join("Show.S01E02.mkv"), "filebot");
+        assert!(old_path.exists());
+        assert!(db.get_rename_batch(journal.batch_id()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_undo_batch_restores_chained_renames() {
+        let (db, dir) = setup();
+        let original = dir.path().join("title_t00.mkv");
+        let speech = dir.path().join("Show.S01E02.mkv");
+        let filebot = dir.path().join("Show.S01E01.Pilot.mkv");
+        std::fs::write(&original, "").unwrap();
+
+        let journal = RenameJournal::new(Some(&db), false);
+        journal.rename(&original, &speech, "speech").unwrap();
+        std::fs::rename(&speech, &filebot).unwrap();
+        // A later step of the same run joins the batch
+        RenameJournal::for_batch(Some(&db), journal.batch_id()).record(&speech, &filebot, "filebot");
+
+        let batch = db.get_rename_batch(journal.batch_id()).unwrap();
+        assert_eq!(batch.iter().map(|e| e.method.as_str()).collect::<Vec<_>>(), vec!["speech", "filebot"]);
+
+        let restored = undo_batch(&db, journal.batch_id()).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert!(original.exists() && !speech.exists() && !filebot.exists());
+        assert!(db.get_rename_batch(journal.batch_id()).unwrap().iter().all(|e| e.undone_at.is_some()));
+        assert!(undo_batch(&db, journal.batch_id()).unwrap().is_empty());
+        assert!(undo_batch(&db, "no-such-batch").is_err());
+    }
+
+    #[test]
+    fn test_undo_stops_when_name_is_taken() {
+        let (db, dir) = setup();
+        let original = dir.path().join("title_t00.mkv");
+        let renamed = dir.path().join("Show.S01E01.mkv");
+        std::fs::write(&original, "").unwrap();
+
+        let journal = RenameJournal::new(Some(&db), false);
+        journal.rename(&original, &renamed, "speech").unwrap();
+        std::fs::write(&original, "new rip").unwrap();
+
+        assert!(journal.rename(&renamed, &original, "speech").is_err());
+        assert!(undo_batch(&db, journal.batch_id()).is_err());
+        assert!(renamed.exists());
+    }
+}
@@ -0,0 +1,1057 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::process::Stdio;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+use crate::config::RipProfile;
+use crate::disc_scan::{DiscScan, TitleInfo};
+use crate::drive::MediaType;
+
+/// Boxed future returned by backend operations
+pub type BackendFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Events streamed by a backend while it rips a single title
+#[derive(Debug, Clone, PartialEq)]
+pub enum RipEvent {
+    /// Percentage (0-100) of the current title that has been ripped
+    Progress(f32),
+    /// The backend has finished reading and is encoding the title
+    Encoding,
+    /// A line of tool output worth showing to the user
+    Log(String),
+    /// The rip was paused and the ripping tool is suspended
+    Paused,
+    /// The rip was resumed after a pause
+    Resumed,
+}
+
+/// Requested state of a rip, set from the API or TUI and honoured by the backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RipState {
+    Running,
+    Paused,
+    Stopped,
+}
+
+/// Shared handle for pausing, resuming and stopping a backend's rip
+///
+/// Clones share the same state, so the copy kept in the drive status controls the backend's rip.
+#[derive(Debug, Clone)]
+pub struct RipControl {
+    state: Arc<watch::Sender<RipState>>,
+}
+
+impl Default for RipControl {
+    fn default() -> Self {
+        RipControl { state: Arc::new(watch::channel(RipState::Running).0) }
+    }
+}
+
+impl RipControl {
+    /// Suspend the rip; returns false if it wasn't running
+    pub fn pause(&self) -> bool {
+        self.transition(RipState::Running, RipState::Paused)
+    }
+
+    /// Continue a paused rip; returns false if it wasn't paused
+    pub fn resume(&self) -> bool {
+        self.transition(RipState::Paused, RipState::Running)
+    }
+
+    /// Stop the rip for good
+    pub fn stop(&self) {
+        self.state.send_replace(RipState::Stopped);
+    }
+
+    pub fn state(&self) -> RipState {
+        *self.state.borrow()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.state() == RipState::Stopped
+    }
+
+    /// Watch for state changes
+    pub fn subscribe(&self) -> watch::Receiver<RipState> {
+        self.state.subscribe()
+    }
+
+    /// Wait until the rip is no longer paused, returning the state it left the pause in
+    pub async fn wait_while_paused(&self) -> RipState {
+        let mut rx = self.subscribe();
+        let state = rx.wait_for(|state| *state != RipState::Paused).await
+            .map(|state| *state)
+            .unwrap_or(RipState::Stopped);
+        state
+    }
+
+    fn transition(&self, from: RipState, to: RipState) -> bool {
+        self.state.send_if_modified(|state| {
+            if *state == from {
+                *state = to;
+                true
+            } else {
+                false
+            }
+        })
+    }
+}
+
+/// A ripping tool that can list the titles on a disc and rip them one at a time
+pub trait RipBackend: Send + Sync {
+    /// Short identifier used in config files and logs
+    fn name(&self) -> &'static str;
+
+    /// Media type this backend pretends to be reading, if it doesn't need a real drive
+    fn media_hint(&self) -> Option<MediaType> {
+        None
+    }
+
+    /// Handle used to pause, resume or stop this backend's rips
+    fn control(&self) -> &RipControl;
+
+    /// Scan the disc in `device` and return its titles
+    fn scan<'a>(&'a self, device: &'a str) -> BackendFuture<'a, DiscScan>;
+
+    /// Rip one title into `output_dir`, returning the path of the file that was written
+    ///
+    /// Pausing suspends the ripping tool; stopping kills it and removes the partial output.
+    fn rip_title<'a>(
+        &'a self,
+        device: &'a str,
+        title: &'a TitleInfo,
+        output_dir: &'a Path,
+        events: &'a mut (dyn FnMut(RipEvent) + Send),
+    ) -> BackendFuture<'a, PathBuf>;
+}
+
+/// Pick the backend for a rip profile, falling back to the default for the media type
+pub fn backend_for_profile(
+    profile: Option<&RipProfile>,
+    media_type: &MediaType,
+    default_quality: u8,
+    control: RipControl,
+) -> Result<Box<dyn RipBackend>> {
+    let requested = profile.and_then(|p| p.backend.as_deref());
+    let name = match requested {
+        Some(name) => name.to_lowercase(),
+        None if *media_type == MediaType::AudioCD => "abcde".to_string(),
+        None => "makemkv".to_string(),
+    };
+
+    match name.as_str() {
+        "makemkv" => Ok(Box::new(MakeMkvBackend {
+            profile: profile.and_then(|p| p.makemkv_profile.clone()),
+            control,
+        })),
+        "abcde" | "cdparanoia" => Ok(Box::new(AbcdeBackend {
+            quality: profile.and_then(|p| p.audio_quality).unwrap_or(default_quality),
+            control,
+        })),
+        "fake" => {
+            let script = match profile.and_then(|p| p.fake_script.as_ref()) {
+                Some(path) => FakeScript::load(path)?,
+                None => FakeScript::default(),
+            };
+            let mut backend = FakeBackend::new(script);
+            backend.control = control;
+            Ok(Box::new(backend))
+        }
+        other => Err(anyhow!("Unknown rip backend '{}' (expected makemkv, abcde or fake)", other)),
+    }
+}
+
+/// Snapshot the files in a directory so newly written output can be found afterwards
+async fn list_files(dir: &Path) -> HashSet<PathBuf> {
+    let mut files = HashSet::new();
+    if let Ok(mut entries) = tokio::fs::read_dir(dir).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            files.insert(entry.path());
+        }
+    }
+    files
+}
+
+/// Find the most recently modified file with `extension` that wasn't in `before`
+async fn find_new_file(dir: &Path, before: &HashSet<PathBuf>, extension: &str) -> Option<PathBuf> {
+    let mut newest: Option<(std::time::SystemTime, PathBuf)> = None;
+    for path in list_files(dir).await {
+        if before.contains(&path) || path.extension().and_then(|s| s.to_str()) != Some(extension) {
+            continue;
+        }
+        let modified = tokio::fs::metadata(&path).await
+            .and_then(|m| m.modified())
+            .unwrap_or(std::time::UNIX_EPOCH);
+        if newest.as_ref().map(|(t, _)| modified > *t).unwrap_or(true) {
+            newest = Some((modified, path));
+        }
+    }
+    newest.map(|(_, path)| path)
+}
+
+/// Delete everything written to `dir` since `before` was taken
+async fn remove_partial_output(dir: &Path, before: &HashSet<PathBuf>) {
+    for path in list_files(dir).await {
+        if before.contains(&path) {
+            continue;
+        }
+        let result = if path.is_dir() {
+            tokio::fs::remove_dir_all(&path).await
+        } else {
+            tokio::fs::remove_file(&path).await
+        };
+        match result {
+            Ok(_) => info!("Removed partial output {}", path.display()),
+            Err(e) => warn!("Failed to remove partial output {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Send a signal (e.g. "STOP", "CONT", "TERM") to a child's whole process group
+///
+/// Children are spawned as group leaders, so this reaches the helpers they start too.
+async fn signal_process_group(pid: u32, signal: &str) {
+    match Command::new("kill")
+        .arg("-s")
+        .arg(signal)
+        .arg("--")
+        .arg(format!("-{}", pid))
+        .output()
+        .await {
+            Ok(output) if output.status.success() => debug!("Sent SIG{} to process group {}", signal, pid),
+            Ok(output) => warn!("Failed to send SIG{} to process group {}: {}", signal, pid, String::from_utf8_lossy(&output.stderr).trim()),
+            Err(e) => warn!("Failed to run kill: {}", e),
+        }
+}
+
+/// Apply a pause, resume or stop request to a running ripping tool
+///
+/// On stop the process group is terminated (then killed if it lingers), partial output is removed
+/// and an error is returned so the caller abandons the rip.
+async fn apply_rip_state(
+    state: RipState,
+    child: &mut Child,
+    output_dir: &Path,
+    before: &HashSet<PathBuf>,
+    events: &mut (dyn FnMut(RipEvent) + Send),
+) -> Result<()> {
+    let Some(pid) = child.id() else { return Ok(()) };
+    match state {
+        RipState::Paused => {
+            signal_process_group(pid, "STOP").await;
+            events(RipEvent::Paused);
+        }
+        RipState::Running => {
+            signal_process_group(pid, "CONT").await;
+            events(RipEvent::Resumed);
+        }
+        RipState::Stopped => {
+            info!("Stopping rip process group {}", pid);
+            signal_process_group(pid, "TERM").await;
+            // Stopped processes only act on SIGTERM once continued
+            signal_process_group(pid, "CONT").await;
+            if tokio::time::timeout(tokio::time::Duration::from_secs(5), child.wait()).await.is_err() {
+                signal_process_group(pid, "KILL").await;
+                let _ = child.wait().await;
+            }
+            remove_partial_output(output_dir, before).await;
+            return Err(anyhow!("Rip stopped"));
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// MakeMKV
+// ---------------------------------------------------------------------------
+
+/// Video disc backend driving `makemkvcon` in robot mode
+pub struct MakeMkvBackend {
+    pub profile: Option<String>,
+    pub control: RipControl,
+}
+
+/// MakeMKV's own title length cutoff, for scans and rips alike so title numbers agree
+///
+/// Zero so short titles (extras, shows under 5 minutes) reach `TitleSelection`, which does the filtering.
+const MAKEMKV_MIN_LENGTH_SECONDS: u32 = 0;
+
+/// Parse a MakeMKV progress line ("PRGV:current,total,max") into a percentage
+pub fn parse_makemkv_progress(line: &str) -> Option<f32> {
+    let parts: Vec<&str> = line.strip_prefix("PRGV:")?.split(',').collect();
+    if parts.len() < 3 {
+        return None;
+    }
+    let current = parts[0].trim().parse::<u32>().ok()?;
+    let max = parts[2].trim().parse::<u32>().ok()?;
+    Some(if max > 0 { (current as f32 / max as f32) * 100.0 } else { 0.0 })
+}
+
+impl RipBackend for MakeMkvBackend {
+    fn name(&self) -> &'static str {
+        "makemkv"
+    }
+
+    fn control(&self) -> &RipControl {
+        &self.control
+    }
+
+    fn scan<'a>(&'a self, device: &'a str) -> BackendFuture<'a, DiscScan> {
+        Box::pin(async move {
+            // Check if makemkvcon is installed
+            let check = Command::new("which")
+                .arg("makemkvcon")
+                .output()
+                .await?;
+
+            if !check.status.success() {
+                return Err(anyhow!("makemkvcon not found. Install MakeMKV from https://www.makemkv.com/"));
+            }
+
+            // Setup MakeMKV settings to skip subtitles
+            setup_makemkv_settings().await?;
+
+            info!("Scanning disc in {}", device);
+            let output = Command::new("makemkvcon")
+                .arg("-r")
+                .arg("info")
+                .arg(format!("dev:{}", device))
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .output()
+                .await
+                .map_err(|e| anyhow!("Failed to start makemkvcon: {}", e))?;
+
+            for line in String::from_utf8_lossy(&output.stderr).lines() {
+                warn!("makemkvcon: {}", line);
+            }
+
+            if !output.status.success() {
+                return Err(anyhow!("Disc scan failed with status: {}", output.status));
+            }
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            debug!("makemkvcon scan output:\n{}", stdout);
+            Ok(DiscScan::parse_makemkv(device, &stdout))
+        })
+    }
+
+    fn rip_title<'a>(
+        &'a self,
+        device: &'a str,
+        title: &'a TitleInfo,
+        output_dir: &'a Path,
+        events: &'a mut (dyn FnMut(RipEvent) + Send),
+    ) -> BackendFuture<'a, PathBuf> {
+        Box::pin(async move {
+            let before = list_files(output_dir).await;
+
+            let mut rip_child = Command::new("makemkvcon")
+                .arg("-r")
+                .arg("--progress=-same")  // Output progress to stdout
+                .arg(format!("--minlength={}", MAKEMKV_MIN_LENGTH_SECONDS))
+                .arg("--noscan")          // Don't scan disc again, we already did
+                .arg("mkv")
+                .arg(format!("dev:{}", device))
+                .arg(title.index.to_string())  // Rip specific title by number
+                .arg(output_dir)
+                .env("MAKEMKV_PROFILE", self.profile.as_deref().unwrap_or("default"))
+                .process_group(0)  // Own process group so pause/stop reach every helper
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .map_err(|e| anyhow!("Failed to start makemkvcon rip: {}", e))?;
+
+            let stdout = rip_child.stdout.take()
+                .ok_or_else(|| anyhow!("Failed to capture stdout"))?;
+            let stderr = rip_child.stderr.take()
+                .ok_or_else(|| anyhow!("Failed to capture stderr"))?;
+
+            let mut stdout_reader = BufReader::new(stdout).lines();
+            let mut stderr_reader = BufReader::new(stderr).lines();
+            let mut stderr_done = false;
+            let mut control = self.control.subscribe();
+            // A pause or stop requested before the tool started applies straight away
+            let pending = *control.borrow_and_update();
+            if pending != RipState::Running {
+                apply_rip_state(pending, &mut rip_child, output_dir, &before, &mut *events).await?;
+            }
+
+            loop {
+                tokio::select! {
+                    result = stdout_reader.next_line() => {
+                        match result {
+                            Ok(Some(line)) => {
+                                debug!("makemkvcon: {}", line);
+                                if let Some(percentage) = parse_makemkv_progress(&line) {
+                                    events(RipEvent::Progress(percentage));
+                                } else if line.starts_with("MSG:") {
+                                    events(RipEvent::Log(line));
+                                }
+                            }
+                            Ok(None) => break,
+                            Err(e) => {
+                                debug!("Error reading rip stdout: {}", e);
+                                break;
+                            }
+                        }
+                    }
+                    result = stderr_reader.next_line(), if !stderr_done => {
+                        match result {
+                            Ok(Some(line)) => {
+                                if line.contains("ERROR") || line.contains("error") || line.contains("failed") {
+                                    tracing::error!("makemkvcon: {}", line);
+                                    events(RipEvent::Log(format!("❌ ERROR: {}", line)));
+                                } else {
+                                    debug!("makemkvcon stderr: {}", line);
+                                }
+                            }
+                            Ok(None) => stderr_done = true, // Stop polling a closed stream
+                            Err(e) => debug!("Error reading rip stderr: {}", e),
+                        }
+                    }
+                    Ok(()) = control.changed() => {
+                        let state = *control.borrow_and_update();
+                        apply_rip_state(state, &mut rip_child, output_dir, &before, &mut *events).await?;
+                    }
+                }
+            }
+
+            let rip_status = rip_child.wait().await?;
+            if !rip_status.success() {
+                return Err(anyhow!("Failed to rip title {}: status {}", title.index, rip_status));
+            }
+
+            find_new_file(output_dir, &before, "mkv").await
+                .ok_or_else(|| anyhow!("Could not find ripped file for title {}", title.index))
+        })
+    }
+}
+
+/// Configure MakeMKV to skip subtitles and keep titles of any length
+async fn setup_makemkv_settings() -> Result<()> {
+    use std::env;
+
+    // Get MakeMKV data directory
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    let makemkv_dir = PathBuf::from(home).join(".MakeMKV");
+
+    // Create directory if it doesn't exist
+    tokio::fs::create_dir_all(&makemkv_dir).await?;
+
+    let settings_file = makemkv_dir.join("settings.conf");
+
+    tokio::fs::write(&settings_file, makemkv_settings()).await?;
+
+    info!("MakeMKV settings configured: skip subtitles, no minimum title length");
+
+    Ok(())
+}
+
+/// Contents of MakeMKV's settings.conf
+fn makemkv_settings() -> String {
+    // Settings to disable subtitle selection by default
+    // app_DefaultSelectionString controls what tracks are selected
+    // Format: +AUDIOTRACK,+VIDEOTRACK,-SUBTITLETRACK
+    format!(
+        r#"# Ripley auto-generated MakeMKV settings
+# Skip subtitles by default
+app_DefaultSelectionString = "+sel:all,-sel:subtitle"
+
+# Minimum title length in seconds (title selection rules do the filtering)
+app_MinLength = "{}"
+"#,
+        MAKEMKV_MIN_LENGTH_SECONDS
+    )
+}
+
+// ---------------------------------------------------------------------------
+// abcde / cdparanoia
+// ---------------------------------------------------------------------------
+
+/// Audio CD backend: cdparanoia reads the table of contents, abcde rips and encodes to FLAC
+pub struct AbcdeBackend {
+    pub quality: u8,
+    pub control: RipControl,
+}
+
+/// Parse the track table printed by `cdparanoia -Q`
+///
+/// Track lengths are in sectors (75 per second).
+pub fn parse_cdparanoia_toc(output: &str) -> Vec<TitleInfo> {
+    let re = regex::Regex::new(r"^\s*(\d+)\.\s+(\d+)\s+\[").unwrap();
+    output.lines()
+        .filter_map(|line| {
+            let caps = re.captures(line)?;
+            let index = caps[1].parse::<u32>().ok()?;
+            let sectors = caps[2].parse::<u64>().ok()?;
+            Some(TitleInfo {
+                index,
+                duration_seconds: (sectors / 75) as u32,
+                size_bytes: Some(sectors * 2352),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+impl RipBackend for AbcdeBackend {
+    fn name(&self) -> &'static str {
+        "abcde"
+    }
+
+    fn control(&self) -> &RipControl {
+        &self.control
+    }
+
+    fn scan<'a>(&'a self, device: &'a str) -> BackendFuture<'a, DiscScan> {
+        Box::pin(async move {
+            // Kill any existing abcde processes for this device
+            info!("Checking for existing abcde processes on {}...", device);
+            match Command::new("pkill")
+                .arg("-f")
+                .arg(format!("abcde.*{}", device))
+                .output()
+                .await {
+                    Ok(_) => info!("Killed any existing abcde processes"),
+                    Err(e) => tracing::error!("Failed to kill abcde processes: {}", e),
+                }
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+            // Unmount the disc with retries (macOS may auto-remount, Linux may need permissions)
+            info!("Unmounting {}...", device);
+            for attempt in 1..=3 {
+                match crate::drive::unmount_disc(device).await {
+                    Ok(_) => {
+                        info!("Successfully unmounted {} (attempt {})", device, attempt);
+                        break;
+                    }
+                    Err(e) => {
+                        warn!("Unmount attempt {} failed: {}", attempt, e);
+                        if attempt < 3 {
+                            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                        }
+                    }
+                }
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+
+            // cdparanoia prints the table of contents to stderr
+            let output = Command::new("cdparanoia")
+                .arg("-Q")
+                .arg("-d")
+                .arg(device)
+                .output()
+                .await
+                .map_err(|e| anyhow!("Failed to run cdparanoia: {}", e))?;
+
+            let toc = String::from_utf8_lossy(&output.stderr);
+            Ok(DiscScan {
+                device: device.to_string(),
+                disc_type: Some("Audio CD".to_string()),
+                titles: parse_cdparanoia_toc(&toc),
+                scanned_at: Some(chrono::Utc::now()),
+                ..Default::default()
+            })
+        })
+    }
+
+    fn rip_title<'a>(
+        &'a self,
+        device: &'a str,
+        title: &'a TitleInfo,
+        output_dir: &'a Path,
+        events: &'a mut (dyn FnMut(RipEvent) + Send),
+    ) -> BackendFuture<'a, PathBuf> {
+        Box::pin(async move {
+            let before = list_files(output_dir).await;
+
+            // Configure abcde
+            let config = crate::ripper::create_abcde_config(output_dir, self.quality)?;
+            let config_path = output_dir.join(".abcde.conf");
+            if let Err(e) = tokio::fs::write(&config_path, &config).await {
+                tracing::error!("Failed to write abcde config: {}", e);
+                return Err(anyhow!("Failed to write config: {}", e));
+            }
+
+            let mut child = Command::new("abcde")
+                .arg("-c")
+                .arg(&config_path)
+                .arg("-d")
+                .arg(device)
+                .arg("-o")
+                .arg("flac")
+                .arg("-n")  // Tracks are named from the disc metadata fetched before the rip; skip abcde's CDDB lookup
+                .arg("-N")
+                .arg(title.index.to_string())
+                .process_group(0)  // abcde forks cdparanoia and flac; signal them all together
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .map_err(|e| anyhow!("Failed to start abcde: {}", e))?;
+
+            let stdout = child.stdout.take()
+                .ok_or_else(|| anyhow!("Failed to capture stdout"))?;
+            let stderr = child.stderr.take()
+                .ok_or_else(|| anyhow!("Failed to capture stderr"))?;
+            let mut stdout_reader = BufReader::new(stdout).lines();
+            let mut stderr_reader = BufReader::new(stderr).lines();
+            let mut stderr_done = false;
+            let mut stderr_lines = Vec::new();
+            let mut control = self.control.subscribe();
+            // A pause or stop requested before the tool started applies straight away
+            let pending = *control.borrow_and_update();
+            if pending != RipState::Running {
+                apply_rip_state(pending, &mut child, output_dir, &before, &mut *events).await?;
+            }
+
+            loop {
+                tokio::select! {
+                    result = stdout_reader.next_line() => {
+                        match result {
+                            Ok(Some(line)) => {
+                                info!("abcde: {}", line);
+                                if line.contains("Grabbing track") || line.contains("Reading track") {
+                                    events(RipEvent::Progress(0.0));
+                                } else if line.contains("Encoding") || line.contains("encoding") {
+                                    events(RipEvent::Encoding);
+                                }
+                                events(RipEvent::Log(line));
+                            }
+                            Ok(None) => break,
+                            Err(e) => {
+                                debug!("Error reading stdout: {}", e);
+                                break;
+                            }
+                        }
+                    }
+                    result = stderr_reader.next_line(), if !stderr_done => {
+                        match result {
+                            Ok(Some(line)) => {
+                                if line.contains("ERROR") || line.contains("error") || line.contains("failed") {
+                                    tracing::error!("abcde: {}", line);
+                                    events(RipEvent::Log(format!("ERROR: {}", line)));
+                                } else {
+                                    warn!("abcde: {}", line);
+                                    events(RipEvent::Log(line.clone()));
+                                }
+                                stderr_lines.push(line);
+                            }
+                            Ok(None) => stderr_done = true, // Stop polling a closed stream
+                            Err(e) => debug!("Error reading stderr: {}", e),
+                        }
+                    }
+                    Ok(()) = control.changed() => {
+                        let state = *control.borrow_and_update();
+                        apply_rip_state(state, &mut child, output_dir, &before, &mut *events).await?;
+                    }
+                }
+            }
+
+            // Drain any remaining stderr
+            while let Ok(Some(line)) = stderr_reader.next_line().await {
+                stderr_lines.push(line);
+            }
+
+            let status = child.wait().await?;
+            if !status.success() {
+                let error_msg = if !stderr_lines.is_empty() {
+                    format!("abcde failed with status: {}\nErrors:\n{}", status, stderr_lines.join("\n"))
+                } else {
+                    format!("abcde failed with status: {}", status)
+                };
+                return Err(anyhow!(error_msg));
+            }
+
+            events(RipEvent::Progress(100.0));
+            find_new_file(output_dir, &before, "flac").await
+                .ok_or_else(|| anyhow!("Could not find ripped file for track {}", title.index))
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Fake backend
+// ---------------------------------------------------------------------------
+
+/// Script describing what the fake backend reports and writes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FakeScript {
+    /// Media type to report when no drive is present (defaults to DVD)
+    #[serde(default)]
+    pub media_type: Option<MediaType>,
+    #[serde(default)]
+    pub volume_label: Option<String>,
+    pub titles: Vec<FakeTitle>,
+    /// Number of progress events emitted per title
+    #[serde(default = "default_fake_steps")]
+    pub progress_steps: u32,
+    /// Delay between progress events
+    #[serde(default)]
+    pub step_delay_ms: u64,
+    /// File extension of the written output
+    #[serde(default = "default_fake_extension")]
+    pub extension: String,
+}
+
+/// A title on the fake disc
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FakeTitle {
+    pub index: u32,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub duration_seconds: u32,
+    #[serde(default)]
+    pub chapter_count: u32,
+    /// Segment map as MakeMKV would report it, e.g. "1-4"
+    #[serde(default)]
+    pub segments: Option<String>,
+    /// Source file as MakeMKV would report it, e.g. "00800.mpls" on Blu-ray
+    #[serde(default)]
+    pub source_file: Option<String>,
+    /// Bytes written to the output file
+    #[serde(default = "default_fake_size")]
+    pub size_bytes: u64,
+    /// Fail partway through ripping this title
+    #[serde(default)]
+    pub fail: bool,
+}
+
+fn default_fake_steps() -> u32 { 4 }
+fn default_fake_extension() -> String { "mkv".to_string() }
+fn default_fake_size() -> u64 { 1024 }
+
+impl Default for FakeScript {
+    /// A four-episode TV disc with a play-all title
+    fn default() -> Self {
+        let title = |index: u32, duration_seconds: u32, segments: &str| FakeTitle {
+            index,
+            name: Some(format!("Title {}", index)),
+            duration_seconds,
+            chapter_count: 6,
+            segments: Some(segments.to_string()),
+            source_file: None,
+            size_bytes: default_fake_size(),
+            fail: false,
+        };
+        FakeScript {
+            media_type: None,
+            volume_label: Some("FAKE_DISC".to_string()),
+            titles: vec![
+                title(0, 88 * 60 + 12, "1-4"),
+                title(1, 22 * 60 + 3, "1"),
+                title(2, 22 * 60 + 1, "2"),
+                title(3, 21 * 60 + 58, "3"),
+                title(4, 22 * 60 + 10, "4"),
+            ],
+            progress_steps: default_fake_steps(),
+            step_delay_ms: 0,
+            extension: default_fake_extension(),
+        }
+    }
+}
+
+impl FakeScript {
+    /// Load a script from a YAML or JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read fake backend script {}: {}", path.display(), e))?;
+        serde_yaml::from_str(&contents)
+            .map_err(|e| anyhow!("Invalid fake backend script {}: {}", path.display(), e))
+    }
+}
+
+/// Backend that replays a script instead of touching a drive, for tests and demos
+pub struct FakeBackend {
+    pub script: FakeScript,
+    pub control: RipControl,
+}
+
+impl FakeBackend {
+    pub fn new(script: FakeScript) -> Self {
+        FakeBackend { script, control: RipControl::default() }
+    }
+}
+
+impl RipBackend for FakeBackend {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    fn control(&self) -> &RipControl {
+        &self.control
+    }
+
+    fn media_hint(&self) -> Option<MediaType> {
+        Some(self.script.media_type.clone().unwrap_or(MediaType::DVD))
+    }
+
+    fn scan<'a>(&'a self, device: &'a str) -> BackendFuture<'a, DiscScan> {
+        Box::pin(async move {
+            debug!("Fake backend scanning {}", device);
+            Ok(DiscScan {
+                device: device.to_string(),
+                disc_type: Some(match self.script.media_type {
+                    Some(MediaType::BluRay) => "Blu-ray disc".to_string(),
+                    _ => "Fake disc".to_string(),
+                }),
+                disc_name: self.script.volume_label.clone(),
+                volume_label: self.script.volume_label.clone(),
+                titles: self.script.titles.iter()
+                    .map(|t| TitleInfo {
+                        index: t.index,
+                        name: t.name.clone(),
+                        duration_seconds: t.duration_seconds,
+                        size_bytes: Some(t.size_bytes),
+                        chapter_count: t.chapter_count,
+                        segment_count: t.segments.as_deref().map(|m| m.split(',').count() as u32).unwrap_or(0),
+                        segment_map: t.segments.clone(),
+                        source_file: t.source_file.clone(),
+                        ..Default::default()
+                    })
+                    .collect(),
+                scanned_at: Some(chrono::Utc::now()),
+            })
+        })
+    }
+
+    fn rip_title<'a>(
+        &'a self,
+        _device: &'a str,
+        title: &'a TitleInfo,
+        output_dir: &'a Path,
+        events: &'a mut (dyn FnMut(RipEvent) + Send),
+    ) -> BackendFuture<'a, PathBuf> {
+        Box::pin(async move {
+            let script_title = self.script.titles.iter()
+                .find(|t| t.index == title.index)
+                .ok_or_else(|| anyhow!("Title {} is not on the fake disc", title.index))?;
+
+            // Write the output up front, like a real tool, so stopping has something to clean up
+            let before = list_files(output_dir).await;
+            let path = output_dir.join(format!("title_t{:02}.{}", title.index, self.script.extension));
+            tokio::fs::write(&path, b"").await?;
+
+            let steps = self.script.progress_steps.max(1);
+            for step in 1..=steps {
+                if self.control.state() == RipState::Paused {
+                    events(RipEvent::Paused);
+                    if self.control.wait_while_paused().await == RipState::Running {
+                        events(RipEvent::Resumed);
+                    }
+                }
+                if self.control.is_stopped() {
+                    remove_partial_output(output_dir, &before).await;
+                    return Err(anyhow!("Rip stopped"));
+                }
+                if script_title.fail && step * 2 > steps {
+                    remove_partial_output(output_dir, &before).await;
+                    return Err(anyhow!("Scripted failure ripping title {}", title.index));
+                }
+                if self.script.step_delay_ms > 0 {
+                    tokio::time::sleep(tokio::time::Duration::from_millis(self.script.step_delay_ms)).await;
+                }
+                events(RipEvent::Progress(step as f32 / steps as f32 * 100.0));
+            }
+
+            tokio::fs::write(&path, vec![0u8; script_title.size_bytes as usize]).await?;
+            events(RipEvent::Log(format!("Fake backend wrote {}", path.display())));
+            Ok(path)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_makemkv_progress() {
+        assert_eq!(parse_makemkv_progress("PRGV:32768,0,65536"), Some(50.0));
+        assert_eq!(parse_makemkv_progress("PRGV:1,0,0"), Some(0.0));
+        assert_eq!(parse_makemkv_progress("PRGT:5018,0,\"Saving\""), None);
+    }
+
+    #[test]
+    fn test_makemkv_keeps_short_titles() {
+        assert!(makemkv_settings().contains("app_MinLength = \"0\""));
+
+        // A 2-minute extra MakeMKV reports reaches the title selection, which rips it with extras on
+        let scan = DiscScan::parse_makemkv("/dev/sr0", r#"TCOUNT:2
+TINFO:0,9,0,"0:22:03"
+TINFO:1,9,0,"0:02:00"
+"#);
+        let selection = crate::title_selection::TitleSelection { include_extras: true, ..Default::default() };
+        let selected: Vec<u32> = selection.select(&scan.titles).selected.iter().map(|t| t.index).collect();
+        assert_eq!(selected, vec![0, 1]);
+
+        // So does a show whose episodes are under 5 minutes
+        let shorts = crate::title_selection::TitleSelection { min_minutes: Some(1), max_minutes: Some(3), ..Default::default() };
+        assert_eq!(shorts.select(&scan.titles).selected.iter().map(|t| t.index).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_parse_cdparanoia_toc() {
+        let toc = r#"cdparanoia III release 10.2 (September 11, 2008)
+
+Table of contents (audio tracks only):
+track        length               begin        copy pre ch
+===========================================================
+  1.    16503 [03:40.03]        0 [00:00.00]    no   no  2
+  2.    21972 [04:52.72]    16503 [03:40.03]    no   no  2
+TOTAL   38475 [08:33.00]    (audio only)
+"#;
+        let tracks = parse_cdparanoia_toc(toc);
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0].index, 1);
+        assert_eq!(tracks[0].duration_seconds, 220);
+        assert_eq!(tracks[1].duration_seconds, 292);
+    }
+
+    #[test]
+    fn test_backend_for_profile() {
+        let backend = backend_for_profile(None, &MediaType::DVD, 5, RipControl::default()).unwrap();
+        assert_eq!(backend.name(), "makemkv");
+        let backend = backend_for_profile(None, &MediaType::AudioCD, 5, RipControl::default()).unwrap();
+        assert_eq!(backend.name(), "abcde");
+
+        let profile = RipProfile {
+            name: "Test".to_string(),
+            description: None,
+            audio_quality: None,
+            makemkv_profile: None,
+            is_default: false,
+            backend: Some("fake".to_string()),
+            fake_script: None,
+            title_selection: None,
+        };
+        let backend = backend_for_profile(Some(&profile), &MediaType::None, 5, RipControl::default()).unwrap();
+        assert_eq!(backend.name(), "fake");
+        assert_eq!(backend.media_hint(), Some(MediaType::DVD));
+
+        let bad = RipProfile { backend: Some("vhs".to_string()), ..profile };
+        assert!(backend_for_profile(Some(&bad), &MediaType::DVD, 5, RipControl::default()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fake_backend_rip() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FakeBackend::new(FakeScript::default());
+
+        let titles = backend.scan("/dev/fake").await.unwrap().titles;
+        assert_eq!(titles.len(), 5);
+
+        let mut progress = Vec::new();
+        let path = backend.rip_title("/dev/fake", &titles[1], dir.path(), &mut |event| {
+            if let RipEvent::Progress(p) = event {
+                progress.push(p);
+            }
+        }).await.unwrap();
+
+        assert!(path.exists());
+        assert_eq!(progress, vec![25.0, 50.0, 75.0, 100.0]);
+    }
+
+    #[tokio::test]
+    async fn test_fake_backend_scripted_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let script: FakeScript = serde_yaml::from_str(r#"
+titles:
+  - index: 0
+    duration_seconds: 1320
+    fail: true
+"#).unwrap();
+        let backend = FakeBackend::new(script);
+        let titles = backend.scan("/dev/fake").await.unwrap().titles;
+
+        let result = backend.rip_title("/dev/fake", &titles[0], dir.path(), &mut |_| {}).await;
+        assert!(result.is_err());
+        assert!(!dir.path().join("title_t00.mkv").exists());
+    }
+
+    #[tokio::test]
+    async fn test_fake_backend_pause_and_resume() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FakeBackend::new(FakeScript::default());
+        let titles = backend.scan("/dev/fake").await.unwrap().titles;
+        let control = backend.control().clone();
+
+        let mut events = Vec::new();
+        let mut on_event = |event: RipEvent| {
+            if event == RipEvent::Progress(25.0) {
+                assert!(control.pause());
+            }
+            events.push(event);
+        };
+        let rip = backend.rip_title("/dev/fake", &titles[1], dir.path(), &mut on_event);
+        let resume = async {
+            let mut rx = backend.control().subscribe();
+            rx.wait_for(|state| *state == RipState::Paused).await.unwrap();
+            assert!(dir.path().join("title_t01.mkv").exists());
+            assert!(backend.control().resume());
+        };
+        let (result, _) = tokio::join!(rip, resume);
+
+        assert!(result.unwrap().exists());
+        assert_eq!(events, vec![
+            RipEvent::Progress(25.0),
+            RipEvent::Paused,
+            RipEvent::Resumed,
+            RipEvent::Progress(50.0),
+            RipEvent::Progress(75.0),
+            RipEvent::Progress(100.0),
+            RipEvent::Log(format!("Fake backend wrote {}", dir.path().join("title_t01.mkv").display())),
+        ]);
+        assert_eq!(backend.control().state(), RipState::Running);
+    }
+
+    #[tokio::test]
+    async fn test_fake_backend_stop_removes_partial_output() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("title_t00.mkv"), b"earlier rip").unwrap();
+        let backend = FakeBackend::new(FakeScript::default());
+        let titles = backend.scan("/dev/fake").await.unwrap().titles;
+        let control = backend.control().clone();
+
+        let result = backend.rip_title("/dev/fake", &titles[1], dir.path(), &mut |event| {
+            if event == RipEvent::Progress(50.0) {
+                control.stop();
+            }
+        }).await;
+
+        assert_eq!(result.unwrap_err().to_string(), "Rip stopped");
+        assert!(!dir.path().join("title_t01.mkv").exists());
+        assert!(dir.path().join("title_t00.mkv").exists());
+        assert!(!backend.control().pause());
+        assert!(!backend.control().resume());
+    }
+
+    #[tokio::test]
+    async fn test_fake_backend_stopped_before_rip() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FakeBackend::new(FakeScript::default());
+        let titles = backend.scan("/dev/fake").await.unwrap().titles;
+        backend.control().stop();
+
+        let mut progress = Vec::new();
+        let result = backend.rip_title("/dev/fake", &titles[1], dir.path(), &mut |event| {
+            if let RipEvent::Progress(p) = event {
+                progress.push(p);
+            }
+        }).await;
+
+        assert_eq!(result.unwrap_err().to_string(), "Rip stopped");
+        assert!(progress.is_empty());
+        assert!(!dir.path().join("title_t01.mkv").exists());
+    }
+}
@@ -1,11 +1,12 @@
 use anyhow::Result;
-use std::path::Path;
-use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
-use tracing::{debug, info};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
 use crate::metadata::DiscMetadata;
+use crate::disc_scan::TitleInfo;
+use crate::naming::NameFields;
+use crate::rename_journal::RenameJournal;
+use crate::rip_backend::{RipBackend, RipEvent, RipState};
 
 #[derive(Debug, Clone)]
 pub struct RipProgress {
@@ -26,57 +27,32 @@ pub enum RipStatus {
     Idle,
     FetchingMetadata,
     Ripping,
+    Paused,
     Encoding,
     Complete,
     Error(String),
 }
 
-/// Rip a CD using abcde
+/// Rip a CD track by track using the given backend (abcde by default)
+///
+/// Each track is renamed with the music naming `template` (relative to `output_dir`) as soon as it's ripped,
+/// recorded in `journal`. Returns the folders the tracks ended up in.
+#[allow(clippy::too_many_arguments)]
 pub async fn rip_cd<F, L>(
+    backend: &dyn RipBackend,
     device: &str,
     metadata: &DiscMetadata,
     output_dir: &Path,
-    quality: u8,
+    template: &str,
+    journal: &RenameJournal<'_>,
     mut progress_callback: F,
     mut log_callback: L,
-) -> Result<()>
+) -> Result<Vec<PathBuf>>
 where
     F: FnMut(RipProgress) + Send,
     L: FnMut(String) + Send,
 {
-    info!("Starting rip of {} - {} from {}", metadata.artist, metadata.album, device);
-
-    // Kill any existing abcde processes for this device
-    info!("Checking for existing abcde processes on {}...", device);
-    match Command::new("pkill")
-        .arg("-f")
-        .arg(format!("abcde.*{}", device))
-        .output()
-        .await {
-            Ok(_) => info!("Killed any existing abcde processes"),
-            Err(e) => tracing::error!("Failed to kill abcde processes: {}", e),
-        }
-    
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-
-    // Unmount the disc with retries (macOS may auto-remount, Linux may need permissions)
-    info!("Unmounting {}...", device);
-    for attempt in 1..=3 {
-        match crate::drive::unmount_disc(device).await {
-            Ok(_) => {
-                info!("Successfully unmounted {} (attempt {})", device, attempt);
-                break;
-            }
-            Err(e) => {
-                tracing::warn!("Unmount attempt {} failed: {}", attempt, e);
-                if attempt < 3 {
-                    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                }
-            }
-        }
-    }
-    
-    tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+    info!("Starting rip of {} - {} from {} ({} backend)", metadata.artist, metadata.album, device, backend.name());
 
     // Create output directory structure: Artist/Album/
     info!("Creating output directory...");
@@ -90,156 +66,151 @@ where
     }
     info!("Output directory: {}", album_dir.display());
 
-    // Configure abcde
-    info!("Generating abcde config...");
-    let config = create_abcde_config(&album_dir, quality)?;
-    let config_path = album_dir.join(".abcde.conf");
-    if let Err(e) = tokio::fs::write(&config_path, &config).await {
-        tracing::error!("Failed to write abcde config: {}", e);
-        return Err(anyhow::anyhow!("Failed to write config: {}", e));
-    }
-    info!("Config written to: {}", config_path.display());
-
-    // Run abcde with progress tracking
-    info!("Starting abcde process...");
-    let mut child = match Command::new("abcde")
-        .arg("-c")
-        .arg(&config_path)
-        .arg("-d")
-        .arg(device)
-        .arg("-o")
-        .arg("flac")
-        .arg("-N")
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn() {
-            Ok(c) => c,
-            Err(e) => {
-                tracing::error!("Failed to spawn abcde: {}", e);
-                return Err(anyhow::anyhow!("Failed to start abcde: {}", e));
-            }
-        };
-    info!("abcde process started, monitoring output...");
-
-    // Track progress by parsing abcde output in real-time
-    let stdout = child.stdout.take()
-        .ok_or_else(|| anyhow::anyhow!("Failed to capture stdout"))?;
-    let stderr = child.stderr.take()
-        .ok_or_else(|| anyhow::anyhow!("Failed to capture stderr"))?;
-    let mut stdout_reader = BufReader::new(stdout).lines();
-    let mut stderr_reader = BufReader::new(stderr).lines();
+    // Read the track list from the disc, falling back to the metadata track list
+    let tracks = match backend.scan(device).await {
+        Ok(scan) if !scan.titles.is_empty() => scan.titles,
+        Ok(_) => {
+            warn!("No tracks reported by {}, using metadata track list", backend.name());
+            tracks_from_metadata(metadata)
+        }
+        Err(e) => {
+            warn!("Track scan failed ({}), using metadata track list", e);
+            log_callback(format!("⚠️  Could not read track list: {}", e));
+            tracks_from_metadata(metadata)
+        }
+    };
 
-    let total_tracks = metadata.tracks.len() as u32;
-    let mut current_track = 0;
-    let mut stderr_lines = Vec::new();
+    let total_tracks = tracks.len() as u32;
+    let mut track_dirs: Vec<PathBuf> = Vec::new();
 
-    // Process output in real-time
-    loop {
-        tokio::select! {
-            result = stdout_reader.next_line() => {
-                match result {
-                    Ok(Some(line)) => {
-                        info!("abcde: {}", line);
-                        log_callback(line.clone());
-                        
-                        // Parse progress from abcde output
-                        if line.contains("Grabbing track") || line.contains("Reading track") {
-                            if let Some(track_num) = line.split("track").nth(1)
-                                .and_then(|s| s.split_whitespace().next())
-                                .and_then(|s| s.parse::<u32>().ok()) 
-                            {
-                                current_track = track_num;
-                                let track_name = metadata.tracks.get((current_track - 1) as usize)
-                                    .map(|t| t.title.clone())
-                                    .unwrap_or_else(|| format!("Track {}", current_track));
+    for (idx, track) in tracks.iter().enumerate() {
+        let current_track = idx as u32 + 1;
+        let track_name = metadata.tracks.iter()
+            .find(|t| t.number == track.index)
+            .map(|t| t.title.clone())
+            .unwrap_or_else(|| format!("Track {}", track.index));
 
-                                progress_callback(RipProgress {
-                                    current_track,
-                                    total_tracks,
-                                    track_name: track_name.clone(),
-                                    percentage: (current_track as f32 / total_tracks as f32) * 100.0,
-                                    status: RipStatus::Ripping,
-                                    speed_mbps: None,
-                                    bytes_processed: None,
-                                });
-                            }
-                        } else if line.contains("Encoding") || line.contains("encoding") {
-                            progress_callback(RipProgress {
-                                current_track,
-                                total_tracks,
-                                track_name: metadata.tracks.get((current_track - 1) as usize)
-                                    .map(|t| t.title.clone())
-                                    .unwrap_or_else(|| format!("Track {}", current_track)),
-                                percentage: (current_track as f32 / total_tracks as f32) * 100.0,
-                                status: RipStatus::Encoding,
-                                speed_mbps: None,
-                                bytes_processed: None,
-                            });
-                        }
-                    }
-                    Ok(None) => break,
-                    Err(e) => {
-                        debug!("Error reading stdout: {}", e);
-                        break;
-                    }
-                }
-            }
-            result = stderr_reader.next_line() => {
-                match result {
-                    Ok(Some(line)) => {
-                        if line.contains("ERROR") || line.contains("error") || line.contains("failed") {
-                            tracing::error!("abcde: {}", line);
-                            log_callback(format!("ERROR: {}", line));
-                        } else {
-                            tracing::warn!("abcde: {}", line);
-                            log_callback(line.clone());
-                        }
-                        stderr_lines.push(line);
-                    }
-                    Ok(None) => {}
-                    Err(e) => debug!("Error reading stderr: {}", e),
-                }
-            }
+        // A pause between tracks holds the next track back until resumed
+        if backend.control().state() == RipState::Paused {
+            log_callback(format!("⏸️  Paused before track {}", track.index));
+            progress_callback(RipProgress {
+                current_track,
+                total_tracks,
+                track_name: track_name.clone(),
+                percentage: (idx as f32 / total_tracks as f32) * 100.0,
+                status: RipStatus::Paused,
+                speed_mbps: None,
+                bytes_processed: None,
+            });
+            backend.control().wait_while_paused().await;
         }
-    }
-
-    // Drain any remaining stderr
-    while let Ok(Some(line)) = stderr_reader.next_line().await {
-        if line.contains("ERROR") || line.contains("error") || line.contains("failed") {
-            tracing::error!("abcde: {}", line);
-        } else {
-            tracing::warn!("abcde: {}", line);
+        if backend.control().is_stopped() {
+            return Err(anyhow::anyhow!("Rip stopped before track {}", track.index));
         }
-        stderr_lines.push(line);
-    }
-
-    // Wait for completion
-    let status = child.wait().await?;
 
-    if status.success() {
-        info!("Successfully ripped {} - {}", metadata.artist, metadata.album);
         progress_callback(RipProgress {
-            current_track: total_tracks,
+            current_track,
             total_tracks,
-            track_name: "Complete".to_string(),
-            percentage: 100.0,
-            status: RipStatus::Complete,
+            track_name: track_name.clone(),
+            percentage: (idx as f32 / total_tracks as f32) * 100.0,
+            status: RipStatus::Ripping,
             speed_mbps: None,
             bytes_processed: None,
         });
-        Ok(())
-    } else {
-        // Include stderr in error message
-        let error_msg = if !stderr_lines.is_empty() {
-            format!("abcde failed with status: {}\nErrors:\n{}", 
-                status, 
-                stderr_lines.join("\n"))
-        } else {
-            format!("abcde failed with status: {}", status)
+
+        let result = {
+            let mut on_event = |event: RipEvent| match event {
+                RipEvent::Progress(track_percentage) => progress_callback(RipProgress {
+                    current_track,
+                    total_tracks,
+                    track_name: track_name.clone(),
+                    percentage: ((idx as f32 + track_percentage / 100.0) / total_tracks as f32) * 100.0,
+                    status: RipStatus::Ripping,
+                    speed_mbps: None,
+                    bytes_processed: None,
+                }),
+                RipEvent::Encoding => progress_callback(RipProgress {
+                    current_track,
+                    total_tracks,
+                    track_name: track_name.clone(),
+                    percentage: (current_track as f32 / total_tracks as f32) * 100.0,
+                    status: RipStatus::Encoding,
+                    speed_mbps: None,
+                    bytes_processed: None,
+                }),
+                RipEvent::Paused | RipEvent::Resumed => progress_callback(RipProgress {
+                    current_track,
+                    total_tracks,
+                    track_name: track_name.clone(),
+                    percentage: (idx as f32 / total_tracks as f32) * 100.0,
+                    status: if event == RipEvent::Paused { RipStatus::Paused } else { RipStatus::Ripping },
+                    speed_mbps: None,
+                    bytes_processed: None,
+                }),
+                RipEvent::Log(line) => log_callback(line),
+            };
+            backend.rip_title(device, track, &album_dir, &mut on_event).await
         };
-        tracing::error!("{}", error_msg);
-        Err(anyhow::anyhow!(error_msg))
+
+        let ripped = match result {
+            Ok(path) => path,
+            Err(e) => {
+                tracing::error!("Failed to rip track {}: {}", track.index, e);
+                return Err(e);
+            }
+        };
+
+        // Name the track from the music template instead of abcde's own format
+        let fields = NameFields {
+            artist: Some(metadata.artist.clone()),
+            album: Some(metadata.album.clone()),
+            year: metadata.year.clone(),
+            track: Some(track.index),
+            title: Some(track_name.clone()),
+            ..Default::default()
+        }.with_ext(ripped.extension().and_then(|e| e.to_str()).unwrap_or("flac"));
+        let renamed = crate::naming::render(template, &fields)
+            .map(|relative| output_dir.join(relative))
+            .and_then(|dest| {
+                journal.rename(&ripped, &dest, "music_template")?;
+                Ok(dest)
+            });
+        let final_path = match renamed {
+            Ok(dest) => dest,
+            Err(e) => {
+                warn!("Failed to rename track {}: {}", track.index, e);
+                log_callback(format!("⚠️  Could not rename track {}: {}", track.index, e));
+                ripped
+            }
+        };
+        if let Some(dir) = final_path.parent().filter(|dir| !track_dirs.iter().any(|d| d == dir)) {
+            track_dirs.push(dir.to_path_buf());
+        }
     }
+
+    info!("Successfully ripped {} - {}", metadata.artist, metadata.album);
+    progress_callback(RipProgress {
+        current_track: total_tracks,
+        total_tracks,
+        track_name: "Complete".to_string(),
+        percentage: 100.0,
+        status: RipStatus::Complete,
+        speed_mbps: None,
+        bytes_processed: None,
+    });
+    Ok(track_dirs)
+}
+
+/// Build a track list from metadata when the disc can't be scanned
+fn tracks_from_metadata(metadata: &DiscMetadata) -> Vec<TitleInfo> {
+    metadata.tracks.iter()
+        .map(|t| TitleInfo {
+            index: t.number,
+            name: Some(t.title.clone()),
+            duration_seconds: t.duration.unwrap_or(0),
+            ..Default::default()
+        })
+        .collect()
 }
 
 /// Create minimal abcde configuration
@@ -266,7 +237,6 @@ CDDBURL="http://gnudb.gnudb.org/~cddb/cddb.cgi"
     Ok(config)
 }
 
-
 /// Sanitize filename by removing invalid characters
 pub fn sanitize_filename(name: &str) -> String {
     name.chars()
@@ -281,15 +251,6 @@ pub fn sanitize_filename(name: &str) -> String {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_to_pascal_case_with_periods() {
-        assert_eq!(to_pascal_case_with_periods("Foster's Home for Imaginary Friends"), "Fosters.Home.For.Imaginary.Friends");
-        assert_eq!(to_pascal_case_with_periods("The Matrix"), "The.Matrix");
-        assert_eq!(to_pascal_case_with_periods("AC/DC - Back in Black"), "Ac.Dc.Back.In.Black");
-        assert_eq!(to_pascal_case_with_periods("Star Wars: Episode IV"), "Star.Wars.Episode.Iv");
-        assert_eq!(to_pascal_case_with_periods("21 Jump Street"), "21.Jump.Street");
-    }
-
     #[test]
     fn test_sanitize_filename() {
         assert_eq!(sanitize_filename("Normal Name"), "Normal Name");
@@ -312,6 +273,80 @@ mod tests {
         assert!(config.contains("FLACOPTS=\"-0f\""));
     }
 
+    #[tokio::test]
+    async fn test_rip_cd_names_tracks_from_template() {
+        use crate::rip_backend::{FakeBackend, FakeScript};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut script = FakeScript { extension: "flac".to_string(), ..FakeScript::default() };
+        script.titles.truncate(2);
+        let backend = FakeBackend::new(script);
+        let track = |number: u32, title: &str| crate::metadata::Track {
+            number,
+            title: title.to_string(),
+            artist: None,
+            duration: None,
+        };
+        let metadata = DiscMetadata {
+            artist: "AC/DC".to_string(),
+            album: "Back in Black".to_string(),
+            year: Some("1980".to_string()),
+            genre: None,
+            tracks: vec![track(0, "Hells Bells"), track(1, "Shoot to Thrill")],
+        };
+
+        let track_dirs = rip_cd(
+            &backend,
+            "/dev/fake",
+            &metadata,
+            dir.path(),
+            "{artist}/{album} ({year})/{track:02}. {title}.{ext}",
+            &RenameJournal::new(None, false),
+            |_| {},
+            |_| {},
+        ).await.unwrap();
+
+        let album = dir.path().join("AC_DC").join("Back in Black (1980)");
+        assert_eq!(track_dirs, vec![album.clone()]);
+        assert!(album.join("00. Hells Bells.flac").exists());
+        assert!(album.join("01. Shoot to Thrill.flac").exists());
+    }
+
+    #[tokio::test]
+    async fn test_rip_cd_stops_between_tracks() {
+        use crate::rip_backend::{FakeBackend, FakeScript};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut script = FakeScript { extension: "flac".to_string(), ..FakeScript::default() };
+        script.titles.truncate(2);
+        let backend = FakeBackend::new(script);
+        let control = backend.control().clone();
+        let metadata = DiscMetadata {
+            artist: "Artist".to_string(),
+            album: "Album".to_string(),
+            year: None,
+            genre: None,
+            tracks: Vec::new(),
+        };
+
+        // Stop as soon as the first track finishes
+        let result = rip_cd(
+            &backend,
+            "/dev/fake",
+            &metadata,
+            dir.path(),
+            "{artist}/{album}/{track:02}. {title}.{ext}",
+            &RenameJournal::new(None, false),
+            |progress| if progress.percentage >= 50.0 { control.stop() },
+            |_| {},
+        ).await;
+
+        assert_eq!(result.unwrap_err().to_string(), "Rip stopped before track 1");
+        let album = dir.path().join("Artist").join("Album");
+        assert!(album.join("00. Track 0.flac").exists());
+        assert!(!album.join("01. Track 1.flac").exists());
+    }
+
     #[test]
     fn test_rip_progress() {
         let progress = RipProgress {
@@ -1,28 +1,192 @@
-use anyhow::Result;
-use std::path::Path;
+use anyhow::{Context, Result};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::process::Stdio;
 use tokio::process::Command;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::config::{LlmMatcherConfig, SpeechMatchConfig};
+use crate::dvd_metadata::Episode;
+use crate::subtitle_ocr::SubtitleOcr;
+
+pub type TranscriptionFuture<'a> = Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>>;
+pub type MatchFuture<'a> = Pin<Box<dyn Future<Output = Result<EpisodeMatch>> + Send + 'a>>;
+
+/// Turns a speech audio file (16 kHz mono WAV, as cut by `extract_and_transcribe_audio_segments`) into text
+pub trait Transcriber: Send + Sync {
+    /// Short identifier used in config files and logs
+    fn name(&self) -> &'static str;
+
+    fn transcribe<'a>(&'a self, audio_path: &'a Path) -> TranscriptionFuture<'a>;
+}
+
+/// Local Whisper implementations that can be run as a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhisperEngine {
+    WhisperCpp,    // whisper.cpp's `whisper-cli`
+    FasterWhisper, // faster-whisper's `whisper-ctranslate2`
+}
+
+/// Transcribes fully offline by running a local Whisper binary
+#[derive(Debug, Clone)]
+pub struct LocalWhisper {
+    pub engine: WhisperEngine,
+    pub binary: PathBuf,
+    pub model: String,
+    pub language: Option<String>,
+}
+
+impl LocalWhisper {
+    /// whisper.cpp needs a ggml model file; bare model names resolve to `~/.cache/whisper/ggml-<name>.bin`
+    fn model_arg(&self) -> String {
+        let model = if self.model.is_empty() { "base" } else { self.model.as_str() };
+        if self.engine == WhisperEngine::FasterWhisper || model.contains('/') || model.ends_with(".bin") {
+            return model.to_string();
+        }
+        dirs::home_dir()
+            .unwrap_or_default()
+            .join(".cache")
+            .join("whisper")
+            .join(format!("ggml-{}.bin", model))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Command line for transcribing `audio_path`; faster-whisper also writes a transcript file to `output_dir`
+    fn args(&self, audio_path: &Path, output_dir: &Path) -> Vec<String> {
+        let audio = audio_path.to_string_lossy().to_string();
+        let mut args = match self.engine {
+            WhisperEngine::WhisperCpp => vec![
+                "-m".to_string(), self.model_arg(),
+                "-f".to_string(), audio,
+                "-np".to_string(), // Only print the transcript
+            ],
+            WhisperEngine::FasterWhisper => vec![
+                audio,
+                "--model".to_string(), self.model_arg(),
+                "--output_dir".to_string(), output_dir.to_string_lossy().to_string(),
+                "--output_format".to_string(), "txt".to_string(),
+            ],
+        };
+        if let Some(ref language) = self.language {
+            args.push(match self.engine {
+                WhisperEngine::WhisperCpp => "-l".to_string(),
+                WhisperEngine::FasterWhisper => "--language".to_string(),
+            });
+            args.push(language.clone());
+        }
+        args
+    }
+}
+
+impl Transcriber for LocalWhisper {
+    fn name(&self) -> &'static str {
+        match self.engine {
+            WhisperEngine::WhisperCpp => "whisper.cpp",
+            WhisperEngine::FasterWhisper => "faster-whisper",
+        }
+    }
+
+    fn transcribe<'a>(&'a self, audio_path: &'a Path) -> TranscriptionFuture<'a> {
+        Box::pin(async move {
+            // faster-whisper's transcript file is removed along with the folder when `output_dir` drops
+            let output_dir = tempfile::tempdir().context("Failed to create a folder for the transcript")?;
+            let args = self.args(audio_path, output_dir.path());
+            debug!("Running {} {}", self.binary.display(), args.join(" "));
+            let output = Command::new(&self.binary)
+                .args(&args)
+                .stdin(Stdio::null())
+                .output()
+                .await
+                .with_context(|| format!("Failed to run {} (is {} installed?)", self.binary.display(), self.name()))?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(anyhow::anyhow!(
+                    "{} failed: {}",
+                    self.name(),
+                    stderr.lines().last().unwrap_or("unknown error").trim()
+                ));
+            }
+
+            Ok(parse_whisper_output(&String::from_utf8_lossy(&output.stdout)))
+        })
+    }
+}
+
+/// Pick the transcriber configured under `speech_match`
+pub fn transcriber_from_config(config: &SpeechMatchConfig) -> Result<Box<dyn Transcriber>> {
+    let name = config.transcriber.as_deref().unwrap_or("whisper.cpp").to_lowercase();
+    let (engine, default_binary) = match name.as_str() {
+        "whisper.cpp" | "whisper-cpp" | "whisper_cpp" | "whispercpp" => (WhisperEngine::WhisperCpp, "whisper-cli"),
+        "faster-whisper" | "faster_whisper" | "fasterwhisper" => (WhisperEngine::FasterWhisper, "whisper-ctranslate2"),
+        other => return Err(anyhow::anyhow!("Unknown transcriber '{}' (expected whisper.cpp or faster-whisper)", other)),
+    };
+
+    Ok(Box::new(LocalWhisper {
+        engine,
+        binary: PathBuf::from(config.whisper_binary.as_deref().unwrap_or(default_binary)),
+        model: config.whisper_model.clone(),
+        language: config.whisper_language.clone(),
+    }))
+}
+
+/// Strip the `[00:00:00.000 --> 00:00:04.000]` segment timestamps Whisper prints, leaving the spoken text
+fn parse_whisper_output(output: &str) -> String {
+    output
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim();
+            match trimmed.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+                Some((timestamps, text)) if timestamps.contains("-->") => text.trim(),
+                _ => trimmed,
+            }
+        })
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EpisodeMatch {
     pub season: u32,
     pub episode: u32,
     pub title: String,
     pub confidence: f32,
+    #[serde(default)]
+    pub match_method: String, // Recorded in episode_match_results: "transcript", "llm", "subtitle_reference", ...
 }
 
-/// Extract subtitles from video file or transcribe audio as fallback
+/// Extract subtitles from video file or transcribe audio as fallback, using the configured transcriber
 pub async fn extract_and_transcribe_audio(video_path: &Path) -> Result<String> {
+    let config = crate::config::Config::load().unwrap_or_default();
+    let transcriber = transcriber_from_config(&config.speech_match)?;
+    let ocr = SubtitleOcr::from_config(&config.speech_match);
+    extract_and_transcribe_audio_with(video_path, transcriber.as_ref(), &ocr).await
+}
+
+/// Extract text subtitles, then OCR image subtitles with `ocr`, then transcribe audio with `transcriber` as fallback
+pub async fn extract_and_transcribe_audio_with(
+    video_path: &Path,
+    transcriber: &dyn Transcriber,
+    ocr: &SubtitleOcr,
+) -> Result<String> {
     // First, try to extract embedded subtitles using ffmpeg
     if let Ok(subtitles) = extract_subtitles_from_video(video_path).await {
         info!("Extracted embedded subtitles: {} characters", subtitles.len());
         return Ok(subtitles);
     }
     
-    info!("No embedded subtitles found, falling back to audio transcription");
-    extract_and_transcribe_audio_segments(video_path).await
+    // DVD and Blu-ray subtitles are usually images (VobSub/PGS), which need OCR
+    match ocr.dialogue(video_path).await {
+        Ok(subtitles) => return Ok(subtitles),
+        Err(e) => debug!("Subtitle OCR unavailable: {}", e),
+    }
+    
+    info!("No embedded subtitles found, falling back to audio transcription with {}", transcriber.name());
+    extract_and_transcribe_audio_segments(video_path, transcriber).await
 }
 
 /// Extract embedded subtitles from video file (SRT, ASS, SSA, etc.)
@@ -62,7 +226,7 @@ async fn extract_subtitles_from_video(video_path: &Path) -> Result<String> {
 }
 
 /// Parse SRT content and extract just the dialogue lines
-fn parse_srt_dialogue(srt_content: &str) -> String {
+pub(crate) fn parse_srt_dialogue(srt_content: &str) -> String {
     let mut dialogue_lines = Vec::new();
     
     for line in srt_content.lines() {
@@ -92,11 +256,8 @@ fn parse_srt_dialogue(srt_content: &str) -> String {
     dialogue_lines.join(" ")
 }
 
-/// Extract audio from multiple segments (beginning, middle, end) and transcribe
-async fn extract_and_transcribe_audio_segments(video_path: &Path) -> Result<String> {
-    info!("Extracting audio from multiple segments of {}", video_path.display());
-    
-    // Get video duration
+/// Length of a video in seconds, from ffprobe
+async fn video_duration_seconds(video_path: &Path) -> Result<f64> {
     let duration_output = Command::new("ffprobe")
         .args([
             "-v", "error",
@@ -108,7 +269,20 @@ async fn extract_and_transcribe_audio_segments(video_path: &Path) -> Result<Stri
         .await?;
     
     let duration_str = String::from_utf8_lossy(&duration_output.stdout);
-    let duration: f64 = duration_str.trim().parse()?;
+    Ok(duration_str.trim().parse()?)
+}
+
+/// Length of a video rounded to whole minutes, for matching against episode runtimes
+pub async fn video_duration_minutes(video_path: &Path) -> Result<u32> {
+    Ok((video_duration_seconds(video_path).await? / 60.0).round() as u32)
+}
+
+/// Extract audio from multiple segments (beginning, middle, end) and transcribe
+async fn extract_and_transcribe_audio_segments(video_path: &Path, transcriber: &dyn Transcriber) -> Result<String> {
+    info!("Extracting audio from multiple segments of {}", video_path.display());
+    
+    // Get video duration
+    let duration = video_duration_seconds(video_path).await?;
     
     // Extract 3 segments: right after intro, middle, and before credits
     // Intros are usually 30-60 seconds, credits at the very end
@@ -150,12 +324,14 @@ async fn extract_and_transcribe_audio_segments(video_path: &Path) -> Result<Stri
         }
         
         // Transcribe segment
-        let transcript = transcribe_with_openai_api(&audio_file).await?;
-        info!("Transcribed {} segment: {} characters", label, transcript.len());
-        all_transcripts.push(format!("[{}]: {}", label.to_uppercase(), transcript));
+        let transcript = transcriber.transcribe(Path::new(&audio_file)).await;
         
         // Clean up temp file
         let _ = tokio::fs::remove_file(&audio_file).await;
+        
+        let transcript = transcript?;
+        info!("Transcribed {} segment: {} characters", label, transcript.len());
+        all_transcripts.push(format!("[{}]: {}", label.to_uppercase(), transcript));
     }
     
     if all_transcripts.is_empty() {
@@ -169,34 +345,283 @@ async fn extract_and_transcribe_audio_segments(video_path: &Path) -> Result<Stri
 }
 
 
-/// Transcribe audio using OpenAI Whisper API (DISABLED)
-async fn transcribe_with_openai_api(_audio_path: &str) -> Result<String> {
-    Err(anyhow::anyhow!("OpenAI API support has been removed from Ripley"))
+/// Picks the episode a transcript (or subtitle dialogue) belongs to
+pub trait EpisodeMatcher: Send + Sync {
+    /// Short identifier used in config files and logs
+    fn name(&self) -> &'static str;
+
+    fn match_episode<'a>(
+        &'a self,
+        show_name: &'a str,
+        transcript: &'a str,
+        episodes: &'a [Episode],
+        exclude_episode: Option<(u32, u32)>, // (season, episode) to exclude
+    ) -> MatchFuture<'a>;
+}
+
+/// Matches offline by similarity to each episode's title and overview (see transcript_match.rs)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OverviewMatcher;
+
+impl EpisodeMatcher for OverviewMatcher {
+    fn name(&self) -> &'static str {
+        "overview"
+    }
+
+    fn match_episode<'a>(
+        &'a self,
+        show_name: &'a str,
+        transcript: &'a str,
+        episodes: &'a [Episode],
+        exclude_episode: Option<(u32, u32)>,
+    ) -> MatchFuture<'a> {
+        Box::pin(async move {
+            crate::transcript_match::match_transcript(show_name, transcript, episodes, exclude_episode)
+        })
+    }
+}
+
+const DEFAULT_LLM_PROMPT: &str = "You are identifying an episode of the TV show \"{show}\" from its dialogue.
+
+Episodes:
+{episodes}
+
+Transcript:
+{transcript}
+
+Reply with the episode code (e.g. S01E02) followed by your confidence from 0 to 100, and nothing else.";
+
+/// Asks a model behind an OpenAI-compatible chat completions endpoint (OpenAI, llama.cpp server, Ollama, ...)
+#[derive(Debug, Clone)]
+pub struct LlmMatcher {
+    pub base_url: String,
+    pub model: String,
+    pub api_key: Option<String>,
+    pub prompt_template: String,
+}
+
+impl LlmMatcher {
+    /// Fill in the prompt template; excluded episodes are left out of the list
+    fn prompt(&self, show_name: &str, transcript: &str, episodes: &[Episode], exclude_episode: Option<(u32, u32)>) -> String {
+        let episode_list = episodes.iter()
+            .filter(|ep| exclude_episode != Some((ep.season, ep.episode)))
+            .map(|ep| format!(
+                "S{:02}E{:02} - {}: {}",
+                ep.season, ep.episode, ep.title, ep.overview.as_deref().unwrap_or("No overview")
+            ))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        self.prompt_template
+            .replace("{show}", show_name)
+            .replace("{episodes}", &episode_list)
+            .replace("{transcript}", transcript)
+    }
+
+    async fn complete(&self, prompt: String) -> Result<String> {
+        let url = format!("{}/chat/completions", self.base_url.trim_end_matches('/'));
+        debug!("Requesting episode match from {} ({})", url, self.model);
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(120))
+            .build()?;
+        let mut request = client.post(&url).json(&serde_json::json!({
+            "model": self.model,
+            "messages": [{ "role": "user", "content": prompt }],
+            "temperature": 0,
+        }));
+        if let Some(ref api_key) = self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await
+            .with_context(|| format!("Failed to reach LLM endpoint {}", url))?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("LLM endpoint returned status: {}", response.status()));
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        json["choices"][0]["message"]["content"]
+            .as_str()
+            .map(|content| content.to_string())
+            .ok_or_else(|| anyhow::anyhow!("LLM response had no message content"))
+    }
+}
+
+impl EpisodeMatcher for LlmMatcher {
+    fn name(&self) -> &'static str {
+        "llm"
+    }
+
+    fn match_episode<'a>(
+        &'a self,
+        show_name: &'a str,
+        transcript: &'a str,
+        episodes: &'a [Episode],
+        exclude_episode: Option<(u32, u32)>,
+    ) -> MatchFuture<'a> {
+        Box::pin(async move {
+            let prompt = self.prompt(show_name, transcript, episodes, exclude_episode);
+            let response = self.complete(prompt).await?;
+            debug!("LLM replied: {}", response.trim());
+
+            let episode_match = parse_match_response(&response, episodes)?;
+            if exclude_episode == Some((episode_match.season, episode_match.episode)) {
+                return Err(anyhow::anyhow!(
+                    "LLM picked excluded episode S{:02}E{:02}", episode_match.season, episode_match.episode
+                ));
+            }
+            Ok(episode_match)
+        })
+    }
 }
 
-/// Match transcript against TMDB episodes using OpenAI
+/// Parse an `S01E02` episode code followed by an optional confidence (85 when missing), e.g. "S01E02 confidence: 88"
+fn parse_match_response(response: &str, episodes: &[Episode]) -> Result<EpisodeMatch> {
+    let episode_re = regex::Regex::new(r"[Ss](\d+)[Ee](\d+)").unwrap();
+    let confidence_re = regex::Regex::new(r"(\d+(?:\.\d+)?)").unwrap();
+
+    let caps = episode_re.captures(response)
+        .ok_or_else(|| anyhow::anyhow!("No episode code in response: {}", response.trim()))?;
+    let season: u32 = caps[1].parse()?;
+    let episode: u32 = caps[2].parse()?;
+
+    let after_episode = &response[caps.get(0).unwrap().end()..];
+    let confidence = confidence_re.captures(after_episode)
+        .and_then(|conf_caps| conf_caps[1].parse::<f32>().ok())
+        .unwrap_or(85.0)
+        .clamp(0.0, 100.0);
+
+    let title = episodes.iter()
+        .find(|ep| ep.season == season && ep.episode == episode)
+        .map(|ep| ep.title.clone())
+        .ok_or_else(|| anyhow::anyhow!("Response names unknown episode S{:02}E{:02}", season, episode))?;
+
+    Ok(EpisodeMatch { season, episode, title, confidence, match_method: "llm".to_string() })
+}
+
+/// Compares dialogue with per-episode reference `.srt` files, deferring to `fallback` for shows without any
+pub struct ReferenceSubtitleMatcher {
+    pub dir: PathBuf,
+    pub fallback: Box<dyn EpisodeMatcher>,
+}
+
+impl EpisodeMatcher for ReferenceSubtitleMatcher {
+    fn name(&self) -> &'static str {
+        "subtitle_reference"
+    }
+
+    fn match_episode<'a>(
+        &'a self,
+        show_name: &'a str,
+        transcript: &'a str,
+        episodes: &'a [Episode],
+        exclude_episode: Option<(u32, u32)>,
+    ) -> MatchFuture<'a> {
+        Box::pin(async move {
+            let files = crate::reference_subtitles::find_reference_subtitles(&self.dir, show_name);
+            let mut references = Vec::new();
+            for ep in episodes.iter().filter(|ep| exclude_episode != Some((ep.season, ep.episode))) {
+                let Some((_, path)) = files.iter().find(|(code, _)| *code == (ep.season, ep.episode)) else {
+                    continue;
+                };
+                match crate::reference_subtitles::load_reference(path) {
+                    Ok(dialogue) => references.push((ep, dialogue)),
+                    Err(e) => warn!("Could not read reference subtitles {}: {}", path.display(), e),
+                }
+            }
+
+            if !references.is_empty() {
+                debug!("Comparing against {} reference subtitle file(s)", references.len());
+                if let Some(episode_match) = crate::reference_subtitles::match_reference(transcript, &references) {
+                    return Ok(episode_match);
+                }
+            }
+            debug!("No usable reference subtitles for {}, using the {} matcher", show_name, self.fallback.name());
+            self.fallback.match_episode(show_name, transcript, episodes, exclude_episode).await
+        })
+    }
+}
+
+/// Pick the episode matcher configured under `speech_match`
+///
+/// When reference subtitles are available (see `reference_subtitles::reference_dir`) they are tried first.
+pub fn matcher_from_config(config: &SpeechMatchConfig) -> Result<Box<dyn EpisodeMatcher>> {
+    let matcher = base_matcher_from_config(config)?;
+    Ok(match crate::reference_subtitles::reference_dir(config) {
+        Some(dir) => Box::new(ReferenceSubtitleMatcher { dir, fallback: matcher }),
+        None => matcher,
+    })
+}
+
+fn base_matcher_from_config(config: &SpeechMatchConfig) -> Result<Box<dyn EpisodeMatcher>> {
+    let name = config.matcher.as_deref().unwrap_or("overview").to_lowercase();
+    match name.as_str() {
+        "overview" | "offline" => Ok(Box::new(OverviewMatcher)),
+        "llm" | "openai" => {
+            let llm: &LlmMatcherConfig = config.llm.as_ref()
+                .ok_or_else(|| anyhow::anyhow!("The llm matcher needs a speech_match.llm section"))?;
+            let api_key = match llm.api_key_env {
+                Some(ref var) => Some(std::env::var(var)
+                    .with_context(|| format!("API key environment variable {} is not set", var))?),
+                None => None,
+            };
+            Ok(Box::new(LlmMatcher {
+                base_url: llm.base_url.clone(),
+                model: llm.model.clone(),
+                api_key,
+                prompt_template: llm.prompt_template.clone().unwrap_or_else(|| DEFAULT_LLM_PROMPT.to_string()),
+            }))
+        }
+        other => Err(anyhow::anyhow!("Unknown matcher '{}' (expected overview or llm)", other)),
+    }
+}
+
+/// Record an accepted match in `episode_match_results` under its match method
+pub fn record_match(
+    db: &crate::database::Database,
+    show_name: &str,
+    episode_match: &EpisodeMatch,
+    title_index: Option<u32>,
+) -> Result<i64> {
+    db.record_episode_match(&crate::database::EpisodeMatchResult {
+        id: None,
+        timestamp: chrono::Utc::now(),
+        show_name: show_name.to_string(),
+        season: episode_match.season,
+        episode: episode_match.episode,
+        episode_title: Some(episode_match.title.clone()),
+        match_method: episode_match.match_method.clone(),
+        confidence: Some(episode_match.confidence),
+        title_index,
+        rip_history_id: None,
+    })
+}
+
+/// Match transcript against TMDB episodes with the configured matcher
 pub async fn match_episode_by_transcript(
     show_name: &str,
     transcript: &str,
-    episodes: &[crate::dvd_metadata::Episode],
+    episodes: &[Episode],
 ) -> Result<EpisodeMatch> {
     match_episode_by_transcript_with_exclusion(show_name, transcript, episodes, None).await
 }
 
-/// Match transcript against TMDB episodes using OpenAI, excluding a specific episode
+/// Match transcript against TMDB episodes with the configured matcher, excluding a specific episode
 pub async fn match_episode_by_transcript_with_exclusion(
-    _show_name: &str,
-    _transcript: &str,
-    _episodes: &[crate::dvd_metadata::Episode],
-    _exclude_episode: Option<(u32, u32)>, // (season, episode) to exclude
+    show_name: &str,
+    transcript: &str,
+    episodes: &[Episode],
+    exclude_episode: Option<(u32, u32)>, // (season, episode) to exclude
 ) -> Result<EpisodeMatch> {
-    Err(anyhow::anyhow!("OpenAI API support has been removed from Ripley"))
+    let config = crate::config::Config::load().unwrap_or_default();
+    let matcher = matcher_from_config(&config.speech_match)?;
+    matcher.match_episode(show_name, transcript, episodes, exclude_episode).await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dvd_metadata::Episode;
 
     #[test]
     fn test_parse_srt_dialogue() {
@@ -235,7 +660,29 @@ This is a test subtitle.
 
     #[test]
     fn test_episode_match_parsing() {
-        let _episodes = vec![
+        let episodes = test_episodes();
+
+        // Test parsing various response formats
+        let test_cases = vec![
+            ("S01E01\n90", 1, 1, 90.0),
+            ("S01E02 confidence: 88", 1, 2, 88.0),
+            ("S01E01\n95", 1, 1, 95.0),
+            ("The answer is s01e02.", 1, 2, 85.0),
+        ];
+
+        for (response, expected_season, expected_episode, expected_confidence) in test_cases {
+            let ep_match = parse_match_response(response, &episodes).unwrap();
+            assert_eq!(ep_match.season, expected_season);
+            assert_eq!(ep_match.episode, expected_episode);
+            assert_eq!(ep_match.confidence, expected_confidence);
+        }
+        assert_eq!(parse_match_response("S01E02", &episodes).unwrap().title, "Second Episode");
+        assert!(parse_match_response("S03E01 95", &episodes).is_err());
+        assert!(parse_match_response("I don't know", &episodes).is_err());
+    }
+
+    fn test_episodes() -> Vec<Episode> {
+        vec![
             Episode {
                 season: 1,
                 episode: 1,
@@ -252,31 +699,163 @@ This is a test subtitle.
                 runtime_minutes: Some(22),
                 overview: Some("Second episode".to_string()),
             },
-        ];
+        ]
+    }
 
-        // Test parsing various response formats
-        let test_cases = vec![
-            ("S01E01\n90", 1, 1, 90.0),
-            ("S01E02 confidence: 88", 1, 2, 88.0),
-            ("S01E01\n95", 1, 1, 95.0),
-        ];
+    #[test]
+    fn test_matcher_from_config() {
+        let mut config = crate::config::Config::default().speech_match;
+        assert_eq!(base_matcher_from_config(&config).unwrap().name(), "overview");
+        config.reference_subtitles = Some("/srv/subtitles".to_string());
+        assert_eq!(matcher_from_config(&config).unwrap().name(), "subtitle_reference");
 
-        let re = regex::Regex::new(r"S(\d+)E(\d+)").unwrap();
-        let confidence_re = regex::Regex::new(r"(\d+)").unwrap();
-        for (response, expected_season, expected_episode, expected_confidence) in test_cases {
-            let caps = re.captures(response).unwrap();
-            assert_eq!(caps[1].parse::<u32>().unwrap(), expected_season);
-            assert_eq!(caps[2].parse::<u32>().unwrap(), expected_episode);
-            
-            // Extract confidence - find number after episode code
-            let after_episode = &response[caps.get(0).unwrap().end()..];
-            let confidence = if let Some(conf_caps) = confidence_re.captures(after_episode) {
-                conf_caps[1].parse::<f32>().unwrap_or(85.0)
-            } else {
-                85.0
-            };
-            assert_eq!(confidence, expected_confidence);
-        }
+        config.matcher = Some("llm".to_string());
+        assert!(matcher_from_config(&config).is_err()); // No llm section
+        config.llm = Some(LlmMatcherConfig {
+            base_url: "http://localhost:11434/v1".to_string(),
+            model: "llama3".to_string(),
+            api_key_env: None,
+            prompt_template: None,
+        });
+        assert_eq!(base_matcher_from_config(&config).unwrap().name(), "llm");
+
+        config.llm.as_mut().unwrap().api_key_env = Some("RIPLEY_TEST_UNSET_API_KEY".to_string());
+        assert!(matcher_from_config(&config).is_err());
+        config.matcher = Some("gpt".to_string());
+        assert!(matcher_from_config(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reference_subtitle_matcher() {
+        let dir = tempfile::tempdir().unwrap();
+        let show_dir = dir.path().join("Test Show");
+        std::fs::create_dir_all(&show_dir).unwrap();
+        std::fs::write(show_dir.join("Test.Show.S01E01.srt"), "1\n00:00:01,000 --> 00:00:03,000\nWhere did you park the spaceship?\n").unwrap();
+        std::fs::write(show_dir.join("Test.Show.S01E02.srt"), "1\n00:00:01,000 --> 00:00:03,000\nThe cake is in the oven already.\n").unwrap();
+
+        let matcher = ReferenceSubtitleMatcher { dir: dir.path().to_path_buf(), fallback: Box::new(OverviewMatcher) };
+        let episodes = test_episodes();
+        let ep_match = matcher.match_episode("Test Show", "the cake is in the oven", &episodes, None).await.unwrap();
+        assert_eq!((ep_match.episode, ep_match.match_method.as_str()), (2, "subtitle_reference"));
+
+        // Excluding the only match leaves nothing in common, so the overview matcher takes over
+        let ep_match = matcher.match_episode("Test Show", "the cake is in the oven, second episode", &episodes, Some((1, 2))).await.unwrap();
+        assert_eq!((ep_match.episode, ep_match.match_method.as_str()), (1, "transcript"));
+    }
+
+    #[tokio::test]
+    async fn test_llm_matcher_against_stub_server() {
+        use axum::{routing::post, Json, Router};
+        use std::sync::{Arc, Mutex};
+
+        type Recorded = Vec<(Option<String>, serde_json::Value)>; // (authorization header, body)
+
+        // Stub OpenAI-compatible endpoint that records the request and always answers S01E02
+        let requests: Arc<Mutex<Recorded>> = Arc::default();
+        let recorded = Arc::clone(&requests);
+        let app = Router::new().route("/v1/chat/completions", post(
+            move |headers: axum::http::HeaderMap, Json(body): Json<serde_json::Value>| {
+                let recorded = Arc::clone(&recorded);
+                async move {
+                    let auth = headers.get("authorization").and_then(|v| v.to_str().ok()).map(String::from);
+                    recorded.lock().unwrap().push((auth, body));
+                    Json(serde_json::json!({
+                        "choices": [{ "message": { "role": "assistant", "content": "S01E02\n92" } }]
+                    }))
+                }
+            },
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let matcher = LlmMatcher {
+            base_url: format!("http://{}/v1/", addr),
+            model: "llama3".to_string(),
+            api_key: Some("secret".to_string()),
+            prompt_template: "Show {show}\n{episodes}\n---\n{transcript}".to_string(),
+        };
+        let episodes = test_episodes();
+        let ep_match = matcher.match_episode("Test Show", "Hello there", &episodes, None).await.unwrap();
+        assert_eq!((ep_match.season, ep_match.episode), (1, 2));
+        assert_eq!(ep_match.title, "Second Episode");
+        assert_eq!(ep_match.confidence, 92.0);
+
+        // The excluded episode is left out of the prompt, and picking it anyway is an error
+        let err = matcher.match_episode("Test Show", "Hello there", &episodes, Some((1, 2))).await.unwrap_err();
+        assert!(err.to_string().contains("excluded"));
+
+        let requests = requests.lock().unwrap();
+        let (auth, body) = &requests[0];
+        assert_eq!(auth.as_deref(), Some("Bearer secret"));
+        assert_eq!(body["model"], "llama3");
+        assert_eq!(
+            body["messages"][0]["content"],
+            "Show Test Show\nS01E01 - Pilot: First episode\nS01E02 - Second Episode: Second episode\n---\nHello there"
+        );
+        assert!(!requests[1].1["messages"][0]["content"].as_str().unwrap().contains("S01E02"));
+    }
+
+    #[test]
+    fn test_parse_whisper_output() {
+        let output = "\n[00:00:00.000 --> 00:00:03.200]   Good news, everyone!\n[00:00:03.200 --> 00:00:05.000]  [MUSIC]\n";
+        assert_eq!(parse_whisper_output(output), "Good news, everyone! [MUSIC]");
+        assert_eq!(parse_whisper_output("Plain text output\n"), "Plain text output");
+    }
+
+    #[test]
+    fn test_transcriber_from_config() {
+        let mut config = crate::config::Config::default().speech_match;
+        config.whisper_model = "/models/ggml-small.en.bin".to_string();
+        config.whisper_language = Some("en".to_string());
+        let transcriber = transcriber_from_config(&config).unwrap();
+        assert_eq!(transcriber.name(), "whisper.cpp");
+
+        config.transcriber = Some("faster-whisper".to_string());
+        assert_eq!(transcriber_from_config(&config).unwrap().name(), "faster-whisper");
+        config.transcriber = Some("openai".to_string());
+        assert!(transcriber_from_config(&config).is_err());
+
+        let whisper = LocalWhisper {
+            engine: WhisperEngine::WhisperCpp,
+            binary: PathBuf::from("whisper-cli"),
+            model: "small".to_string(),
+            language: Some("en".to_string()),
+        };
+        let args = whisper.args(Path::new("/tmp/segment.wav"), Path::new("/tmp/transcript"));
+        assert!(args[1].ends_with("ggml-small.bin"));
+        assert_eq!(&args[2..], ["-f", "/tmp/segment.wav", "-np", "-l", "en"]);
+
+        let faster = LocalWhisper { engine: WhisperEngine::FasterWhisper, language: None, ..whisper };
+        let args = faster.args(Path::new("/tmp/segment.wav"), Path::new("/tmp/transcript"));
+        assert_eq!(args, ["/tmp/segment.wav", "--model", "small", "--output_dir", "/tmp/transcript", "--output_format", "txt"]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_local_whisper_runs_binary() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("whisper-cli");
+        std::fs::write(&binary, "#!/bin/sh\necho \"[00:00:00.000 --> 00:00:02.000]   Transcribed $4\"\n").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let whisper = LocalWhisper {
+            engine: WhisperEngine::WhisperCpp,
+            binary,
+            model: "/models/ggml-base.bin".to_string(),
+            language: None,
+        };
+        let transcript = whisper.transcribe(Path::new("/tmp/segment.wav")).await.unwrap();
+        assert_eq!(transcript, "Transcribed /tmp/segment.wav");
+
+        let failing = dir.path().join("whisper-fail");
+        std::fs::write(&failing, "#!/bin/sh\necho 'failed to load model' >&2\nexit 1\n").unwrap();
+        std::fs::set_permissions(&failing, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let whisper = LocalWhisper { binary: failing, ..whisper };
+        let err = whisper.transcribe(Path::new("/tmp/segment.wav")).await.unwrap_err();
+        assert!(err.to_string().contains("failed to load model"));
     }
 
     #[test]
@@ -286,6 +865,7 @@ This is a test subtitle.
             episode: 5,
             title: "Test Episode".to_string(),
             confidence: 92.5,
+            match_method: "transcript".to_string(),
         };
 
         assert_eq!(ep_match.season, 2);
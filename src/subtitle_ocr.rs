@@ -0,0 +1,299 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+use tracing::{debug, info};
+
+use crate::config::SpeechMatchConfig;
+use crate::speech_match::parse_srt_dialogue;
+
+// DVD (VobSub) and Blu-ray (PGS) subtitles are images, so ffmpeg can't convert them to SRT. Instead ffmpeg
+// renders the subtitle stream to one PNG per change (fps=1 + mpdecimate, named by their second), tesseract
+// reads each image, and the result is written next to the MKV as `<name>.ocr.srt` so it's only done once.
+
+/// Codecs ffmpeg reports for image-based subtitles
+const BITMAP_CODECS: &[&str] = &["dvd_subtitle", "hdmv_pgs_subtitle", "dvb_subtitle", "xsub"];
+
+/// How long the last cue stays up when no blank frame follows it
+const LAST_CUE_SECONDS: u32 = 3;
+
+/// A subtitle stream as listed by ffprobe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubtitleStream {
+    pub index: u32, // Absolute stream index within the file
+    pub codec: String,
+}
+
+impl SubtitleStream {
+    pub fn is_bitmap(&self) -> bool {
+        BITMAP_CODECS.contains(&self.codec.as_str())
+    }
+}
+
+/// OCRs image subtitles with a local tesseract install
+#[derive(Debug, Clone)]
+pub struct SubtitleOcr {
+    pub binary: PathBuf,
+    pub language: String, // tesseract language code, e.g. "eng"
+}
+
+impl SubtitleOcr {
+    pub fn from_config(config: &SpeechMatchConfig) -> Self {
+        SubtitleOcr {
+            binary: PathBuf::from(config.tesseract_binary.as_deref().unwrap_or("tesseract")),
+            language: config.ocr_language.clone().unwrap_or_else(|| "eng".to_string()),
+        }
+    }
+
+    /// Dialogue from the first image subtitle stream, in the same form `parse_srt_dialogue` returns
+    ///
+    /// Uses the cached `<name>.ocr.srt` next to the video when there is one.
+    pub async fn dialogue(&self, video_path: &Path) -> Result<String> {
+        let cache = cache_path(video_path);
+        if let Ok(srt) = tokio::fs::read_to_string(&cache).await {
+            let dialogue = parse_srt_dialogue(&srt);
+            if !dialogue.is_empty() {
+                info!("Using cached subtitle OCR from {}", cache.display());
+                return Ok(dialogue);
+            }
+        }
+
+        let stream = probe_subtitle_streams(video_path).await?
+            .into_iter()
+            .find(|s| s.is_bitmap())
+            .ok_or_else(|| anyhow::anyhow!("No image subtitles found in video"))?;
+        info!("OCRing {} subtitle stream {} with {}", stream.codec, stream.index, self.binary.display());
+
+        let frames_dir = std::env::temp_dir().join(format!(
+            "ripley_subtitle_ocr_{}_{}",
+            std::process::id(),
+            video_path.file_stem().unwrap_or_default().to_string_lossy()
+        ));
+        let frames = render_subtitle_frames(video_path, &stream, &frames_dir).await;
+        let cues = match frames {
+            Ok(frames) => self.recognize_frames(&frames).await,
+            Err(e) => Err(e),
+        };
+        let _ = tokio::fs::remove_dir_all(&frames_dir).await;
+
+        let srt = build_srt(&cues?);
+        let dialogue = parse_srt_dialogue(&srt);
+        if dialogue.is_empty() {
+            return Err(anyhow::anyhow!("OCR found no text in the subtitles"));
+        }
+
+        if let Err(e) = tokio::fs::write(&cache, &srt).await {
+            debug!("Could not cache subtitle OCR at {}: {}", cache.display(), e);
+        }
+        info!("OCRed subtitles: {} characters", dialogue.len());
+        Ok(dialogue)
+    }
+
+    /// OCR each frame; blank frames end the previous cue
+    async fn recognize_frames(&self, frames: &[(u32, PathBuf)]) -> Result<Vec<(u32, u32, String)>> {
+        let mut cues: Vec<(u32, u32, String)> = Vec::new();
+        let mut open: Option<(u32, String)> = None;
+
+        for (second, image) in frames {
+            let text = self.recognize(image).await?;
+            if let Some((start, previous)) = open.take() {
+                if previous == text {
+                    open = Some((start, previous));
+                    continue;
+                }
+                cues.push((start, *second, previous));
+            }
+            if !text.is_empty() {
+                open = Some((*second, text));
+            }
+        }
+        if let Some((start, text)) = open {
+            cues.push((start, start + LAST_CUE_SECONDS, text));
+        }
+        Ok(cues)
+    }
+
+    /// Text tesseract reads from one subtitle image, one line per subtitle line
+    async fn recognize(&self, image: &Path) -> Result<String> {
+        let output = Command::new(&self.binary)
+            .arg(image)
+            .arg("stdout")
+            .args(["-l", &self.language, "--psm", "6"]) // psm 6: a single uniform block of text
+            .stdin(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .with_context(|| format!("Failed to run {} (is tesseract installed?)", self.binary.display()))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow::anyhow!(
+                "tesseract failed: {}",
+                stderr.lines().last().unwrap_or("unknown error").trim()
+            ));
+        }
+        Ok(clean_ocr_text(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+/// Where the OCR result for a video is cached
+pub fn cache_path(video_path: &Path) -> PathBuf {
+    video_path.with_extension("ocr.srt")
+}
+
+/// List the subtitle streams in a video with ffprobe
+pub async fn probe_subtitle_streams(video_path: &Path) -> Result<Vec<SubtitleStream>> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "error", "-select_streams", "s", "-show_entries", "stream=index,codec_name", "-of", "csv=p=0"])
+        .arg(video_path)
+        .output()
+        .await
+        .context("Failed to run ffprobe")?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("ffprobe failed on {}", video_path.display()));
+    }
+    Ok(parse_subtitle_streams(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse ffprobe's `index,codec_name` CSV lines
+fn parse_subtitle_streams(output: &str) -> Vec<SubtitleStream> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (index, codec) = line.trim().split_once(',')?;
+            Some(SubtitleStream { index: index.parse().ok()?, codec: codec.trim().to_string() })
+        })
+        .collect()
+}
+
+/// Render a subtitle stream to `<second>.png` images, one per change, as (second, path) in order
+async fn render_subtitle_frames(video_path: &Path, stream: &SubtitleStream, dir: &Path) -> Result<Vec<(u32, PathBuf)>> {
+    tokio::fs::create_dir_all(dir).await?;
+
+    // White-on-transparent subtitles become black text on white, which tesseract reads best
+    let filter = format!("[0:{}]fps=1,mpdecimate,format=gray,negate[sub]", stream.index);
+    let status = Command::new("ffmpeg")
+        .arg("-i").arg(video_path)
+        .args(["-filter_complex", &filter, "-map", "[sub]", "-fps_mode", "vfr", "-frame_pts", "1", "-y"])
+        .arg(dir.join("%06d.png"))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .context("Failed to run ffmpeg")?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!("ffmpeg could not render subtitle stream {}", stream.index));
+    }
+
+    let mut frames = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if let Some(second) = frame_second(&path) {
+            frames.push((second, path));
+        }
+    }
+    frames.sort();
+    Ok(frames)
+}
+
+/// The second a rendered frame was taken at, from its `000042.png` name
+fn frame_second(path: &Path) -> Option<u32> {
+    if path.extension()?.to_str()? != "png" {
+        return None;
+    }
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+/// Trim OCR output to its non-empty lines (tesseract ends each page with a form feed)
+fn clean_ocr_text(text: &str) -> String {
+    text.lines()
+        .map(|line| line.trim_matches(|c: char| c.is_whitespace() || c == '\u{c}'))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Write (start second, end second, text) cues as SRT
+fn build_srt(cues: &[(u32, u32, String)]) -> String {
+    let timestamp = |s: u32| format!("{:02}:{:02}:{:02},000", s / 3600, s / 60 % 60, s % 60);
+    cues.iter()
+        .enumerate()
+        .map(|(i, (start, end, text))| format!("{}\n{} --> {}\n{}\n", i + 1, timestamp(*start), timestamp(*end), text))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_subtitle_streams() {
+        let streams = parse_subtitle_streams("2,hdmv_pgs_subtitle\n3,subrip\n\n4,dvd_subtitle\n");
+        assert_eq!(streams.len(), 3);
+        assert_eq!(streams[0], SubtitleStream { index: 2, codec: "hdmv_pgs_subtitle".to_string() });
+        assert!(streams[0].is_bitmap());
+        assert!(!streams[1].is_bitmap());
+        assert!(streams[2].is_bitmap());
+    }
+
+    #[test]
+    fn test_build_srt_matches_parse_srt_dialogue() {
+        let cues = vec![
+            (72, 75, "Welcome to the Moon!".to_string()),
+            (3661, 3664, "Good news,\neveryone!".to_string()),
+        ];
+        let srt = build_srt(&cues);
+        assert!(srt.starts_with("1\n00:01:12,000 --> 00:01:15,000\nWelcome to the Moon!\n"));
+        assert!(srt.contains("2\n01:01:01,000 --> 01:01:04,000\n"));
+        assert_eq!(parse_srt_dialogue(&srt), "Welcome to the Moon! Good news, everyone!");
+    }
+
+    #[test]
+    fn test_frame_second_and_clean_ocr_text() {
+        assert_eq!(frame_second(Path::new("/tmp/x/000042.png")), Some(42));
+        assert_eq!(frame_second(Path::new("/tmp/x/notes.txt")), None);
+        assert_eq!(clean_ocr_text("  Hello there!\n\n General Kenobi \n\u{c}"), "Hello there!\nGeneral Kenobi");
+        assert_eq!(cache_path(Path::new("/rips/Show/title_t00.mkv")), PathBuf::from("/rips/Show/title_t00.ocr.srt"));
+    }
+
+    #[tokio::test]
+    async fn test_dialogue_uses_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let video = dir.path().join("title_t00.mkv");
+        std::fs::write(cache_path(&video), "1\n00:00:01,000 --> 00:00:03,000\n<i>Cached line</i>\n").unwrap();
+
+        let ocr = SubtitleOcr::from_config(&crate::config::Config::default().speech_match);
+        assert_eq!(ocr.language, "eng");
+        assert_eq!(ocr.dialogue(&video).await.unwrap(), "Cached line");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_recognize_frames_merges_and_ends_cues() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Fake tesseract that "reads" the image file's contents
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("tesseract");
+        std::fs::write(&binary, "#!/bin/sh\ncat \"$1\"\nprintf '\\f'\n").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut frames = Vec::new();
+        for (second, text) in [(10, "First line"), (11, "First line"), (14, ""), (20, "Second line")] {
+            let path = dir.path().join(format!("{:06}.png", second));
+            std::fs::write(&path, text).unwrap();
+            frames.push((second, path));
+        }
+
+        let ocr = SubtitleOcr { binary, language: "eng".to_string() };
+        let cues = ocr.recognize_frames(&frames).await.unwrap();
+        assert_eq!(cues, vec![
+            (10, 14, "First line".to_string()),
+            (20, 23, "Second line".to_string()),
+        ]);
+    }
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+use crate::disc_scan::TitleInfo;
+
+/// Default duration window for episode-length titles (minutes)
+const DEFAULT_MIN_MINUTES: u32 = 18;
+const DEFAULT_MAX_MINUTES: u32 = 70;
+/// Default tolerance when matching title runtimes to episode runtimes (minutes)
+const DEFAULT_TOLERANCE_MINUTES: u32 = 5;
+/// Titles shorter than this are menus, logos or warnings, never extras
+const EXTRAS_MIN_SECONDS: u32 = 60;
+
+/// Rules for which titles on a disc get ripped
+///
+/// Set per rip profile (config.yaml) and per show (database); a show's rules win over the profile's.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TitleSelection {
+    #[serde(default)]
+    pub min_minutes: Option<u32>, // Shortest title to rip (default 18)
+    #[serde(default)]
+    pub max_minutes: Option<u32>, // Longest title to rip (default 70, unbounded with longest_only)
+    #[serde(default)]
+    pub longest_only: bool, // Only rip the longest title in the window (movies)
+    #[serde(default)]
+    pub titles: Option<Vec<u32>>, // Explicit title indices; bypasses the duration rules
+    #[serde(default)]
+    pub include_extras: bool, // Also rip titles shorter than min_minutes (featurettes, shorts)
+    #[serde(default)]
+    pub tolerance_minutes: Option<u32>, // Runtime tolerance for episode matching (default 5)
+}
+
+/// Outcome of applying a `TitleSelection` to a disc's titles
+#[derive(Debug, Default)]
+pub struct SelectedTitles<'a> {
+    pub selected: Vec<&'a TitleInfo>,
+    pub skipped: Vec<(&'a TitleInfo, String)>, // (title, reason)
+}
+
+impl TitleSelection {
+    /// Combine profile and show rules with an explicit title list from the request
+    pub fn resolve(
+        profile: Option<&TitleSelection>,
+        show: Option<&TitleSelection>,
+        titles: Option<&[u32]>,
+    ) -> TitleSelection {
+        let mut selection = show.or(profile).cloned().unwrap_or_default();
+        if let Some(titles) = titles.filter(|t| !t.is_empty()) {
+            selection.titles = Some(titles.to_vec());
+        }
+        selection
+    }
+
+    pub fn min_minutes(&self) -> u32 {
+        self.min_minutes.unwrap_or(DEFAULT_MIN_MINUTES)
+    }
+
+    pub fn max_minutes(&self) -> Option<u32> {
+        match self.max_minutes {
+            Some(max) => Some(max),
+            None if self.longest_only => None,
+            None => Some(DEFAULT_MAX_MINUTES),
+        }
+    }
+
+    pub fn tolerance_minutes(&self) -> u32 {
+        self.tolerance_minutes.unwrap_or(DEFAULT_TOLERANCE_MINUTES)
+    }
+
+    /// Whether a title of this length falls inside the duration window
+    pub fn in_window(&self, minutes: u32) -> bool {
+        minutes >= self.min_minutes() && self.max_minutes().is_none_or(|max| minutes <= max)
+    }
+
+    /// Split titles into those to rip and those to skip, with a reason for each skip
+    pub fn select<'a>(&self, titles: &'a [TitleInfo]) -> SelectedTitles<'a> {
+        let mut result = SelectedTitles::default();
+
+        if let Some(ref wanted) = self.titles {
+            for title in titles {
+                if wanted.contains(&title.index) {
+                    result.selected.push(title);
+                } else {
+                    result.skipped.push((title, "not in selected titles".to_string()));
+                }
+            }
+            return result;
+        }
+
+        let mut main: Vec<&TitleInfo> = Vec::new();
+        let mut extras: Vec<&TitleInfo> = Vec::new();
+        for title in titles {
+            let minutes = title.duration_minutes();
+            if self.in_window(minutes) {
+                main.push(title);
+            } else if minutes < self.min_minutes() {
+                if self.include_extras && title.duration_seconds >= EXTRAS_MIN_SECONDS {
+                    extras.push(title);
+                } else {
+                    result.skipped.push((title, format!("shorter than {} min", self.min_minutes())));
+                }
+            } else {
+                let max = self.max_minutes().unwrap_or(u32::MAX);
+                result.skipped.push((title, format!("longer than {} min", max)));
+            }
+        }
+
+        if self.longest_only {
+            // First longest wins so ties keep disc order
+            let longest = main.iter()
+                .rev()
+                .max_by_key(|t| t.duration_seconds)
+                .map(|t| t.index);
+            for title in main.drain(..) {
+                if Some(title.index) == longest {
+                    result.selected.push(title);
+                } else {
+                    result.skipped.push((title, "not the longest title".to_string()));
+                }
+            }
+        } else {
+            result.selected = main;
+        }
+
+        result.selected.extend(extras);
+        result.selected.sort_by_key(|t| t.index);
+        result.skipped.sort_by_key(|(t, _)| t.index);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title(index: u32, minutes: u32) -> TitleInfo {
+        TitleInfo { index, duration_seconds: minutes * 60, ..Default::default() }
+    }
+
+    fn indices(titles: &[&TitleInfo]) -> Vec<u32> {
+        titles.iter().map(|t| t.index).collect()
+    }
+
+    #[test]
+    fn test_default_selection_matches_episode_window() {
+        let titles = vec![title(0, 88), title(1, 22), title(2, 23), title(3, 5), title(4, 0)];
+        let result = TitleSelection::default().select(&titles);
+        assert_eq!(indices(&result.selected), vec![1, 2]);
+        assert_eq!(result.skipped.len(), 3);
+        assert_eq!(result.skipped[0].1, "longer than 70 min");
+        assert_eq!(result.skipped[1].1, "shorter than 18 min");
+    }
+
+    #[test]
+    fn test_longest_only_picks_movie() {
+        let titles = vec![title(0, 4), title(1, 121), title(2, 119), title(3, 12)];
+        let selection = TitleSelection { longest_only: true, ..Default::default() };
+        let result = selection.select(&titles);
+        assert_eq!(indices(&result.selected), vec![1]);
+
+        let with_extras = TitleSelection { longest_only: true, include_extras: true, ..Default::default() };
+        assert_eq!(indices(&with_extras.select(&titles).selected), vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_short_cartoons_with_custom_window() {
+        let titles = vec![title(0, 7), title(1, 6), title(2, 42)];
+        let selection = TitleSelection { min_minutes: Some(5), max_minutes: Some(15), ..Default::default() };
+        assert_eq!(indices(&selection.select(&titles).selected), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_explicit_titles_override_rules() {
+        let titles = vec![title(0, 88), title(1, 22), title(2, 3)];
+        let profile = TitleSelection { longest_only: true, ..Default::default() };
+        let selection = TitleSelection::resolve(Some(&profile), None, Some(&[0, 2]));
+        let result = selection.select(&titles);
+        assert_eq!(indices(&result.selected), vec![0, 2]);
+        assert_eq!(result.skipped[0].1, "not in selected titles");
+    }
+
+    #[test]
+    fn test_show_rules_win_over_profile() {
+        let profile = TitleSelection { longest_only: true, ..Default::default() };
+        let show = TitleSelection { min_minutes: Some(10), ..Default::default() };
+        let selection = TitleSelection::resolve(Some(&profile), Some(&show), None);
+        assert!(!selection.longest_only);
+        assert_eq!(selection.min_minutes(), 10);
+        assert_eq!(selection.max_minutes(), Some(70));
+        assert_eq!(TitleSelection::resolve(Some(&profile), None, Some(&[])).max_minutes(), None);
+    }
+}
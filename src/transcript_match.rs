@@ -0,0 +1,271 @@
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+use crate::dvd_metadata::Episode;
+use crate::speech_match::EpisodeMatch;
+
+// Offline transcript matching: each episode's title and overview is a BM25 document and the transcript
+// (or subtitle dialogue) is the query. Character names and other proper nouns from the overview that are
+// spoken in the transcript earn a bonus, since names are the most reliable link between a plot summary
+// and what is actually said on screen.
+
+// BM25 parameters (the usual defaults)
+const K1: f32 = 1.2;
+const B: f32 = 0.75;
+// Weight of the proper noun overlap relative to BM25
+const NAME_WEIGHT: f32 = 0.5;
+// Score scale at which a match counts as strong evidence on its own (see `confidence`)
+const STRONG_SCORE: f32 = 4.0;
+
+const STOPWORDS: &[&str] = &[
+    "about", "after", "again", "all", "also", "and", "any", "are", "back", "been", "before", "being",
+    "but", "can", "come", "could", "did", "does", "doing", "don", "down", "each", "even", "for", "from",
+    "get", "gets", "going", "gonna", "got", "had", "has", "have", "her", "here", "hers", "hey", "him",
+    "his", "how", "into", "its", "just", "know", "let", "like", "look", "make", "more", "most", "much",
+    "must", "not", "now", "off", "okay", "one", "only", "other", "our", "out", "over", "really", "right",
+    "said", "say", "see", "she", "should", "some", "such", "take", "tell", "than", "that", "the", "their",
+    "them", "then", "there", "these", "they", "thing", "think", "this", "those", "through", "too", "two",
+    "until", "upon", "very", "want", "was", "way", "well", "were", "what", "when", "where", "which",
+    "while", "who", "why", "will", "with", "would", "yeah", "yes", "you", "your",
+];
+
+/// Score every episode against a transcript; higher is more similar, 0 means nothing in common
+///
+/// Words from the show's name are ignored since they appear in every overview.
+pub fn score_episodes(show_name: &str, transcript: &str, episodes: &[Episode]) -> Vec<f32> {
+    if episodes.is_empty() {
+        return Vec::new();
+    }
+
+    let ignored: HashSet<String> = tokenize(show_name).into_iter().collect();
+    let documents: Vec<Vec<String>> = episodes.iter()
+        .map(|ep| {
+            // The title counts twice: it's short and usually names the episode's subject
+            let mut tokens = tokenize(&ep.title);
+            tokens.extend(tokenize(&ep.title));
+            tokens.extend(tokenize(ep.overview.as_deref().unwrap_or_default()));
+            tokens.retain(|t| !ignored.contains(t));
+            tokens
+        })
+        .collect();
+
+    // Document frequencies and BM25 IDF over the candidate episodes
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for tokens in &documents {
+        for token in tokens.iter().collect::<HashSet<_>>() {
+            *document_frequency.entry(token.as_str()).or_default() += 1;
+        }
+    }
+    let n = documents.len() as f32;
+    let idf = |token: &str| -> f32 {
+        let df = document_frequency.get(token).copied().unwrap_or(0) as f32;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    };
+    let average_length = documents.iter().map(|d| d.len()).sum::<usize>() as f32 / n;
+
+    let mut query: HashMap<String, usize> = HashMap::new();
+    for token in tokenize(transcript) {
+        if !ignored.contains(&token) {
+            *query.entry(token).or_default() += 1;
+        }
+    }
+
+    episodes.iter().zip(&documents)
+        .map(|(episode, tokens)| {
+            let mut term_frequency: HashMap<&str, usize> = HashMap::new();
+            for token in tokens {
+                *term_frequency.entry(token.as_str()).or_default() += 1;
+            }
+            let length_norm = 1.0 - B + B * tokens.len() as f32 / average_length.max(1.0);
+
+            let bm25: f32 = query.iter()
+                .filter_map(|(token, &query_count)| {
+                    let tf = *term_frequency.get(token.as_str())? as f32;
+                    let saturation = tf * (K1 + 1.0) / (tf + K1 * length_norm);
+                    Some(idf(token) * saturation * (1.0 + (query_count as f32).ln()))
+                })
+                .sum();
+
+            let names: f32 = proper_nouns(episode.overview.as_deref().unwrap_or_default())
+                .into_iter()
+                .filter(|name| !ignored.contains(name) && query.contains_key(name))
+                .map(|name| idf(&name))
+                .sum();
+
+            bm25 + NAME_WEIGHT * names
+        })
+        .collect()
+}
+
+/// Pick the episode that best matches a transcript, skipping `exclude_episode` (season, episode)
+pub fn match_transcript(
+    show_name: &str,
+    transcript: &str,
+    episodes: &[Episode],
+    exclude_episode: Option<(u32, u32)>,
+) -> Result<EpisodeMatch> {
+    let candidates: Vec<Episode> = episodes.iter()
+        .filter(|ep| exclude_episode != Some((ep.season, ep.episode)))
+        .cloned()
+        .collect();
+    if candidates.is_empty() {
+        return Err(anyhow::anyhow!("No episodes to match against"));
+    }
+
+    let scores = score_episodes(show_name, transcript, &candidates);
+    let (best_index, best) = scores.iter().copied().enumerate()
+        .fold((0, f32::MIN), |best, (i, score)| if score > best.1 { (i, score) } else { best });
+    if best <= 0.0 {
+        return Err(anyhow::anyhow!("Transcript has nothing in common with any episode overview"));
+    }
+    let runner_up = scores.iter().copied().enumerate()
+        .filter(|&(i, _)| i != best_index)
+        .map(|(_, score)| score)
+        .fold(0.0, f32::max);
+
+    let episode = &candidates[best_index];
+    Ok(EpisodeMatch {
+        season: episode.season,
+        episode: episode.episode,
+        title: episode.title.clone(),
+        confidence: confidence(best, runner_up),
+        match_method: "transcript".to_string(),
+    })
+}
+
+/// 0-100 confidence from the best score and the runner-up's
+///
+/// Both the margin over the runner-up (a close second means the transcript fits two episodes) and the
+/// strength of the best score (so a weak lone match doesn't look certain) have to be high.
+pub fn confidence(best: f32, runner_up: f32) -> f32 {
+    if best <= 0.0 {
+        return 0.0;
+    }
+    let margin = ((best - runner_up.max(0.0)) / best).clamp(0.0, 1.0);
+    let strength = 1.0 - (-best / STRONG_SCORE).exp();
+    ((margin * strength).sqrt() * 100.0).clamp(0.0, 100.0)
+}
+
+/// Lowercased words of three letters or more, without stopwords and with plurals folded
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(normalize)
+        .filter(|word| word.chars().count() >= 3 && !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
+
+fn normalize(word: &str) -> String {
+    let word = word.trim_matches('\'').to_lowercase();
+    let word = word.strip_suffix("'s").unwrap_or(&word);
+    if let Some(stem) = word.strip_suffix("ies").filter(|s| s.len() >= 3) {
+        format!("{}y", stem)
+    } else if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/// Capitalised words that don't start a sentence: character names, places and the like
+fn proper_nouns(text: &str) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut sentence_start = true;
+    for word in text.split_whitespace() {
+        let trimmed = word.trim_matches(|c: char| !c.is_alphanumeric());
+        if !sentence_start && trimmed.chars().next().is_some_and(|c| c.is_uppercase()) {
+            names.extend(tokenize(trimmed));
+        }
+        sentence_start = word.ends_with(['.', '!', '?', ':']);
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episode(episode: u32, title: &str, overview: &str) -> Episode {
+        Episode {
+            season: 1,
+            episode,
+            title: title.to_string(),
+            title_index: episode - 1,
+            runtime_minutes: Some(22),
+            overview: Some(overview.to_string()),
+        }
+    }
+
+    fn episodes() -> Vec<Episode> {
+        vec![
+            episode(1, "Space Pilot 3000", "Fry, a pizza delivery boy, is accidentally frozen on New Year's Eve 1999 and wakes up a thousand years later, where he meets Leela and the robot Bender."),
+            episode(2, "The Series Has Landed", "The crew makes its first delivery to the Moon, where Fry and Leela get lost at a lunar theme park and meet a grumpy gopher farmer."),
+            episode(3, "I, Roommate", "Fry moves in with Bender after the Professor's office floods, but Bender's tiny apartment is a closet, so they go looking for a bigger place."),
+        ]
+    }
+
+    // Subtitle dialogue from the second episode, as parse_srt_dialogue returns it
+    const MOON_SRT: &str = r#"1
+00:01:12,000 --> 00:01:15,000
+Welcome to the Moon, everybody!
+
+2
+00:01:16,500 --> 00:01:19,000
+<i>Leela, look, a lunar theme park!</i>
+
+3
+00:02:40,000 --> 00:02:44,000
+Get off my land! No gophers, no crew, no Fry!
+
+4
+00:02:45,000 --> 00:02:48,000
+The farmer's got a point about the gopher.
+"#;
+
+    #[test]
+    fn test_matches_subtitle_dialogue() {
+        let dialogue = crate::speech_match::parse_srt_dialogue(MOON_SRT);
+        let result = match_transcript("Futurama", &dialogue, &episodes(), None).unwrap();
+        assert_eq!((result.season, result.episode), (1, 2));
+        assert_eq!(result.title, "The Series Has Landed");
+        assert!(result.confidence >= 85.0, "confidence {}", result.confidence);
+    }
+
+    #[test]
+    fn test_matches_transcript_and_is_deterministic() {
+        let transcript = "[OPENING]: Where am I? It's the year three thousand. You were frozen, \
+            a cryogenic accident on New Year's Eve. [EARLY-MIDDLE]: Pizza delivery for I.C. Wiener.";
+        let first = match_transcript("Futurama", transcript, &episodes(), None).unwrap();
+        let second = match_transcript("Futurama", transcript, &episodes(), None).unwrap();
+        assert_eq!(first.episode, 1);
+        assert_eq!(first.confidence, second.confidence);
+        assert_eq!(score_episodes("Futurama", transcript, &episodes()), score_episodes("Futurama", transcript, &episodes()));
+    }
+
+    #[test]
+    fn test_exclude_episode() {
+        let dialogue = crate::speech_match::parse_srt_dialogue(MOON_SRT);
+        let result = match_transcript("Futurama", &dialogue, &episodes(), Some((1, 2))).unwrap();
+        assert_ne!(result.episode, 2);
+        assert!(result.confidence < 60.0, "confidence {}", result.confidence);
+
+        let only = vec![episodes()[1].clone()];
+        assert!(match_transcript("Futurama", &dialogue, &only, Some((1, 2))).is_err());
+    }
+
+    #[test]
+    fn test_unrelated_transcript() {
+        let result = match_transcript("Futurama", "Completely unrelated chatter about weather forecasts.", &episodes(), None);
+        assert!(result.is_err());
+        assert_eq!(confidence(0.0, 0.0), 0.0);
+        assert!(confidence(10.0, 9.5) < confidence(10.0, 1.0));
+        assert!(confidence(1.0, 0.0) < confidence(12.0, 0.0));
+    }
+
+    #[test]
+    fn test_tokenize_and_proper_nouns() {
+        assert_eq!(tokenize("The gophers' farm, Bender's closet!"), vec!["gopher", "farm", "bender", "closet"]);
+        let names = proper_nouns("Fry moves in with Bender. Later the Professor calls.");
+        assert!(names.contains("bender") && names.contains("professor"));
+        assert!(!names.contains("fry") && !names.contains("later"));
+    }
+}
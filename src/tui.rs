@@ -16,6 +16,7 @@ use std::io;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use crate::dvd_metadata::{MediaType, MetadataCandidate};
 use crate::ripper::RipProgress;
 
 #[derive(Debug, Clone)]
@@ -23,6 +24,7 @@ pub struct DriveState {
     pub device: String,
     pub progress: Option<RipProgress>,
     pub album_info: Option<String>,
+    pub session: Option<String>, // Box-set session progress, e.g. "disc 3 of 5, episodes 9–12"
     pub logs: Vec<String>,
 }
 
@@ -30,6 +32,7 @@ pub struct DriveState {
 pub enum InputMode {
     Normal,
     AwaitingTitleInput { device: String, default_title: Option<String> },
+    ChoosingMetadata { device: String, candidates: Vec<MetadataCandidate>, selected: usize },
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +43,7 @@ pub struct AppState {
     pub should_quit: bool,
     pub input_mode: InputMode,
     pub current_input: String,
+    pub chosen_candidate: Option<(String, Option<usize>)>, // Set when a metadata choice dialog closes: its device and the pick, None for "none of these"
 }
 
 impl Default for AppState {
@@ -51,6 +55,7 @@ impl Default for AppState {
             should_quit: false,
             input_mode: InputMode::Normal,
             current_input: String::new(),
+            chosen_candidate: None,
         }
     }
 }
@@ -75,11 +80,38 @@ impl AppState {
                 device: device.to_string(),
                 progress: None,
                 album_info: None,
+                session: None,
                 logs: vec![formatted],
             });
         }
     }
     
+    pub fn set_drive_session(&mut self, device: &str, session: Option<String>) {
+        if let Some(drive) = self.drives.iter_mut().find(|d| d.device == device) {
+            drive.session = session;
+        }
+    }
+    
+    /// Keys in the metadata choice dialog: arrows move, Enter picks the highlighted candidate, Esc picks none
+    pub fn handle_choice_key(&mut self, key: KeyCode) {
+        let InputMode::ChoosingMetadata { ref device, ref candidates, ref mut selected } = self.input_mode else {
+            return;
+        };
+        match key {
+            KeyCode::Up => *selected = selected.saturating_sub(1),
+            KeyCode::Down => *selected = (*selected + 1).min(candidates.len().saturating_sub(1)),
+            KeyCode::Enter => {
+                self.chosen_candidate = Some((device.clone(), Some(*selected)));
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Esc => {
+                self.chosen_candidate = Some((device.clone(), None));
+                self.input_mode = InputMode::Normal;
+            }
+            _ => {}
+        }
+    }
+    
     pub fn add_rsync_log(&mut self, message: String) {
         let formatted = format!("[{}] {}", chrono::Local::now().format("%H:%M:%S"), message);
         self.rsync_logs.push(formatted);
@@ -169,6 +201,9 @@ impl Tui {
                                     _ => {}
                                 }
                             }
+                            InputMode::ChoosingMetadata { .. } => {
+                                state.handle_choice_key(key.code);
+                            }
                         }
                     }
                 }
@@ -334,6 +369,9 @@ fn ui(f: &mut Frame, state: &AppState) {
         InputMode::AwaitingTitleInput { device, default_title } => {
             render_input_dialog(f, "TV Show Title", &format!("Enter title for {} (or press Enter to use default)", device), &state.current_input, default_title.as_deref());
         }
+        InputMode::ChoosingMetadata { device, candidates, selected } => {
+            render_choice_dialog(f, device, candidates, *selected);
+        }
         InputMode::Normal => {}
     }
 }
@@ -394,11 +432,14 @@ fn render_drive_with_log(f: &mut Frame, area: Rect, drive: &DriveState) {
         .split(area);
     
     // Render progress
-    let title = if let Some(ref info) = drive.album_info {
+    let mut title = if let Some(ref info) = drive.album_info {
         format!("{} - {}", drive.device, info)
     } else {
         drive.device.clone()
     };
+    if let Some(ref session) = drive.session {
+        title = format!("{} ({})", title, session);
+    }
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -568,6 +609,70 @@ fn render_input_dialog(f: &mut Frame, title: &str, prompt: &str, current_input:
     f.render_widget(help_widget, chunks[5]);
 }
 
+fn render_choice_dialog(f: &mut Frame, device: &str, candidates: &[MetadataCandidate], selected: usize) {
+    // Center the dialog: one line per candidate plus the highlighted candidate's overview
+    let area = f.area();
+    let dialog_width = 80.min(area.width - 4);
+    let dialog_height = (candidates.len() as u16 + 9).min(area.height);
+    
+    let dialog_area = Rect {
+        x: (area.width.saturating_sub(dialog_width)) / 2,
+        y: (area.height.saturating_sub(dialog_height)) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+    
+    f.render_widget(Block::default().style(Style::default().bg(Color::Black)), dialog_area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Which title is in {}?", device))
+        .style(Style::default().fg(Color::Yellow).bg(Color::Black));
+    let inner = block.inner(dialog_area);
+    f.render_widget(block, dialog_area);
+    
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(candidates.len() as u16),  // Candidates
+            Constraint::Length(1),                         // Spacer
+            Constraint::Min(3),                            // Overview
+            Constraint::Length(1),                         // Help text
+        ])
+        .split(inner);
+    
+    let items: Vec<ListItem> = candidates.iter().enumerate()
+        .map(|(idx, c)| {
+            let kind = if c.media_type == MediaType::TVShow { "TV" } else { "Movie" };
+            let line = format!(
+                "{} {} ({}) [{}]",
+                if idx == selected { ">" } else { " " },
+                c.title,
+                c.year.as_deref().unwrap_or("????"),
+                kind,
+            );
+            let style = if idx == selected {
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(line).style(style)
+        })
+        .collect();
+    f.render_widget(List::new(items), chunks[0]);
+    
+    let overview = candidates.get(selected)
+        .and_then(|c| c.overview.as_deref())
+        .unwrap_or("No overview");
+    let overview_widget = Paragraph::new(overview)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .style(Style::default().fg(Color::DarkGray));
+    f.render_widget(overview_widget, chunks[2]);
+    
+    let help_widget = Paragraph::new("↑/↓ to move, Enter to choose, Esc for none of these")
+        .style(Style::default().fg(Color::DarkGray));
+    f.render_widget(help_widget, chunks[3]);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -588,6 +693,7 @@ mod tests {
             device: "/dev/sr0".to_string(),
             progress: None,
             album_info: None,
+            session: None,
             logs: vec!["Test log".to_string()],
         };
         assert_eq!(drive.device, "/dev/sr0");
@@ -620,6 +726,17 @@ mod tests {
         assert_eq!(state.drives[1].device, "/dev/sr1");
     }
 
+    #[test]
+    fn test_set_drive_session() {
+        let mut state = AppState::default();
+        state.add_drive_log("/dev/sr0", "Inserted".to_string());
+        state.set_drive_session("/dev/sr0", Some("disc 3 of 5, episodes 9–12".to_string()));
+        state.set_drive_session("/dev/sr1", Some("ignored".to_string()));
+        
+        assert_eq!(state.drives.len(), 1);
+        assert_eq!(state.drives[0].session.as_deref(), Some("disc 3 of 5, episodes 9–12"));
+    }
+
     #[test]
     fn test_add_rsync_log() {
         let mut state = AppState::default();
@@ -685,6 +802,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_metadata_choice_keys() {
+        let candidate = |title: &str, year: &str| MetadataCandidate {
+            media_type: MediaType::TVShow,
+            id: title.to_string(),
+            title: title.to_string(),
+            year: Some(year.to_string()),
+            overview: None,
+            poster_url: None,
+            score: 1000,
+        };
+        let mut state = AppState {
+            input_mode: InputMode::ChoosingMetadata {
+                device: "/dev/sr0".to_string(),
+                candidates: vec![candidate("Battlestar Galactica", "2004"), candidate("Battlestar Galactica", "1978")],
+                selected: 0,
+            },
+            ..Default::default()
+        };
+        
+        state.handle_choice_key(KeyCode::Down);
+        state.handle_choice_key(KeyCode::Down); // Stays on the last candidate
+        assert!(matches!(state.input_mode, InputMode::ChoosingMetadata { selected: 1, .. }));
+        state.handle_choice_key(KeyCode::Enter);
+        assert!(matches!(state.input_mode, InputMode::Normal));
+        assert_eq!(state.chosen_candidate, Some(("/dev/sr0".to_string(), Some(1))));
+        
+        state.input_mode = InputMode::ChoosingMetadata {
+            device: "/dev/sr0".to_string(),
+            candidates: vec![candidate("The Office", "2005")],
+            selected: 0,
+        };
+        state.handle_choice_key(KeyCode::Esc);
+        assert!(matches!(state.input_mode, InputMode::Normal));
+        assert_eq!(state.chosen_candidate, Some(("/dev/sr0".to_string(), None)));
+    }
+
     #[test]
     fn test_log_timestamping() {
         let mut state = AppState::default();
@@ -15,6 +15,7 @@ fn create_test_state() -> ApiState {
         event_tx,
         db,
         operations: Arc::new(RwLock::new(std::collections::HashMap::new())),
+        metadata_choices: Arc::default(),
     }
 }
 
@@ -91,6 +92,8 @@ async fn test_api_event_serialization() {
             ApiEvent::OperationFailed { .. } => {}
             ApiEvent::AgentStatusChanged { .. } => {}
             ApiEvent::UpscalingJobStatusChanged { .. } => {}
+            ApiEvent::KnownDiscInserted { .. } => {}
+            ApiEvent::MetadataChoiceRequired { .. } => {}
         }
     }
 }
@@ -176,6 +179,7 @@ async fn test_rip_status_updates() {
             progress: 0.0,
             paused: false,
             paused_at: None,
+            control: Default::default(),
         });
     }
 
@@ -212,6 +216,7 @@ async fn test_status_update_event() {
         progress: 0.75,
         paused: false,
         paused_at: None,
+        control: Default::default(),
     });
     status.logs.push("Log 1".to_string());
     status.logs.push("Log 2".to_string());
@@ -267,6 +272,7 @@ async fn test_rip_status_serialization() {
         progress: 0.42,
         paused: false,
         paused_at: None,
+        control: Default::default(),
     });
     status.logs.push("Starting rip...".to_string());
     status.logs.push("Processing MKV...".to_string());
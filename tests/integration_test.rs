@@ -1,4 +1,7 @@
+use ripley::config::Config;
+use ripley::dvd_metadata::{self, EpisodeOrder, MediaType};
 use ripley::metadata;
+use ripley::metadata_provider;
 use ripley::ripper;
 use std::path::PathBuf;
 
@@ -126,4 +129,70 @@ mod tests {
         assert_eq!(statuses.len(), 6);
         assert_eq!(statuses[5], ripper::RipStatus::Error("test error".to_string()));
     }
+
+    /// Mock TMDB serving one show (with a DVD-order episode group) and one movie
+    async fn mock_tmdb() -> String {
+        use axum::{extract::Query, routing::get, Json, Router};
+        use std::collections::HashMap;
+
+        let app = Router::new()
+            .route("/3/search/tv", get(|Query(q): Query<HashMap<String, String>>| async move {
+                let results = if q["query"] == "Firefly" {
+                    serde_json::json!([
+                        { "id": 2, "name": "Firefly Lane", "first_air_date": "2021-02-03", "popularity": 90.0 },
+                        { "id": 1437, "name": "Firefly", "first_air_date": "2002-09-20", "popularity": 40.0 },
+                    ])
+                } else {
+                    serde_json::json!([])
+                };
+                Json(serde_json::json!({ "results": results }))
+            }))
+            .route("/3/search/movie", get(|| async {
+                Json(serde_json::json!({ "results": [{ "id": 16320, "title": "Serenity", "release_date": "2005-09-30" }] }))
+            }))
+            .route("/3/tv/1437/season/1", get(|| async {
+                Json(serde_json::json!({ "episodes": [
+                    { "episode_number": 1, "name": "The Train Job", "runtime": 43 },
+                    { "episode_number": 11, "name": "Serenity", "runtime": 86 },
+                ]}))
+            }))
+            .route("/3/tv/1437/episode_groups", get(|| async {
+                Json(serde_json::json!({ "results": [{ "id": "dvd", "type": 3, "episode_count": 2 }] }))
+            }))
+            .route("/3/tv/episode_group/dvd", get(|| async {
+                Json(serde_json::json!({ "groups": [{ "order": 1, "episodes": [
+                    { "name": "Serenity", "order": 0, "runtime": 86 },
+                    { "name": "The Train Job", "order": 1, "runtime": 43 },
+                ]}]}))
+            }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/3", addr)
+    }
+
+    #[tokio::test]
+    async fn test_dvd_metadata_from_mock_tmdb() {
+        let mut config = Config::default();
+        config.metadata.tmdb.base_url = Some(mock_tmdb().await);
+        config.metadata.tmdb.api_key = Some("test".to_string());
+        let provider = metadata_provider::video_provider_from_config(&config).unwrap();
+
+        let show = dvd_metadata::fetch_dvd_metadata_with_episode(provider.as_ref(), Some("FIREFLY_DISC_1"), Some(1), None, EpisodeOrder::Aired)
+            .await
+            .unwrap();
+        assert_eq!((show.title.as_str(), show.year.as_deref()), ("Firefly", Some("2002")));
+        assert_eq!(show.media_type, MediaType::TVShow);
+        assert_eq!(show.episodes.iter().map(|e| e.episode).collect::<Vec<_>>(), vec![1, 11]);
+
+        let dvd = dvd_metadata::fetch_dvd_metadata_with_episode(provider.as_ref(), Some("Firefly"), Some(1), None, EpisodeOrder::Dvd)
+            .await
+            .unwrap();
+        assert_eq!((dvd.episodes[0].episode, dvd.episodes[0].title.as_str()), (1, "Serenity"));
+
+        let movie = dvd_metadata::fetch_dvd_metadata_with_episode(provider.as_ref(), Some("SERENITY"), None, None, EpisodeOrder::Aired)
+            .await
+            .unwrap();
+        assert_eq!((movie.title.as_str(), movie.media_type), ("Serenity", MediaType::Movie));
+    }
 }
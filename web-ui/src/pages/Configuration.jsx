@@ -29,7 +29,7 @@ import ConfirmModal from '../components/ConfirmModal';
 // Default configuration values matching backend defaults
 const DEFAULT_CONFIG = {
   openai_api_key: null,
-  tmdb_api_key: 'fef1285fb85a74350b3292b5fac37fce',
+  tmdb_api_key: null,
   notifications: {
     enabled: true,
     topic: 'staryavsky_alerts',
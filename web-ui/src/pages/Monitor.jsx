@@ -422,6 +422,11 @@ export default function Monitor() {
                           </span>
                         </div>
                         <p className="text-slate-300 text-sm">{operation.message}</p>
+                        {operation.session && (
+                          <p className="text-cyan-400 text-xs mt-1">
+                            {operation.session.show_name} S{String(operation.session.season).padStart(2, '0')}: {operation.session.summary}
+                          </p>
+                        )}
                         {operation.drive && (
                           <p className="text-slate-400 text-xs mt-1">
                             Drive: {operation.drive}